@@ -0,0 +1,294 @@
+//! Headless `stocks` CLI. It reads and writes the same config dir and stores
+//! as the desktop app, so scripts and cron jobs can manage the watchlist and
+//! warm the caches without opening a window.
+
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use tauri_appy_lib::connectivity::ConnectivityMonitor;
+use tauri_appy_lib::domain::{
+    AppError, QuoteSummary, SymbolPerformance, TimeRange, WatchlistItem, normalize_symbol,
+    set_locale,
+};
+use tauri_appy_lib::persistence::{SettingsStore, default_config_dir};
+use tauri_appy_lib::services::{
+    CacheService, MarketDataService, SettingsService, WatchlistService,
+};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "stocks",
+    version,
+    about = "Stocks watchlist, quotes and history"
+)]
+struct Cli {
+    /// Print JSON instead of text.
+    #[arg(long, global = true)]
+    json: bool,
+    /// Config dir to use instead of the desktop app's (or set
+    /// `STOCKS_CONFIG_DIR`).
+    #[arg(long, global = true)]
+    config_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Show or edit the watchlist.
+    #[command(subcommand)]
+    Watchlist(WatchlistCommand),
+    /// Refresh quotes for the watchlist.
+    Quotes,
+    /// Show price history for a symbol.
+    History(HistoryArgs),
+    /// Manage the quote and chart caches.
+    #[command(subcommand)]
+    Cache(CacheCommand),
+    /// Check the saved settings.
+    #[command(subcommand)]
+    Settings(SettingsCommand),
+}
+
+#[derive(Debug, Subcommand)]
+enum WatchlistCommand {
+    /// List watched symbols.
+    List,
+    /// Add a symbol.
+    Add { symbol: String },
+    /// Remove a symbol.
+    Remove { symbol: String },
+}
+
+#[derive(Debug, Args)]
+struct HistoryArgs {
+    symbol: String,
+    /// One of 1D, 1W, 1M, 3M, 1Y.
+    #[arg(long, default_value = "1M", value_parser = parse_range)]
+    range: TimeRange,
+    /// Fetch from the provider even when the cache is fresh.
+    #[arg(long)]
+    refresh: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheCommand {
+    /// Delete cached quotes and chart data.
+    Clear,
+}
+
+#[derive(Debug, Subcommand)]
+enum SettingsCommand {
+    /// Send a test request with the saved API key.
+    Test,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            eprintln!("error: unable to start the async runtime: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match runtime.block_on(run(&cli)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            if !cli.json || print_json(&err).is_err() {
+                eprintln!("error: {} ({})", err.message, err.code);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: &Cli) -> Result<(), AppError> {
+    let config_dir = match &cli.config_dir {
+        Some(dir) => dir.clone(),
+        None => default_config_dir()?,
+    };
+    let settings = SettingsStore::new(config_dir.clone()).load();
+    if let Ok(settings) = &settings {
+        set_locale(settings.locale);
+    }
+    let monitor = ConnectivityMonitor::new();
+
+    match &cli.command {
+        Command::Watchlist(command) => {
+            let service = WatchlistService::from_config_dir(&config_dir);
+            let watchlist = match command {
+                WatchlistCommand::List => service.list()?,
+                WatchlistCommand::Add { symbol } => service.add(symbol)?,
+                WatchlistCommand::Remove { symbol } => service.remove(symbol)?,
+            };
+            output(cli.json, watchlist.as_slice(), print_watchlist)?;
+        }
+        Command::Quotes => {
+            let quotes = MarketDataService::from_config_dir(&config_dir, &monitor)?
+                .refresh_quotes(&settings?)
+                .await?;
+            output(cli.json, quotes.as_slice(), print_quotes)?;
+        }
+        Command::History(args) => {
+            let symbol = normalize_symbol(&args.symbol)?;
+            let performance = MarketDataService::from_config_dir(&config_dir, &monitor)?
+                .load_symbol_performance(&settings?, &symbol, args.range, args.refresh)
+                .await?;
+            output(cli.json, &performance, print_history)?;
+        }
+        Command::Cache(CacheCommand::Clear) => {
+            CacheService::from_config_dir(&config_dir).clear()?;
+            if !cli.json {
+                println!("Cache cleared.");
+            }
+        }
+        Command::Settings(SettingsCommand::Test) => {
+            let result = SettingsService::from_config_dir(&config_dir)?
+                .test_connection()
+                .await?;
+            if !result.ok {
+                return Err(AppError::provider("provider_error").with_arg("detail", result.message));
+            }
+            output(cli.json, &result, |result| println!("{}", result.message))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_range(value: &str) -> Result<TimeRange, String> {
+    TimeRange::ALL
+        .into_iter()
+        .find(|range| range.as_key().eq_ignore_ascii_case(value))
+        .ok_or_else(|| {
+            let keys: Vec<&str> = TimeRange::ALL.iter().map(TimeRange::as_key).collect();
+            format!("expected one of {}", keys.join(", "))
+        })
+}
+
+fn output<T: Serialize + ?Sized>(
+    json: bool,
+    value: &T,
+    print_text: fn(&T),
+) -> Result<(), AppError> {
+    if json {
+        return print_json(value);
+    }
+    print_text(value);
+    Ok(())
+}
+
+/// Fails without printing anything when `value` cannot be serialized.
+fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| AppError::internal("cli_output_failed").with_arg("detail", err))?;
+    println!("{json}");
+    Ok(())
+}
+
+fn print_watchlist(watchlist: &[WatchlistItem]) {
+    for item in watchlist {
+        println!("{}", item.symbol);
+    }
+}
+
+fn print_quotes(quotes: &[QuoteSummary]) {
+    println!("{:<10} {:>12} {:>9}  STATUS", "SYMBOL", "PRICE", "CHANGE");
+    for quote in quotes {
+        let change = quote
+            .change_pct
+            .map(|pct| format!("{pct:+.2}%"))
+            .unwrap_or_else(|| "-".to_string());
+        let status = format!("{:?}", quote.status).to_lowercase();
+        let status = match &quote.error_message {
+            Some(message) => format!("{status}: {message}"),
+            None => status,
+        };
+        println!(
+            "{:<10} {:>12.2} {:>9}  {status}",
+            quote.symbol, quote.price, change
+        );
+    }
+}
+
+fn print_history(performance: &SymbolPerformance) {
+    for point in &performance.points {
+        println!("{}  {:.2}", point.ts, point.close);
+    }
+    let change_pct = if performance.start != 0.0 {
+        (performance.end - performance.start) / performance.start * 100.0
+    } else {
+        0.0
+    };
+    println!(
+        "{} {}: {:.2} -> {:.2} ({change_pct:+.2}%), low {:.2}, high {:.2}",
+        performance.symbol,
+        performance.range.as_key(),
+        performance.start,
+        performance.end,
+        performance.min,
+        performance.max
+    );
+    if let Some(message) = &performance.error_message {
+        println!("Showing cached data ({message}).");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn history_arguments_parse_with_defaults() {
+        let cli = Cli::try_parse_from(["stocks", "history", "aapl"]).unwrap();
+        let Command::History(args) = cli.command else {
+            panic!("expected the history command");
+        };
+        assert_eq!(args.symbol, "aapl");
+        assert_eq!(args.range, TimeRange::OneMonth);
+        assert!(!args.refresh && !cli.json && cli.config_dir.is_none());
+
+        let cli = Cli::try_parse_from([
+            "stocks",
+            "history",
+            "MSFT",
+            "--range",
+            "3m",
+            "--refresh",
+            "--json",
+            "--config-dir",
+            "/tmp/stocks",
+        ])
+        .unwrap();
+        let Command::History(args) = cli.command else {
+            panic!("expected the history command");
+        };
+        assert_eq!(args.range, TimeRange::ThreeMonths);
+        assert!(args.refresh && cli.json);
+        assert_eq!(cli.config_dir, Some(PathBuf::from("/tmp/stocks")));
+    }
+
+    #[test]
+    fn unknown_ranges_and_commands_are_rejected() {
+        let err = Cli::try_parse_from(["stocks", "history", "AAPL", "--range", "5Y"]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expected one of 1D, 1W, 1M, 3M, 1Y")
+        );
+        assert!(Cli::try_parse_from(["stocks", "watchlist", "rename"]).is_err());
+        assert!(Cli::try_parse_from(["stocks"]).is_err());
+    }
+
+    #[test]
+    fn unserializable_output_is_an_error() {
+        let value = HashMap::from([((1, 2), 3)]);
+        assert_eq!(print_json(&value).unwrap_err().code, "cli_output_failed");
+    }
+}
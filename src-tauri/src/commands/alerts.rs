@@ -0,0 +1,159 @@
+use crate::domain::{
+    AlertEvent, AlertRule, AlertRuleInput, AppError, AppSettings, QuoteSummary, now_rfc3339,
+    validate_alert_rule,
+};
+use crate::notifications::{DesktopNotificationSink, notify_alerts};
+use crate::observability::CommandSpan;
+use crate::persistence::AlertStore;
+use crate::services::AlertService;
+use tauri::{AppHandle, Emitter};
+
+/// Event emitted to the frontend once per triggered alert.
+pub const ALERT_TRIGGERED_EVENT: &str = "alert-triggered";
+
+#[tauri::command]
+pub fn get_alert_rules(app: AppHandle) -> Result<Vec<AlertRule>, AppError> {
+    let span = CommandSpan::start("get_alert_rules", &[]);
+    let result = AlertStore::from_app(&app).and_then(|store| store.load_rules());
+    match result {
+        Ok(rules) => {
+            span.ok(&[("rule_count", rules.len().to_string())]);
+            Ok(rules)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn add_alert_rule(app: AppHandle, rule: AlertRuleInput) -> Result<Vec<AlertRule>, AppError> {
+    let span = CommandSpan::start("add_alert_rule", &[("symbol", rule.symbol.clone())]);
+    let result = (|| -> Result<Vec<AlertRule>, AppError> {
+        let validated = validate_alert_rule(uuid::Uuid::new_v4().to_string(), rule, now_rfc3339())?;
+        let store = AlertStore::from_app(&app)?;
+        let mut rules = store.load_rules()?;
+        rules.push(validated);
+        store.save_rules(&rules)
+    })();
+    match result {
+        Ok(rules) => {
+            span.ok(&[("rule_count", rules.len().to_string())]);
+            Ok(rules)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Replaces a rule's settings. The rule keeps its id and creation time but its
+/// trigger state starts over.
+#[tauri::command]
+pub fn update_alert_rule(
+    app: AppHandle,
+    id: String,
+    rule: AlertRuleInput,
+) -> Result<Vec<AlertRule>, AppError> {
+    let span = CommandSpan::start("update_alert_rule", &[("id", id.clone())]);
+    let result = (|| -> Result<Vec<AlertRule>, AppError> {
+        let store = AlertStore::from_app(&app)?;
+        let mut rules = store.load_rules()?;
+        let existing = rules
+            .iter_mut()
+            .find(|existing| existing.id == id)
+            .ok_or_else(|| alert_not_found(&id))?;
+
+        *existing = validate_alert_rule(id.clone(), rule, existing.created_at.clone())?;
+        store.save_rules(&rules)
+    })();
+    match result {
+        Ok(rules) => {
+            span.ok(&[("rule_count", rules.len().to_string())]);
+            Ok(rules)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn delete_alert_rule(app: AppHandle, id: String) -> Result<Vec<AlertRule>, AppError> {
+    let span = CommandSpan::start("delete_alert_rule", &[("id", id.clone())]);
+    let result = (|| -> Result<Vec<AlertRule>, AppError> {
+        let store = AlertStore::from_app(&app)?;
+        let mut rules = store.load_rules()?;
+        let initial_len = rules.len();
+        rules.retain(|rule| rule.id != id);
+        if rules.len() == initial_len {
+            return Err(alert_not_found(&id));
+        }
+
+        store.save_rules(&rules)
+    })();
+    match result {
+        Ok(rules) => {
+            span.ok(&[("rule_count", rules.len().to_string())]);
+            Ok(rules)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_alert_history(app: AppHandle) -> Result<Vec<AlertEvent>, AppError> {
+    let span = CommandSpan::start("get_alert_history", &[]);
+    let result = AlertStore::from_app(&app).and_then(|store| store.load_history());
+    match result {
+        Ok(history) => {
+            span.ok(&[("event_count", history.len().to_string())]);
+            Ok(history)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Evaluates the saved rules against a quote refresh, records triggered alerts
+/// in the history, emits one `ALERT_TRIGGERED_EVENT` per alert and shows
+/// desktop notifications for them. Notification failures are logged by
+/// `notify_alerts` and do not fail the call.
+pub async fn process_alerts(
+    app: &AppHandle,
+    settings: &AppSettings,
+    quotes: &[QuoteSummary],
+) -> Result<Vec<AlertEvent>, AppError> {
+    let (rules, events) = AlertService::from_app(app)?
+        .evaluate(settings, quotes)
+        .await?;
+    if events.is_empty() {
+        return Ok(events);
+    }
+
+    for event in &events {
+        app.emit(ALERT_TRIGGERED_EVENT, event)
+            .map_err(|err| AppError::internal("alert_emit_failed").with_arg("detail", err))?;
+    }
+
+    notify_alerts(
+        &DesktopNotificationSink::new(app),
+        settings,
+        &rules,
+        &events,
+        chrono::Local::now().time(),
+    );
+    Ok(events)
+}
+
+fn alert_not_found(id: &str) -> AppError {
+    AppError::validation("alert_not_found").with_arg("id", id)
+}
@@ -0,0 +1,369 @@
+use crate::commands::portfolio::load_portfolio_performance;
+use crate::domain::{
+    AppError, BenchmarkComparison, ComparisonSeries, CorrelationMatrix, GapFill, QuoteStatus,
+    SeriesSource, SymbolAnalytics, TimeRange, build_comparison, build_correlation_matrix,
+    compare_to_benchmark, compute_symbol_analytics, normalize_symbol, validate_correlation_range,
+};
+use crate::indicators::{IndicatorSet, IndicatorSpec, build_indicator_set, validate_specs};
+use crate::observability::CommandSpan;
+use crate::persistence::{SettingsStore, TimeSeriesCacheStore, WatchlistStore};
+use crate::services::MarketDataService;
+use tauri::AppHandle;
+
+const PORTFOLIO_SUBJECT: &str = "PORTFOLIO";
+const MAX_COMPARISON_SYMBOLS: usize = 10;
+
+/// Compares `symbol`, or the portfolio when no symbol is given, against
+/// `benchmark`. The portfolio is compared through its flow-adjusted growth
+/// index so deposits and withdrawals do not skew the result.
+#[tauri::command]
+pub async fn get_benchmark_comparison(
+    app: AppHandle,
+    symbol: Option<String>,
+    benchmark: String,
+    range: TimeRange,
+) -> Result<BenchmarkComparison, AppError> {
+    let span = CommandSpan::start(
+        "get_benchmark_comparison",
+        &[
+            (
+                "symbol",
+                symbol
+                    .clone()
+                    .unwrap_or_else(|| PORTFOLIO_SUBJECT.to_string()),
+            ),
+            ("benchmark", benchmark.clone()),
+            ("range", range.as_key().to_string()),
+        ],
+    );
+    let result = async {
+        let benchmark_symbol = normalize_symbol(&benchmark)?;
+        let subject_symbol = symbol.as_deref().map(normalize_symbol).transpose()?;
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        let market_data = MarketDataService::from_app(&app)?;
+
+        let benchmark_performance = market_data
+            .load_symbol_performance(&settings, &benchmark_symbol, range, false)
+            .await?;
+
+        let (subject, subject_points, subject_status) = match subject_symbol {
+            Some(subject_symbol) => {
+                let performance = market_data
+                    .load_symbol_performance(&settings, &subject_symbol, range, false)
+                    .await?;
+                (subject_symbol, performance.points, performance.status)
+            }
+            None => {
+                let performance = load_portfolio_performance(&app, &settings, range).await?;
+                (
+                    PORTFOLIO_SUBJECT.to_string(),
+                    performance.growth_index(),
+                    performance.status,
+                )
+            }
+        };
+
+        let status = if subject_status == QuoteStatus::Fresh
+            && benchmark_performance.status == QuoteStatus::Fresh
+        {
+            QuoteStatus::Fresh
+        } else {
+            QuoteStatus::Stale
+        };
+
+        compare_to_benchmark(
+            &subject,
+            &subject_points,
+            &benchmark_symbol,
+            &benchmark_performance.points,
+            range,
+            status,
+        )
+        .ok_or_else(|| {
+            AppError::validation("insufficient_overlap")
+                .with_arg("subject", &subject)
+                .with_arg("benchmark", &benchmark_symbol)
+        })
+    }
+    .await;
+
+    match result {
+        Ok(comparison) => {
+            span.ok(&[
+                ("points", comparison.points.len().to_string()),
+                ("status", format!("{:?}", comparison.status)),
+            ]);
+            Ok(comparison)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_comparison(
+    app: AppHandle,
+    symbols: Vec<String>,
+    range: TimeRange,
+    gap_fill: Option<GapFill>,
+) -> Result<ComparisonSeries, AppError> {
+    let gap_fill = gap_fill.unwrap_or_default();
+    let span = CommandSpan::start(
+        "get_comparison",
+        &[
+            ("symbol_count", symbols.len().to_string()),
+            ("range", range.as_key().to_string()),
+            ("gap_fill", format!("{gap_fill:?}")),
+        ],
+    );
+    let result = async {
+        let mut normalized = Vec::with_capacity(symbols.len());
+        for symbol in &symbols {
+            let symbol = normalize_symbol(symbol)?;
+            if !normalized.contains(&symbol) {
+                normalized.push(symbol);
+            }
+        }
+
+        if normalized.is_empty() || normalized.len() > MAX_COMPARISON_SYMBOLS {
+            return Err(AppError::validation("invalid_comparison")
+                .with_reason("symbol_count")
+                .with_arg("max", MAX_COMPARISON_SYMBOLS));
+        }
+
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        let market_data = MarketDataService::from_app(&app)?;
+
+        // A symbol without data is listed as missing, but failures that hit
+        // every symbol are returned, as is the last failure when no symbol
+        // loaded at all.
+        let mut series = Vec::with_capacity(normalized.len());
+        let mut missing_symbols = Vec::new();
+        let mut last_error = None;
+        for symbol in normalized {
+            match market_data
+                .load_symbol_performance(&settings, &symbol, range, false)
+                .await
+            {
+                Ok(performance) => series.push(performance),
+                Err(err) if err.is_symbol_scoped() => {
+                    missing_symbols.push(symbol);
+                    last_error = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        if series.is_empty()
+            && let Some(err) = last_error
+        {
+            return Err(err);
+        }
+
+        Ok(build_comparison(range, &series, gap_fill, missing_symbols))
+    }
+    .await;
+
+    match result {
+        Ok(comparison) => {
+            span.ok(&[
+                ("points", comparison.timestamps.len().to_string()),
+                (
+                    "missing_count",
+                    comparison.missing_symbols.len().to_string(),
+                ),
+            ]);
+            Ok(comparison)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_indicators(
+    app: AppHandle,
+    symbol: String,
+    range: TimeRange,
+    specs: Vec<IndicatorSpec>,
+) -> Result<IndicatorSet, AppError> {
+    let span = CommandSpan::start(
+        "get_indicators",
+        &[
+            ("symbol", symbol.clone()),
+            ("range", range.as_key().to_string()),
+            ("spec_count", specs.len().to_string()),
+        ],
+    );
+    let result = async {
+        let normalized_symbol = normalize_symbol(&symbol)?;
+        validate_specs(&specs)?;
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        let market_data = MarketDataService::from_app(&app)?;
+
+        let performance = market_data
+            .load_symbol_performance(&settings, &normalized_symbol, range, false)
+            .await?;
+        let history = TimeSeriesCacheStore::from_app(&app)?
+            .load(&normalized_symbol, range.interval())?
+            .map(|entry| entry.points)
+            .unwrap_or_default();
+
+        Ok(build_indicator_set(&performance, &history, &specs))
+    }
+    .await;
+
+    match result {
+        Ok(set) => {
+            span.ok(&[
+                ("points", set.timestamps.len().to_string()),
+                ("status", format!("{:?}", set.status)),
+            ]);
+            Ok(set)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_symbol_analytics(
+    app: AppHandle,
+    symbol: String,
+    range: TimeRange,
+) -> Result<SymbolAnalytics, AppError> {
+    let span = CommandSpan::start(
+        "get_symbol_analytics",
+        &[
+            ("symbol", symbol.clone()),
+            ("range", range.as_key().to_string()),
+        ],
+    );
+    let result = async {
+        let normalized_symbol = normalize_symbol(&symbol)?;
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        let market_data = MarketDataService::from_app(&app)?;
+
+        let performance = market_data
+            .load_symbol_performance(&settings, &normalized_symbol, range, false)
+            .await?;
+        Ok(compute_symbol_analytics(
+            &performance,
+            settings.risk_free_rate_pct,
+        ))
+    }
+    .await;
+
+    match result {
+        Ok(analytics) => {
+            span.ok(&[("status", format!("{:?}", analytics.status))]);
+            Ok(analytics)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Correlates `symbols`, or the saved watchlist when none are given.
+#[tauri::command]
+pub async fn get_correlation_matrix(
+    app: AppHandle,
+    range: TimeRange,
+    symbols: Option<Vec<String>>,
+) -> Result<CorrelationMatrix, AppError> {
+    let span = CommandSpan::start(
+        "get_correlation_matrix",
+        &[("range", range.as_key().to_string())],
+    );
+    let result = async {
+        let requested = match symbols {
+            Some(symbols) => symbols,
+            None => WatchlistStore::from_app(&app)?
+                .load()?
+                .into_iter()
+                .map(|item| item.symbol)
+                .collect(),
+        };
+
+        let mut normalized: Vec<String> = Vec::with_capacity(requested.len());
+        for symbol in &requested {
+            let symbol = normalize_symbol(symbol)?;
+            if !normalized.contains(&symbol) {
+                normalized.push(symbol);
+            }
+        }
+
+        if normalized.len() < 2 {
+            return Err(
+                AppError::validation("invalid_comparison").with_reason("correlation_symbols")
+            );
+        }
+        validate_correlation_range(range)?;
+
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        let market_data = MarketDataService::from_app(&app)?;
+
+        // Same policy as `get_comparison`, except that a matrix needs at
+        // least two loaded series.
+        let mut series = Vec::with_capacity(normalized.len());
+        let mut fetched_symbols = Vec::new();
+        let mut missing_symbols = Vec::new();
+        let mut last_error = None;
+        for symbol in normalized {
+            match market_data
+                .load_symbol_performance_with_source(&settings, &symbol, range, false)
+                .await
+            {
+                Ok((performance, source)) => {
+                    if source == SeriesSource::Fetched {
+                        fetched_symbols.push(symbol);
+                    }
+                    series.push(performance);
+                }
+                Err(err) if err.is_symbol_scoped() => {
+                    missing_symbols.push(symbol);
+                    last_error = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        if series.len() < 2
+            && let Some(err) = last_error
+        {
+            return Err(err);
+        }
+
+        Ok(build_correlation_matrix(
+            range,
+            &series,
+            fetched_symbols,
+            missing_symbols,
+        ))
+    }
+    .await;
+
+    match result {
+        Ok(matrix) => {
+            span.ok(&[
+                ("symbol_count", matrix.symbols.len().to_string()),
+                ("fetched_count", matrix.fetched_symbols.len().to_string()),
+                (
+                    "insufficient_count",
+                    matrix.insufficient_overlap.len().to_string(),
+                ),
+            ]);
+            Ok(matrix)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::connectivity::ConnectivityMonitor;
+use crate::domain::{AppError, ConnectivityStatus, unix_timestamp_secs};
+use crate::observability::CommandSpan;
+use crate::persistence::SettingsStore;
+use tauri::{AppHandle, Manager};
+
+#[tauri::command]
+pub fn get_connectivity_status(app: AppHandle) -> Result<ConnectivityStatus, AppError> {
+    let span = CommandSpan::start("get_connectivity_status", &[]);
+    let result = (|| -> Result<ConnectivityStatus, AppError> {
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        let monitor = app.state::<ConnectivityMonitor>();
+        Ok(monitor.status(settings.offline_mode, unix_timestamp_secs()))
+    })();
+
+    match result {
+        Ok(status) => {
+            span.ok(&[("offline", status.offline.to_string())]);
+            Ok(status)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Turning offline mode off also clears any automatic offline state so the
+/// next refresh goes straight to the network.
+#[tauri::command]
+pub fn set_offline_mode(app: AppHandle, enabled: bool) -> Result<ConnectivityStatus, AppError> {
+    let span = CommandSpan::start("set_offline_mode", &[("enabled", enabled.to_string())]);
+    let result = (|| -> Result<ConnectivityStatus, AppError> {
+        let settings = SettingsStore::from_app(&app)?.set_offline_mode(enabled)?;
+        let monitor = app.state::<ConnectivityMonitor>();
+        if !enabled {
+            monitor.reset();
+        }
+        Ok(monitor.status(settings.offline_mode, unix_timestamp_secs()))
+    })();
+
+    match result {
+        Ok(status) => {
+            span.ok(&[("offline", status.offline.to_string())]);
+            Ok(status)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
@@ -1,36 +1,21 @@
-use crate::domain::AppError;
-use crate::observability::CommandSpan;
+use crate::diagnostics::{DiagnosticsInputs, build_diagnostics_bundle, collect_recent_logs};
+use crate::domain::{
+    AppError, CacheInvalidationResult, CacheStats, DiagnosticsExport, MetricsSnapshot, TimeRange,
+    normalize_symbol, now_rfc3339,
+};
+use crate::observability::{CommandSpan, metrics, write_prometheus_file};
+use crate::persistence::{QuoteCacheStore, SettingsStore, TimeSeriesCacheStore, WatchlistStore};
+use crate::services::CacheService;
 use std::fs;
+use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
 
-const CACHE_DIR_NAME: &str = "cache";
+const PROMETHEUS_FILE_NAME: &str = "metrics.prom";
 
 #[tauri::command]
 pub fn clear_cache(app: AppHandle) -> Result<(), AppError> {
     let span = CommandSpan::start("clear_cache", &[]);
-    let result = (|| -> Result<(), AppError> {
-        let cache_dir = app
-            .path()
-            .app_config_dir()
-            .map_err(|err| {
-                AppError::persistence(
-                    "cache_path_resolution_failed",
-                    format!("Unable to resolve cache directory: {err}"),
-                )
-            })?
-            .join(CACHE_DIR_NAME);
-
-        if cache_dir.exists() {
-            fs::remove_dir_all(&cache_dir).map_err(|err| {
-                AppError::persistence(
-                    "cache_clear_failed",
-                    format!("Unable to clear cache directory: {err}"),
-                )
-            })?;
-        }
-
-        Ok(())
-    })();
+    let result = CacheService::from_app(&app).and_then(|service| service.clear());
 
     match result {
         Ok(()) => {
@@ -43,3 +28,241 @@ pub fn clear_cache(app: AppHandle) -> Result<(), AppError> {
         }
     }
 }
+
+#[tauri::command]
+pub fn get_cache_stats(app: AppHandle) -> Result<CacheStats, AppError> {
+    let span = CommandSpan::start("get_cache_stats", &[]);
+    let result = (|| -> Result<CacheStats, AppError> {
+        Ok(CacheStats {
+            quotes: QuoteCacheStore::from_app(&app)?.stats()?,
+            timeseries: TimeSeriesCacheStore::from_app(&app)?.stats()?,
+        })
+    })();
+
+    match result {
+        Ok(stats) => {
+            span.ok(&[
+                ("quote_entries", stats.quotes.entry_count.to_string()),
+                (
+                    "timeseries_entries",
+                    stats.timeseries.entry_count.to_string(),
+                ),
+            ]);
+            Ok(stats)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Returns the in-process metrics. With `write_prometheus` the snapshot is
+/// also written in Prometheus text format to the log directory, for a
+/// textfile collector to pick up.
+#[tauri::command]
+pub fn get_metrics(
+    app: AppHandle,
+    write_prometheus: Option<bool>,
+) -> Result<MetricsSnapshot, AppError> {
+    let write_prometheus = write_prometheus.unwrap_or(false);
+    let span = CommandSpan::start(
+        "get_metrics",
+        &[("write_prometheus", write_prometheus.to_string())],
+    );
+    let result = (|| -> Result<MetricsSnapshot, AppError> {
+        let mut snapshot = metrics().snapshot(now_rfc3339());
+        if write_prometheus {
+            let path = app
+                .path()
+                .app_log_dir()
+                .map_err(|err| {
+                    AppError::persistence("metrics_path_resolution_failed").with_arg("detail", err)
+                })?
+                .join(PROMETHEUS_FILE_NAME);
+            write_prometheus_file(&path, &snapshot)?;
+            snapshot.prometheus_file = Some(path.display().to_string());
+        }
+        Ok(snapshot)
+    })();
+
+    match result {
+        Ok(snapshot) => {
+            span.ok(&[("command_count", snapshot.commands.len().to_string())]);
+            Ok(snapshot)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Writes a zip with everything needed to debug a report (version, redacted
+/// settings, watchlist, cache stats, recent logs, provider errors and
+/// metrics) to `path`, which must be an absolute path ending in `.zip`.
+#[tauri::command]
+pub fn export_diagnostics(app: AppHandle, path: String) -> Result<DiagnosticsExport, AppError> {
+    let span = CommandSpan::start("export_diagnostics", &[]);
+    let result = (|| -> Result<DiagnosticsExport, AppError> {
+        let target = PathBuf::from(path.trim());
+        let is_zip = target
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"));
+        if !target.is_absolute() || !is_zip {
+            return Err(AppError::validation("invalid_diagnostics_path"));
+        }
+
+        let log_dir = app.path().app_log_dir().map_err(|err| {
+            AppError::persistence("log_path_resolution_failed").with_arg("detail", err)
+        })?;
+        let inputs = DiagnosticsInputs {
+            app_version: app.package_info().version.to_string(),
+            settings: SettingsStore::from_app(&app)?.load()?,
+            watchlist: WatchlistStore::from_app(&app)?.load()?,
+            cache_stats: CacheStats {
+                quotes: QuoteCacheStore::from_app(&app)?.stats()?,
+                timeseries: TimeSeriesCacheStore::from_app(&app)?.stats()?,
+            },
+            provider_errors: metrics().recent_provider_errors(),
+            metrics: metrics().snapshot(now_rfc3339()),
+            logs: collect_recent_logs(&log_dir)?,
+        };
+
+        let (bytes, entries) = build_diagnostics_bundle(&inputs)?;
+        fs::write(&target, &bytes).map_err(|err| {
+            AppError::persistence("diagnostics_write_failed")
+                .with_arg("detail", err)
+                .with_arg("file", target.display())
+        })?;
+
+        Ok(DiagnosticsExport {
+            path: target.display().to_string(),
+            size_bytes: bytes.len() as u64,
+            entries,
+        })
+    })();
+
+    match result {
+        Ok(export) => {
+            span.ok(&[
+                ("size_bytes", export.size_bytes.to_string()),
+                ("entry_count", export.entries.len().to_string()),
+            ]);
+            Ok(export)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn invalidate_symbol_cache(
+    app: AppHandle,
+    symbol: String,
+) -> Result<CacheInvalidationResult, AppError> {
+    let span = CommandSpan::start("invalidate_symbol_cache", &[("symbol", symbol.clone())]);
+    let result = (|| -> Result<CacheInvalidationResult, AppError> {
+        let normalized_symbol = normalize_symbol(&symbol)?;
+        Ok(CacheInvalidationResult {
+            removed_quotes: QuoteCacheStore::from_app(&app)?
+                .remove_where(|cached_symbol| cached_symbol == normalized_symbol)?,
+            removed_timeseries: TimeSeriesCacheStore::from_app(&app)?
+                .remove_where(|file| file.symbol == normalized_symbol)?,
+        })
+    })();
+
+    finish_invalidation(span, result)
+}
+
+/// Drops the cached bars backing `range`. Ranges share one series per
+/// interval, so other ranges with the same interval are invalidated too.
+#[tauri::command]
+pub fn invalidate_range_cache(
+    app: AppHandle,
+    range: TimeRange,
+    symbol: Option<String>,
+) -> Result<CacheInvalidationResult, AppError> {
+    let span = CommandSpan::start(
+        "invalidate_range_cache",
+        &[
+            ("range", range.as_key().to_string()),
+            ("symbol", symbol.clone().unwrap_or_default()),
+        ],
+    );
+    let result = (|| -> Result<CacheInvalidationResult, AppError> {
+        let normalized_symbol = symbol.as_deref().map(normalize_symbol).transpose()?;
+        let interval = range.interval();
+        Ok(CacheInvalidationResult {
+            removed_quotes: Vec::new(),
+            removed_timeseries: TimeSeriesCacheStore::from_app(&app)?.remove_where(|file| {
+                file.interval == Some(interval)
+                    && normalized_symbol
+                        .as_ref()
+                        .is_none_or(|symbol| &file.symbol == symbol)
+            })?,
+        })
+    })();
+
+    finish_invalidation(span, result)
+}
+
+#[tauri::command]
+pub fn clear_quote_cache(app: AppHandle) -> Result<CacheInvalidationResult, AppError> {
+    let span = CommandSpan::start("clear_quote_cache", &[]);
+    let result = (|| -> Result<CacheInvalidationResult, AppError> {
+        Ok(CacheInvalidationResult {
+            removed_quotes: QuoteCacheStore::from_app(&app)?.remove_where(|_| true)?,
+            removed_timeseries: Vec::new(),
+        })
+    })();
+
+    finish_invalidation(span, result)
+}
+
+#[tauri::command]
+pub fn clear_timeseries_cache(app: AppHandle) -> Result<CacheInvalidationResult, AppError> {
+    let span = CommandSpan::start("clear_timeseries_cache", &[]);
+    let result = (|| -> Result<CacheInvalidationResult, AppError> {
+        Ok(CacheInvalidationResult {
+            removed_quotes: Vec::new(),
+            removed_timeseries: TimeSeriesCacheStore::from_app(&app)?.remove_where(|_| true)?,
+        })
+    })();
+
+    finish_invalidation(span, result)
+}
+
+/// Drops cached data for symbols that are neither on the watchlist nor held
+/// or traded in the ledger.
+#[tauri::command]
+pub fn prune_cache_to_watchlist(app: AppHandle) -> Result<CacheInvalidationResult, AppError> {
+    let span = CommandSpan::start("prune_cache_to_watchlist", &[]);
+    let result = CacheService::from_app(&app).and_then(|service| service.prune_untracked());
+
+    finish_invalidation(span, result)
+}
+
+fn finish_invalidation(
+    span: CommandSpan,
+    result: Result<CacheInvalidationResult, AppError>,
+) -> Result<CacheInvalidationResult, AppError> {
+    match result {
+        Ok(removed) => {
+            span.ok(&[
+                ("removed_quotes", removed.removed_quotes.len().to_string()),
+                (
+                    "removed_timeseries",
+                    removed.removed_timeseries.len().to_string(),
+                ),
+            ]);
+            Ok(removed)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
@@ -1,93 +1,45 @@
+use crate::commands::alerts::process_alerts;
+use crate::commands::webhooks::spawn_webhook_dispatch;
 use crate::domain::{
-    AppError, QuoteStatus, QuoteSummary, SymbolPerformance, TimeRange, normalize_symbol,
-    unix_timestamp_secs,
+    AppError, QuoteStatus, QuoteSummary, SymbolPerformance, TimeRange, WebhookTrigger,
+    normalize_symbol,
 };
-use crate::http::build_http_client;
 use crate::observability::CommandSpan;
-use crate::persistence::{
-    QuoteCacheMap, QuoteCacheStore, SettingsStore, TimeSeriesCacheStore, WatchlistStore,
-    is_cache_fresh, is_timeseries_cache_fresh, to_cached_entry, to_stale_performance,
-    to_stale_quote,
-};
-use crate::provider::{MarketDataProvider, TwelveDataAdapter};
+use crate::persistence::SettingsStore;
+use crate::services::MarketDataService;
 use tauri::AppHandle;
 
-const QUOTE_REQUEST_TIMEOUT_SECONDS: u64 = 8;
-
 #[tauri::command]
 pub async fn refresh_watchlist_quotes(app: AppHandle) -> Result<Vec<QuoteSummary>, AppError> {
     let span = CommandSpan::start("refresh_watchlist_quotes", &[]);
     let result = async {
         let settings = SettingsStore::from_app(&app)?.load()?;
-        if settings.api_key.trim().is_empty() {
-            return Err(AppError::validation(
-                "invalid_settings",
-                "Save an API key before refreshing quotes.",
-            ));
-        }
-
-        let watchlist = WatchlistStore::from_app(&app)?.load()?;
-        if watchlist.is_empty() {
-            return Ok(Vec::new());
+        let quotes = MarketDataService::from_app(&app)?
+            .refresh_quotes(&settings)
+            .await?;
+        if quotes.is_empty() {
+            return Ok((quotes, Ok(0)));
         }
 
-        let cache_store = QuoteCacheStore::from_app(&app)?;
-        let mut cache = cache_store.load()?;
-        let client = build_http_client(QUOTE_REQUEST_TIMEOUT_SECONDS)?;
-        let provider = TwelveDataAdapter::new(client);
+        // Alerts are evaluated and recorded even with notifications off;
+        // the setting only silences the desktop notification. A broken alerts
+        // file must not cost the user their quotes, so alert failures are
+        // only logged with the refresh.
+        let alerts = process_alerts(&app, &settings, &quotes).await;
 
-        let now = unix_timestamp_secs();
-        let mut quotes = Vec::with_capacity(watchlist.len());
+        let mut triggers: Vec<WebhookTrigger> = match &alerts {
+            Ok(events) => events.iter().cloned().map(WebhookTrigger::Alert).collect(),
+            Err(_) => Vec::new(),
+        };
+        triggers.push(WebhookTrigger::Refresh(quotes.clone()));
+        spawn_webhook_dispatch(app.clone(), settings, triggers);
 
-        for item in &watchlist {
-            let symbol = item.symbol.clone();
-            if let Some(entry) = cache.get(&symbol)
-                && is_cache_fresh(entry.cached_at, now)
-            {
-                let mut fresh_quote = entry.quote.clone();
-                fresh_quote.status = QuoteStatus::Fresh;
-                fresh_quote.error_code = None;
-                fresh_quote.error_message = None;
-                quotes.push(fresh_quote);
-                continue;
-            }
-
-            match provider.fetch_quote(&symbol, &settings.api_key).await {
-                Ok(mut quote) => {
-                    quote.status = QuoteStatus::Fresh;
-                    quote.error_code = None;
-                    quote.error_message = None;
-                    cache.insert(symbol.clone(), to_cached_entry(quote.clone()));
-                    quotes.push(quote);
-                }
-                Err(err) => {
-                    if let Some(entry) = cache.get(&symbol) {
-                        quotes.push(to_stale_quote(entry, &err));
-                    } else {
-                        quotes.push(QuoteSummary {
-                            symbol: symbol.clone(),
-                            price: 0.0,
-                            change_abs: None,
-                            change_pct: None,
-                            currency: None,
-                            last_updated_at: unix_timestamp_secs().to_string(),
-                            status: QuoteStatus::Error,
-                            error_code: Some(err.code),
-                            error_message: Some(err.message),
-                        });
-                    }
-                }
-            }
-        }
-
-        trim_cache_to_watchlist(&mut cache, &watchlist);
-        cache_store.save(&cache)?;
-        Ok(quotes)
+        Ok((quotes, alerts.map(|events| events.len())))
     }
     .await;
 
     match result {
-        Ok(quotes) => {
+        Ok((quotes, alerts)) => {
             let fresh_count = quotes
                 .iter()
                 .filter(|quote| quote.status == QuoteStatus::Fresh)
@@ -105,6 +57,10 @@ pub async fn refresh_watchlist_quotes(app: AppHandle) -> Result<Vec<QuoteSummary
                 ("fresh_count", fresh_count.to_string()),
                 ("stale_count", stale_count.to_string()),
                 ("error_count", error_count.to_string()),
+                match &alerts {
+                    Ok(triggered) => ("alerts_triggered", triggered.to_string()),
+                    Err(err) => ("alert_error_code", err.code.clone()),
+                },
             ]);
             Ok(quotes)
         }
@@ -115,12 +71,6 @@ pub async fn refresh_watchlist_quotes(app: AppHandle) -> Result<Vec<QuoteSummary
     }
 }
 
-fn trim_cache_to_watchlist(cache: &mut QuoteCacheMap, watchlist: &[crate::domain::WatchlistItem]) {
-    let keep: std::collections::HashSet<&str> =
-        watchlist.iter().map(|item| item.symbol.as_str()).collect();
-    cache.retain(|symbol, _| keep.contains(symbol.as_str()));
-}
-
 #[tauri::command]
 pub async fn get_symbol_performance(
     app: AppHandle,
@@ -157,48 +107,9 @@ async fn fetch_symbol_performance(
     let result = async {
         let normalized_symbol = normalize_symbol(&symbol)?;
         let settings = SettingsStore::from_app(&app)?.load()?;
-        if settings.api_key.trim().is_empty() {
-            return Err(AppError::validation(
-                "invalid_settings",
-                "Save an API key before loading chart data.",
-            ));
-        }
-
-        let cache_store = TimeSeriesCacheStore::from_app(&app)?;
-        let now = unix_timestamp_secs();
-        let cached_entry = cache_store.load(&normalized_symbol, range)?;
-
-        if !force_refresh
-            && let Some(entry) = cached_entry.as_ref()
-            && is_timeseries_cache_fresh(entry.cached_at, now)
-        {
-            let mut fresh_performance = entry.performance.clone();
-            fresh_performance.status = QuoteStatus::Fresh;
-            return Ok(fresh_performance);
-        }
-
-        let client = build_http_client(QUOTE_REQUEST_TIMEOUT_SECONDS)?;
-        let provider = TwelveDataAdapter::new(client);
-
-        match provider
-            .fetch_symbol_performance(&normalized_symbol, range, &settings.api_key)
+        MarketDataService::from_app(&app)?
+            .load_symbol_performance(&settings, &normalized_symbol, range, force_refresh)
             .await
-        {
-            Ok(mut performance) => {
-                performance.status = QuoteStatus::Fresh;
-                cache_store.save(&normalized_symbol, range, performance.clone())?;
-                Ok(performance)
-            }
-            Err(err) => {
-                if let Some(entry) = cached_entry {
-                    let mut stale = to_stale_performance(&entry);
-                    stale.status = QuoteStatus::Stale;
-                    Ok(stale)
-                } else {
-                    Err(err)
-                }
-            }
-        }
     }
     .await;
 
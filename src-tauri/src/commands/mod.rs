@@ -1,5 +1,10 @@
+pub mod alerts;
+pub mod analytics;
 pub mod bootstrap;
+pub mod connectivity;
 pub mod diagnostics;
 pub mod market_data;
+pub mod portfolio;
 pub mod settings;
 pub mod watchlist;
+pub mod webhooks;
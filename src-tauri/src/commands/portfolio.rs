@@ -0,0 +1,313 @@
+use crate::domain::{
+    AppError, AppSettings, LedgerReport, Lot, PortfolioPerformance, PortfolioSummary,
+    SeriesInterval, TimeRange, Transaction, TransactionInput, build_portfolio_performance,
+    now_rfc3339, remove_transaction, replace_transaction, replay_ledger, summarize_ledger,
+    symbols_held_since, unix_secs_to_rfc3339, unix_timestamp_secs, validate_transaction,
+};
+use crate::observability::CommandSpan;
+use crate::persistence::{LedgerStore, SettingsStore};
+use crate::services::MarketDataService;
+use std::collections::HashSet;
+use tauri::AppHandle;
+
+#[tauri::command]
+pub fn get_transactions(app: AppHandle) -> Result<Vec<Transaction>, AppError> {
+    let span = CommandSpan::start("get_transactions", &[]);
+    let result = LedgerStore::from_app(&app).and_then(|store| store.load());
+    match result {
+        Ok(transactions) => {
+            span.ok(&[("transaction_count", transactions.len().to_string())]);
+            Ok(transactions)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn add_transaction(
+    app: AppHandle,
+    transaction: TransactionInput,
+) -> Result<Vec<Transaction>, AppError> {
+    let span = CommandSpan::start(
+        "add_transaction",
+        &[
+            ("symbol", transaction.symbol.clone()),
+            ("kind", format!("{:?}", transaction.kind)),
+        ],
+    );
+    let result = (|| -> Result<Vec<Transaction>, AppError> {
+        let validated =
+            validate_transaction(uuid::Uuid::new_v4().to_string(), transaction, now_rfc3339())?;
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        let store = LedgerStore::from_app(&app)?;
+
+        let mut candidate = store.load()?;
+        candidate.push(validated.clone());
+        replay_ledger(&candidate, settings.lot_matching)?;
+
+        store.append(validated)
+    })();
+    match result {
+        Ok(transactions) => {
+            span.ok(&[("transaction_count", transactions.len().to_string())]);
+            Ok(transactions)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Corrects transaction `id`. It keeps its id and recorded time, and the
+/// edited ledger must still replay, so a sell can never lose its shares.
+#[tauri::command]
+pub fn update_transaction(
+    app: AppHandle,
+    id: String,
+    transaction: TransactionInput,
+) -> Result<Vec<Transaction>, AppError> {
+    let span = CommandSpan::start("update_transaction", &[("id", id.clone())]);
+    let result = (|| -> Result<Vec<Transaction>, AppError> {
+        let store = LedgerStore::from_app(&app)?;
+        let transactions = store.load()?;
+        let recorded_at = transactions
+            .iter()
+            .find(|existing| existing.id == id)
+            .map(|existing| existing.recorded_at.clone())
+            .unwrap_or_else(now_rfc3339);
+        let validated = validate_transaction(id.clone(), transaction, recorded_at)?;
+        let candidate = replace_transaction(&transactions, validated)?;
+        save_replayed_ledger(&app, &store, &candidate)
+    })();
+    match result {
+        Ok(transactions) => {
+            span.ok(&[("transaction_count", transactions.len().to_string())]);
+            Ok(transactions)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Removes transaction `id`, unless a later sell or dividend depends on it.
+#[tauri::command]
+pub fn delete_transaction(app: AppHandle, id: String) -> Result<Vec<Transaction>, AppError> {
+    let span = CommandSpan::start("delete_transaction", &[("id", id.clone())]);
+    let result = (|| -> Result<Vec<Transaction>, AppError> {
+        let store = LedgerStore::from_app(&app)?;
+        let candidate = remove_transaction(&store.load()?, &id)?;
+        save_replayed_ledger(&app, &store, &candidate)
+    })();
+    match result {
+        Ok(transactions) => {
+            span.ok(&[("transaction_count", transactions.len().to_string())]);
+            Ok(transactions)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+fn save_replayed_ledger(
+    app: &AppHandle,
+    store: &LedgerStore,
+    candidate: &[Transaction],
+) -> Result<Vec<Transaction>, AppError> {
+    let settings = SettingsStore::from_app(app)?.load()?;
+    replay_ledger(candidate, settings.lot_matching)?;
+    store.save(candidate)
+}
+
+#[tauri::command]
+pub fn get_portfolio_lots(app: AppHandle) -> Result<Vec<Lot>, AppError> {
+    let span = CommandSpan::start("get_portfolio_lots", &[]);
+    let result = load_ledger_report(&app).map(|report| report.open_lots);
+    match result {
+        Ok(lots) => {
+            span.ok(&[("lot_count", lots.len().to_string())]);
+            Ok(lots)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_ledger_report(app: AppHandle) -> Result<LedgerReport, AppError> {
+    let span = CommandSpan::start("get_ledger_report", &[]);
+    let result = load_ledger_report(&app);
+    match result {
+        Ok(report) => {
+            span.ok(&[
+                ("method", format!("{:?}", report.method)),
+                ("realized_count", report.realized.len().to_string()),
+            ]);
+            Ok(report)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_portfolio_summary(app: AppHandle) -> Result<PortfolioSummary, AppError> {
+    let span = CommandSpan::start("get_portfolio_summary", &[]);
+    let result = async {
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        let transactions = LedgerStore::from_app(&app)?.load()?;
+        let report = replay_ledger(&transactions, settings.lot_matching)?;
+        if report.open_lots.is_empty() {
+            return Ok(summarize_ledger(&report, &[]));
+        }
+
+        let mut symbols: Vec<String> = report
+            .open_lots
+            .iter()
+            .map(|lot| lot.symbol.clone())
+            .collect();
+        symbols.dedup();
+
+        let quotes = MarketDataService::from_app(&app)?
+            .quotes_for_symbols(&settings, &symbols)
+            .await?;
+
+        Ok(summarize_ledger(&report, &quotes))
+    }
+    .await;
+
+    match result {
+        Ok(summary) => {
+            span.ok(&[
+                ("position_count", summary.positions.len().to_string()),
+                ("unpriced_count", summary.unpriced_symbols.len().to_string()),
+            ]);
+            Ok(summary)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_portfolio_performance(
+    app: AppHandle,
+    range: TimeRange,
+) -> Result<PortfolioPerformance, AppError> {
+    let span = CommandSpan::start(
+        "get_portfolio_performance",
+        &[("range", range.as_key().to_string())],
+    );
+    let result = async {
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        load_portfolio_performance(&app, &settings, range).await
+    }
+    .await;
+
+    match result {
+        Ok(performance) => {
+            span.ok(&[
+                ("points", performance.points.len().to_string()),
+                ("status", format!("{:?}", performance.status)),
+                (
+                    "missing_count",
+                    performance.missing_symbols.len().to_string(),
+                ),
+            ]);
+            Ok(performance)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Values the ledger over `range` using cached bars for every symbol held
+/// during it. Symbols whose history cannot be loaded are left out of the
+/// value and the flows and listed in `missing_symbols`; failures that affect
+/// every symbol, such as a rejected API key, are returned instead.
+pub async fn load_portfolio_performance(
+    app: &AppHandle,
+    settings: &AppSettings,
+    range: TimeRange,
+) -> Result<PortfolioPerformance, AppError> {
+    let transactions = LedgerStore::from_app(app)?.load()?;
+    let market_data = MarketDataService::from_app(app)?;
+
+    // Bars skip weekends and closed hours, so a range's bars usually reach
+    // further back than its calendar length. Start from the calendar length
+    // and widen to the first loaded bar until no further symbols turn up.
+    let mut since_date = calendar_start_date(range);
+    let mut attempted = HashSet::new();
+    let mut series = Vec::new();
+    let mut missing_symbols = Vec::new();
+    loop {
+        for symbol in symbols_held_since(&transactions, &since_date) {
+            if !attempted.insert(symbol.clone()) {
+                continue;
+            }
+            match market_data
+                .load_symbol_performance(settings, &symbol, range, false)
+                .await
+            {
+                Ok(performance) => series.push(performance),
+                Err(err) if err.is_symbol_scoped() => missing_symbols.push(symbol),
+                Err(err) => return Err(err),
+            }
+        }
+
+        let first_bar_date = series
+            .iter()
+            .filter_map(|performance| performance.points.first())
+            .filter_map(|point| point.ts.get(..10))
+            .min();
+        match first_bar_date {
+            Some(date) if date < since_date.as_str() => since_date = date.to_string(),
+            _ => break,
+        }
+    }
+
+    Ok(build_portfolio_performance(
+        range,
+        &transactions,
+        &series,
+        missing_symbols,
+    ))
+}
+
+/// The date `range` reaches back to on the calendar, counted from today.
+fn calendar_start_date(range: TimeRange) -> String {
+    let days: u64 = match range {
+        TimeRange::OneDay => 1,
+        TimeRange::OneWeek => 7,
+        TimeRange::OneMonth => 31,
+        TimeRange::ThreeMonths => 92,
+        TimeRange::OneYear => 366,
+    };
+    let start = unix_timestamp_secs().saturating_sub(days * SeriesInterval::OneDay.seconds());
+    i64::try_from(start)
+        .ok()
+        .and_then(unix_secs_to_rfc3339)
+        .map(|ts| ts[..10].to_string())
+        .unwrap_or_default()
+}
+
+fn load_ledger_report(app: &AppHandle) -> Result<LedgerReport, AppError> {
+    let settings = SettingsStore::from_app(app)?.load()?;
+    let transactions = LedgerStore::from_app(app)?.load()?;
+    replay_ledger(&transactions, settings.lot_matching)
+}
@@ -1,16 +1,15 @@
-use crate::domain::{AppError, AppSettings, AppSettingsInput, ProviderTestResult};
-use crate::http::build_http_client;
-use crate::observability::CommandSpan;
-use crate::persistence::SettingsStore;
-use crate::provider::{MarketDataProvider, TwelveDataAdapter};
-use tauri::AppHandle;
-
-const PROVIDER_TEST_TIMEOUT_SECONDS: u64 = 8;
+use crate::connectivity::ConnectivityMonitor;
+use crate::domain::{
+    AppError, AppSettings, AppSettingsInput, ProviderStatusReport, ProviderTestResult, set_locale,
+};
+use crate::observability::{CommandSpan, LogController};
+use crate::services::{SettingsService, SystemClock};
+use tauri::{AppHandle, Manager};
 
 #[tauri::command]
 pub fn get_settings(app: AppHandle) -> Result<AppSettings, AppError> {
     let span = CommandSpan::start("get_settings", &[]);
-    let result = SettingsStore::from_app(&app).and_then(|store| store.load());
+    let result = SettingsService::from_app(&app).and_then(|service| service.load());
     match result {
         Ok(settings) => {
             span.ok(&[]);
@@ -26,15 +25,23 @@ pub fn get_settings(app: AppHandle) -> Result<AppSettings, AppError> {
 #[tauri::command]
 pub fn save_settings(app: AppHandle, settings: AppSettingsInput) -> Result<AppSettings, AppError> {
     let span = CommandSpan::start("save_settings", &[]);
-    let result = SettingsStore::from_app(&app).and_then(|store| store.save(settings));
+    let result = (|| -> Result<(AppSettings, usize), AppError> {
+        let (saved, evicted_count) = SettingsService::from_app(&app)?.save(settings)?;
+        set_locale(saved.locale);
+        if let Some(logging) = app.try_state::<LogController>() {
+            logging.set_level(saved.log_level)?;
+        }
+        Ok((saved, evicted_count))
+    })();
     match result {
-        Ok(saved) => {
+        Ok((saved, evicted_count)) => {
             span.ok(&[
                 ("provider", format!("{:?}", saved.provider)),
                 (
                     "auto_refresh_seconds",
                     saved.auto_refresh_seconds.to_string(),
                 ),
+                ("evicted_count", evicted_count.to_string()),
             ]);
             Ok(saved)
         }
@@ -48,25 +55,43 @@ pub fn save_settings(app: AppHandle, settings: AppSettingsInput) -> Result<AppSe
 #[tauri::command]
 pub async fn test_provider_connection(app: AppHandle) -> Result<ProviderTestResult, AppError> {
     let span = CommandSpan::start("test_provider_connection", &[]);
-    let result = async {
-        let settings = SettingsStore::from_app(&app)?.load()?;
-        if settings.api_key.trim().is_empty() {
-            return Err(AppError::validation(
-                "invalid_settings",
-                "Save an API key before testing provider connection.",
-            ));
+    let result = async { SettingsService::from_app(&app)?.test_connection().await }.await;
+
+    match result {
+        Ok(response) => {
+            span.ok(&[("ok", response.ok.to_string())]);
+            Ok(response)
         }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
 
-        let client = build_http_client(PROVIDER_TEST_TIMEOUT_SECONDS)?;
-        let provider = TwelveDataAdapter::new(client);
-        provider.test_connection(&settings.api_key).await
+/// Probes the provider endpoints; see `SettingsService::provider_status`.
+#[tauri::command]
+pub async fn get_provider_status(app: AppHandle) -> Result<ProviderStatusReport, AppError> {
+    let span = CommandSpan::start("get_provider_status", &[]);
+    let result = async {
+        SettingsService::from_app(&app)?
+            .provider_status(app.state::<ConnectivityMonitor>().inner(), &SystemClock)
+            .await
     }
     .await;
 
     match result {
-        Ok(response) => {
-            span.ok(&[("ok", response.ok.to_string())]);
-            Ok(response)
+        Ok(report) => {
+            let failed = report
+                .endpoints
+                .iter()
+                .filter(|endpoint| !endpoint.ok)
+                .count();
+            span.ok(&[
+                ("circuit", format!("{:?}", report.circuit)),
+                ("failed_endpoints", failed.to_string()),
+            ]);
+            Ok(report)
         }
         Err(err) => {
             span.err(&err, &[]);
@@ -0,0 +1,183 @@
+use crate::domain::{
+    AppError, AppSettings, Webhook, WebhookDelivery, WebhookInput, WebhookTrigger, now_rfc3339,
+    validate_webhook,
+};
+use crate::observability::CommandSpan;
+use crate::persistence::{SettingsStore, WebhookStore};
+use crate::services::{WebhookService, webhook_not_found};
+use tauri::AppHandle;
+
+#[tauri::command]
+pub fn get_webhooks(app: AppHandle) -> Result<Vec<Webhook>, AppError> {
+    let span = CommandSpan::start("get_webhooks", &[]);
+    let result = WebhookStore::from_app(&app).and_then(|store| store.load_webhooks());
+    match result {
+        Ok(webhooks) => {
+            span.ok(&[("webhook_count", webhooks.len().to_string())]);
+            Ok(webhooks)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn add_webhook(app: AppHandle, webhook: WebhookInput) -> Result<Vec<Webhook>, AppError> {
+    let span = CommandSpan::start("add_webhook", &[("name", webhook.name.clone())]);
+    let result = (|| -> Result<Vec<Webhook>, AppError> {
+        let validated = validate_webhook(uuid::Uuid::new_v4().to_string(), webhook, now_rfc3339())?;
+        let store = WebhookStore::from_app(&app)?;
+        let mut webhooks = store.load_webhooks()?;
+        webhooks.push(validated);
+        store.save_webhooks(&webhooks)
+    })();
+    match result {
+        Ok(webhooks) => {
+            span.ok(&[("webhook_count", webhooks.len().to_string())]);
+            Ok(webhooks)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn update_webhook(
+    app: AppHandle,
+    id: String,
+    webhook: WebhookInput,
+) -> Result<Vec<Webhook>, AppError> {
+    let span = CommandSpan::start("update_webhook", &[("id", id.clone())]);
+    let result = (|| -> Result<Vec<Webhook>, AppError> {
+        let store = WebhookStore::from_app(&app)?;
+        let mut webhooks = store.load_webhooks()?;
+        let existing = webhooks
+            .iter_mut()
+            .find(|existing| existing.id == id)
+            .ok_or_else(|| webhook_not_found(&id))?;
+
+        *existing = validate_webhook(id.clone(), webhook, existing.created_at.clone())?;
+        store.save_webhooks(&webhooks)
+    })();
+    match result {
+        Ok(webhooks) => {
+            span.ok(&[("webhook_count", webhooks.len().to_string())]);
+            Ok(webhooks)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn delete_webhook(app: AppHandle, id: String) -> Result<Vec<Webhook>, AppError> {
+    let span = CommandSpan::start("delete_webhook", &[("id", id.clone())]);
+    let result = (|| -> Result<Vec<Webhook>, AppError> {
+        let store = WebhookStore::from_app(&app)?;
+        let mut webhooks = store.load_webhooks()?;
+        let initial_len = webhooks.len();
+        webhooks.retain(|webhook| webhook.id != id);
+        if webhooks.len() == initial_len {
+            return Err(webhook_not_found(&id));
+        }
+
+        store.save_webhooks(&webhooks)
+    })();
+    match result {
+        Ok(webhooks) => {
+            span.ok(&[("webhook_count", webhooks.len().to_string())]);
+            Ok(webhooks)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_webhook_deliveries(app: AppHandle) -> Result<Vec<WebhookDelivery>, AppError> {
+    let span = CommandSpan::start("get_webhook_deliveries", &[]);
+    let result = WebhookStore::from_app(&app).and_then(|store| store.load_deliveries());
+    match result {
+        Ok(deliveries) => {
+            span.ok(&[("delivery_count", deliveries.len().to_string())]);
+            Ok(deliveries)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Sends a sample alert to one webhook right away, whether or not it is
+/// enabled, and logs the delivery like any other.
+#[tauri::command]
+pub async fn test_webhook(app: AppHandle, id: String) -> Result<WebhookDelivery, AppError> {
+    let span = CommandSpan::start("test_webhook", &[("id", id.clone())]);
+    let result = async {
+        let settings = SettingsStore::from_app(&app)?.load()?;
+        WebhookService::from_app(&app)?
+            .send_test(&settings, &id)
+            .await
+    }
+    .await;
+
+    match result {
+        Ok(delivery) => {
+            span.ok(&[
+                ("success", delivery.success.to_string()),
+                ("attempts", delivery.attempts.to_string()),
+            ]);
+            Ok(delivery)
+        }
+        Err(err) => {
+            span.err(&err, &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Delivers `triggers` in the background so a slow or unreachable webhook
+/// never holds up the refresh that produced them. Nothing is sent while
+/// offline.
+pub fn spawn_webhook_dispatch(
+    app: AppHandle,
+    settings: AppSettings,
+    triggers: Vec<WebhookTrigger>,
+) {
+    if triggers.is_empty() {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let span = CommandSpan::start(
+            "dispatch_webhooks",
+            &[("trigger_count", triggers.len().to_string())],
+        );
+        let result = match WebhookService::from_app(&app) {
+            Ok(service) => service.dispatch(&settings, &triggers).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(deliveries) => {
+                let failed = deliveries
+                    .iter()
+                    .filter(|delivery| !delivery.success)
+                    .count();
+                span.ok(&[
+                    ("delivery_count", deliveries.len().to_string()),
+                    ("failed_count", failed.to_string()),
+                ]);
+            }
+            Err(err) => span.err(&err, &[]),
+        }
+    });
+}
@@ -0,0 +1,167 @@
+use crate::domain::{AppError, CircuitState, ConnectivityStatus, unix_secs_to_rfc3339};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+const AUTO_OFFLINE_FAILURE_THRESHOLD: u32 = 3;
+const AUTO_OFFLINE_RETRY_SECONDS: u64 = 120;
+const CONNECTIVITY_ERROR_CODES: [&str; 2] = ["network_connect_error", "network_timeout"];
+
+/// Tracks consecutive network failures and switches the app into automatic
+/// offline mode once they pile up. After `AUTO_OFFLINE_RETRY_SECONDS` the next
+/// request is allowed through as a probe; one more failure re-arms the window.
+#[derive(Debug, Default)]
+pub struct ConnectivityMonitor {
+    state: Mutex<MonitorState>,
+}
+
+#[derive(Debug, Default)]
+struct MonitorState {
+    consecutive_failures: u32,
+    offline_until: Option<u64>,
+}
+
+impl ConnectivityMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_offline(&self, manual_offline: bool, now: u64) -> bool {
+        manual_offline || self.is_auto_offline(now)
+    }
+
+    pub fn is_auto_offline(&self, now: u64) -> bool {
+        self.lock()
+            .offline_until
+            .is_some_and(|offline_until| now < offline_until)
+    }
+
+    /// Any response from the provider, even an error payload, proves the
+    /// network is reachable. Only transport failures count towards going
+    /// offline.
+    pub fn record_outcome<T>(&self, result: &Result<T, AppError>, now: u64) {
+        match result {
+            Err(err) if is_connectivity_error(err) => self.record_failure(now),
+            _ => self.reset(),
+        }
+    }
+
+    pub fn reset(&self) {
+        *self.lock() = MonitorState::default();
+    }
+
+    pub fn status(&self, manual_offline: bool, now: u64) -> ConnectivityStatus {
+        let state = self.lock();
+        let auto_offline = state
+            .offline_until
+            .is_some_and(|offline_until| now < offline_until);
+
+        ConnectivityStatus {
+            offline: manual_offline || auto_offline,
+            manual_offline,
+            auto_offline,
+            consecutive_failures: state.consecutive_failures,
+            retry_at: state
+                .offline_until
+                .filter(|_| auto_offline)
+                .and_then(|offline_until| i64::try_from(offline_until).ok())
+                .and_then(unix_secs_to_rfc3339),
+        }
+    }
+
+    pub fn circuit_state(&self, now: u64) -> CircuitState {
+        match self.lock().offline_until {
+            Some(offline_until) if now < offline_until => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+            None => CircuitState::Closed,
+        }
+    }
+
+    fn record_failure(&self, now: u64) {
+        let mut state = self.lock();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.consecutive_failures >= AUTO_OFFLINE_FAILURE_THRESHOLD {
+            state.offline_until = Some(now + AUTO_OFFLINE_RETRY_SECONDS);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MonitorState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn is_connectivity_error(err: &AppError) -> bool {
+    CONNECTIVITY_ERROR_CODES.contains(&err.code.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connect_error() -> Result<(), AppError> {
+        Err(AppError::provider("network_connect_error"))
+    }
+
+    #[test]
+    fn goes_offline_after_repeated_connect_failures() {
+        let monitor = ConnectivityMonitor::new();
+        monitor.record_outcome(&connect_error(), 100);
+        monitor.record_outcome(&connect_error(), 101);
+        assert!(!monitor.is_auto_offline(102));
+
+        monitor.record_outcome(&connect_error(), 102);
+        assert!(monitor.is_auto_offline(103));
+
+        let status = monitor.status(false, 103);
+        assert!(status.offline);
+        assert!(status.auto_offline);
+        assert_eq!(status.consecutive_failures, 3);
+        assert!(status.retry_at.is_some());
+    }
+
+    #[test]
+    fn allows_a_probe_after_the_retry_window() {
+        let monitor = ConnectivityMonitor::new();
+        for now in 100..103 {
+            monitor.record_outcome(&connect_error(), now);
+        }
+
+        let retry_at = 102 + AUTO_OFFLINE_RETRY_SECONDS;
+        assert!(monitor.is_auto_offline(retry_at - 1));
+        assert!(!monitor.is_auto_offline(retry_at));
+
+        assert_eq!(monitor.circuit_state(retry_at - 1), CircuitState::Open);
+        assert_eq!(monitor.circuit_state(retry_at), CircuitState::HalfOpen);
+
+        monitor.record_outcome(&connect_error(), retry_at);
+        assert!(monitor.is_auto_offline(retry_at + 1));
+
+        monitor.record_outcome(&Ok(()), retry_at + 1);
+        assert_eq!(monitor.circuit_state(retry_at + 1), CircuitState::Closed);
+    }
+
+    #[test]
+    fn provider_errors_and_successes_reset_the_counter() {
+        let monitor = ConnectivityMonitor::new();
+        monitor.record_outcome(&connect_error(), 100);
+        monitor.record_outcome(&connect_error(), 101);
+        monitor.record_outcome::<()>(&Err(AppError::provider("rate_limited")), 102);
+        monitor.record_outcome(&connect_error(), 103);
+
+        assert!(!monitor.is_auto_offline(104));
+        assert_eq!(monitor.status(false, 104).consecutive_failures, 1);
+
+        monitor.record_outcome(&Ok(()), 105);
+        assert_eq!(monitor.status(false, 105).consecutive_failures, 0);
+    }
+
+    #[test]
+    fn manual_offline_overrides_monitor_state() {
+        let monitor = ConnectivityMonitor::new();
+        assert!(monitor.is_offline(true, 100));
+        assert!(!monitor.is_offline(false, 100));
+
+        let status = monitor.status(true, 100);
+        assert!(status.offline);
+        assert!(!status.auto_offline);
+        assert!(status.retry_at.is_none());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::domain::{
+    AppError, AppSettings, CacheStats, MetricsSnapshot, ProviderErrorRecord, REDACTED,
+    WatchlistItem, redact,
+};
+use crate::observability::{LOG_FILE_PREFIX, LOG_FILE_SUFFIX, to_prometheus};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime, ZipWriter};
+
+/// Log lines kept in a bundle, counted from the newest.
+const MAX_LOG_LINES: usize = 2000;
+
+/// Everything that goes into a diagnostics bundle, gathered by the caller.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsInputs {
+    pub app_version: String,
+    pub settings: AppSettings,
+    pub watchlist: Vec<WatchlistItem>,
+    pub cache_stats: CacheStats,
+    pub provider_errors: Vec<ProviderErrorRecord>,
+    pub metrics: MetricsSnapshot,
+    /// Log file names and contents, oldest first.
+    pub logs: Vec<(String, String)>,
+}
+
+/// Builds the zip for `inputs` and returns it with its entry names. The same
+/// inputs always produce the same bytes: entries are written in a fixed
+/// order with fixed timestamps and permissions. Every entry passes through
+/// `redact` and the API key is replaced outright, so the bundle is safe to
+/// attach to a ticket.
+pub fn build_diagnostics_bundle(
+    inputs: &DiagnosticsInputs,
+) -> Result<(Vec<u8>, Vec<String>), AppError> {
+    let mut entries = vec![
+        (
+            "app.json".to_string(),
+            to_pretty_json(&json!({
+                "version": inputs.app_version,
+                "os": std::env::consts::OS,
+                "arch": std::env::consts::ARCH,
+            }))?,
+        ),
+        (
+            "settings.json".to_string(),
+            to_pretty_json(&redacted_settings(&inputs.settings)?)?,
+        ),
+        (
+            "watchlist.json".to_string(),
+            to_pretty_json(&inputs.watchlist)?,
+        ),
+        (
+            "cache_stats.json".to_string(),
+            to_pretty_json(&inputs.cache_stats)?,
+        ),
+        (
+            "provider_errors.json".to_string(),
+            to_pretty_json(&inputs.provider_errors)?,
+        ),
+        ("metrics.json".to_string(), to_pretty_json(&inputs.metrics)?),
+        ("metrics.prom".to_string(), to_prometheus(&inputs.metrics)),
+    ];
+    entries.extend(
+        inputs
+            .logs
+            .iter()
+            .map(|(name, content)| (format!("logs/{name}"), content.clone())),
+    );
+
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(DateTime::default())
+        .unix_permissions(0o644);
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in &entries {
+        writer
+            .start_file(name.as_str(), options)
+            .map_err(bundle_error)?;
+        writer
+            .write_all(redact(content).as_bytes())
+            .map_err(bundle_error)?;
+    }
+    let bytes = writer.finish().map_err(bundle_error)?.into_inner();
+
+    Ok((bytes, entries.into_iter().map(|(name, _)| name).collect()))
+}
+
+/// Reads the tail of the app's log files in `log_dir`, oldest first, keeping
+/// at most `MAX_LOG_LINES` lines across all files. A missing directory
+/// means there are no logs yet.
+pub fn collect_recent_logs(log_dir: &Path) -> Result<Vec<(String, String)>, AppError> {
+    if !log_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let read_dir = fs::read_dir(log_dir).map_err(|err| log_read_error(log_dir, err))?;
+    let mut names = Vec::new();
+    for entry in read_dir {
+        let name = entry
+            .map_err(|err| log_read_error(log_dir, err))?
+            .file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with(LOG_FILE_PREFIX) && name.ends_with(LOG_FILE_SUFFIX) {
+            names.push(name.into_owned());
+        }
+    }
+    // Rotated files are named by date, so name order is age order.
+    names.sort();
+
+    let mut logs = Vec::new();
+    let mut remaining = MAX_LOG_LINES;
+    for name in names.into_iter().rev() {
+        if remaining == 0 {
+            break;
+        }
+        let path = log_dir.join(&name);
+        let content = fs::read_to_string(&path).map_err(|err| log_read_error(&path, err))?;
+        let lines: Vec<&str> = content.lines().collect();
+        let tail = &lines[lines.len().saturating_sub(remaining)..];
+        remaining -= tail.len();
+        logs.push((name, tail.join("\n")));
+    }
+    logs.reverse();
+
+    Ok(logs)
+}
+
+fn redacted_settings(settings: &AppSettings) -> Result<Value, AppError> {
+    let mut value = serde_json::to_value(settings).map_err(bundle_error)?;
+    if let Some(api_key) = value.get_mut("apiKey")
+        && api_key.as_str().is_some_and(|key| !key.is_empty())
+    {
+        *api_key = Value::String(REDACTED.to_string());
+    }
+    Ok(value)
+}
+
+fn to_pretty_json(value: &impl Serialize) -> Result<String, AppError> {
+    serde_json::to_string_pretty(value).map_err(bundle_error)
+}
+
+fn bundle_error(err: impl std::fmt::Display) -> AppError {
+    AppError::internal("diagnostics_bundle_failed").with_arg("detail", err)
+}
+
+/// `path` is the log file that failed to read, or the log directory when
+/// listing it failed.
+fn log_read_error(path: &Path, err: std::io::Error) -> AppError {
+    AppError::persistence("diagnostics_log_read_failed")
+        .with_arg("detail", err)
+        .with_arg("file", path.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CacheStoreStats, register_secret};
+    use crate::observability::MetricsRegistry;
+    use std::io::Read;
+
+    const API_KEY: &str = "td-live-0f3b9a71c2";
+
+    fn inputs() -> DiagnosticsInputs {
+        register_secret(API_KEY);
+        DiagnosticsInputs {
+            app_version: "0.1.0".to_string(),
+            settings: AppSettings {
+                api_key: API_KEY.to_string(),
+                ..AppSettings::default()
+            },
+            watchlist: vec![WatchlistItem {
+                symbol: "AAPL".to_string(),
+                display_name: Some("Apple".to_string()),
+                pinned: None,
+            }],
+            cache_stats: CacheStats {
+                quotes: CacheStoreStats::default(),
+                timeseries: CacheStoreStats::default(),
+            },
+            provider_errors: vec![ProviderErrorRecord {
+                endpoint: "quote".to_string(),
+                code: "invalid_api_key".to_string(),
+                occurred_at: "2026-02-22T12:00:01Z".to_string(),
+            }],
+            metrics: MetricsRegistry::new().snapshot("2026-02-22T12:00:02Z".to_string()),
+            logs: vec![(
+                "stocks.2026-02-22.log".to_string(),
+                format!(r#"{{"message":"command_failed","url":"/quote?apikey={API_KEY}"}}"#),
+            )],
+        }
+    }
+
+    fn unzip(bytes: &[u8]) -> Vec<(String, String)> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        (0..archive.len())
+            .map(|index| {
+                let mut file = archive.by_index(index).unwrap();
+                let mut content = String::new();
+                file.read_to_string(&mut content).unwrap();
+                (file.name().to_string(), content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bundle_contains_every_section_without_the_key() {
+        let (bytes, entries) = build_diagnostics_bundle(&inputs()).unwrap();
+        let files = unzip(&bytes);
+
+        assert_eq!(
+            entries,
+            [
+                "app.json",
+                "settings.json",
+                "watchlist.json",
+                "cache_stats.json",
+                "provider_errors.json",
+                "metrics.json",
+                "metrics.prom",
+                "logs/stocks.2026-02-22.log",
+            ]
+        );
+        assert_eq!(
+            files
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            entries
+        );
+        for (name, content) in &files {
+            assert!(!content.contains(API_KEY), "{name} leaks the API key");
+        }
+
+        let settings: Value = serde_json::from_str(&files[1].1).unwrap();
+        assert_eq!(settings["apiKey"], REDACTED);
+        assert!(files[4].1.contains("invalid_api_key"));
+    }
+
+    #[test]
+    fn bundle_is_reproducible() {
+        let (first, _) = build_diagnostics_bundle(&inputs()).unwrap();
+        let (second, _) = build_diagnostics_bundle(&inputs()).unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn collect_recent_logs_keeps_the_newest_lines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let old_lines: Vec<String> = (0..MAX_LOG_LINES)
+            .map(|line| format!("old {line}"))
+            .collect();
+        fs::write(
+            temp_dir.path().join("stocks.2026-02-21.log"),
+            old_lines.join("\n"),
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("stocks.2026-02-22.log"),
+            "new 1\nnew 2\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("other.txt"), "ignored").unwrap();
+
+        let logs = collect_recent_logs(temp_dir.path()).unwrap();
+
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].0, "stocks.2026-02-21.log");
+        assert!(logs[0].1.starts_with("old 2\n"));
+        assert_eq!(
+            logs[1],
+            (
+                "stocks.2026-02-22.log".to_string(),
+                "new 1\nnew 2".to_string()
+            )
+        );
+        assert!(
+            collect_recent_logs(&temp_dir.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn an_unreadable_log_names_the_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("stocks.2026-02-22.log");
+        fs::write(&path, [0xff, 0xfe, b'\n']).unwrap();
+
+        let err = collect_recent_logs(temp_dir.path()).unwrap_err();
+
+        assert_eq!(err.code, "diagnostics_log_read_failed");
+        assert_eq!(err.args["file"], path.display().to_string());
+    }
+}
@@ -0,0 +1,459 @@
+use super::{
+    AppError, QuoteStatus, QuoteSummary, current_locale, normalize_symbol, render_message,
+    rfc3339_to_unix_secs, unix_secs_to_rfc3339,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const DEFAULT_ALERT_COOLDOWN_SECONDS: u64 = 900;
+const MAX_ALERT_COOLDOWN_SECONDS: u64 = 7 * 24 * 60 * 60;
+/// Daily bars retained per symbol, so a longer average could never be
+/// computed.
+const MAX_MOVING_AVERAGE_PERIOD: usize = 90;
+
+/// What an alert watches. Moving averages are simple averages of the last
+/// `period` daily closes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum AlertCondition {
+    PriceAbove {
+        price: f64,
+    },
+    PriceBelow {
+        price: f64,
+    },
+    /// Day change, up or down, of at least `threshold_pct` percent.
+    PercentChange {
+        threshold_pct: f64,
+    },
+    CrossesAboveMovingAverage {
+        period: usize,
+    },
+    CrossesBelowMovingAverage {
+        period: usize,
+    },
+}
+
+impl AlertCondition {
+    pub fn moving_average_period(&self) -> Option<usize> {
+        match *self {
+            Self::CrossesAboveMovingAverage { period }
+            | Self::CrossesBelowMovingAverage { period } => Some(period),
+            _ => None,
+        }
+    }
+}
+
+/// A persisted alert rule. A rule fires when its condition becomes true while
+/// it is `armed`, then stays disarmed until the condition is seen false again.
+/// `cooldown_seconds` additionally keeps a re-armed rule quiet for a while
+/// after it fired, so a price hovering around a threshold does not flap.
+/// `notify` controls whether the rule also raises a desktop notification.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertRule {
+    pub id: String,
+    pub symbol: String,
+    pub condition: AlertCondition,
+    pub enabled: bool,
+    #[serde(default = "default_notify")]
+    pub notify: bool,
+    pub cooldown_seconds: u64,
+    pub created_at: String,
+    #[serde(default)]
+    pub armed: bool,
+    #[serde(default)]
+    pub last_triggered_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertRuleInput {
+    pub symbol: String,
+    pub condition: AlertCondition,
+    pub enabled: Option<bool>,
+    pub notify: Option<bool>,
+    pub cooldown_seconds: Option<u64>,
+}
+
+/// One triggered alert. `reference_value` is the moving average for crossing
+/// rules and the day change for percent-change rules.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertEvent {
+    pub id: String,
+    pub rule_id: String,
+    pub symbol: String,
+    pub condition: AlertCondition,
+    pub price: f64,
+    pub reference_value: Option<f64>,
+    pub message: String,
+    pub triggered_at: String,
+}
+
+/// Latest moving average per `(symbol, period)`.
+pub type MovingAverages = HashMap<(String, usize), f64>;
+
+/// Validates a rule entered by the user and assigns it `id`. Crossing rules
+/// start disarmed so they only fire after the price was seen on the other
+/// side of the average.
+pub fn validate_alert_rule(
+    id: String,
+    input: AlertRuleInput,
+    created_at: String,
+) -> Result<AlertRule, AppError> {
+    let symbol = normalize_symbol(&input.symbol)?;
+
+    match input.condition {
+        AlertCondition::PriceAbove { price } | AlertCondition::PriceBelow { price } => {
+            if !price.is_finite() || price <= 0.0 {
+                return Err(invalid_alert("price"));
+            }
+        }
+        AlertCondition::PercentChange { threshold_pct } => {
+            if !threshold_pct.is_finite() || threshold_pct <= 0.0 {
+                return Err(invalid_alert("threshold"));
+            }
+        }
+        AlertCondition::CrossesAboveMovingAverage { period }
+        | AlertCondition::CrossesBelowMovingAverage { period } => {
+            if !(2..=MAX_MOVING_AVERAGE_PERIOD).contains(&period) {
+                return Err(invalid_alert("moving_average_period")
+                    .with_arg("max", MAX_MOVING_AVERAGE_PERIOD));
+            }
+        }
+    }
+
+    let cooldown_seconds = input
+        .cooldown_seconds
+        .unwrap_or(DEFAULT_ALERT_COOLDOWN_SECONDS);
+    if cooldown_seconds > MAX_ALERT_COOLDOWN_SECONDS {
+        return Err(invalid_alert("cooldown"));
+    }
+
+    Ok(AlertRule {
+        id,
+        symbol,
+        armed: input.condition.moving_average_period().is_none(),
+        condition: input.condition,
+        enabled: input.enabled.unwrap_or(true),
+        notify: input.notify.unwrap_or_else(default_notify),
+        cooldown_seconds,
+        created_at,
+        last_triggered_at: None,
+    })
+}
+
+/// Evaluates enabled rules against freshly fetched quotes, updating each
+/// rule's armed state in place. Stale and failed quotes are skipped, as are
+/// crossing rules without a cached moving average.
+pub fn evaluate_alerts(
+    rules: &mut [AlertRule],
+    quotes: &[QuoteSummary],
+    moving_averages: &MovingAverages,
+    now_secs: i64,
+) -> Vec<AlertEvent> {
+    let quotes_by_symbol: HashMap<&str, &QuoteSummary> = quotes
+        .iter()
+        .filter(|quote| quote.status == QuoteStatus::Fresh)
+        .map(|quote| (quote.symbol.as_str(), quote))
+        .collect();
+
+    let mut events = Vec::new();
+    for rule in rules.iter_mut().filter(|rule| rule.enabled) {
+        let Some(quote) = quotes_by_symbol.get(rule.symbol.as_str()) else {
+            continue;
+        };
+        let Some((met, reference_value)) = check_condition(rule, quote, moving_averages) else {
+            continue;
+        };
+
+        if !met {
+            rule.armed = true;
+            continue;
+        }
+        if !rule.armed || in_cooldown(rule, now_secs) {
+            continue;
+        }
+
+        let triggered_at = unix_secs_to_rfc3339(now_secs).unwrap_or_default();
+        rule.armed = false;
+        rule.last_triggered_at = Some(triggered_at.clone());
+        events.push(AlertEvent {
+            id: format!("{}-{now_secs}", rule.id),
+            rule_id: rule.id.clone(),
+            symbol: rule.symbol.clone(),
+            condition: rule.condition,
+            price: quote.price,
+            reference_value,
+            message: describe_trigger(rule, quote.price, reference_value),
+            triggered_at,
+        });
+    }
+
+    events
+}
+
+fn check_condition(
+    rule: &AlertRule,
+    quote: &QuoteSummary,
+    moving_averages: &MovingAverages,
+) -> Option<(bool, Option<f64>)> {
+    match rule.condition {
+        AlertCondition::PriceAbove { price } => Some((quote.price > price, None)),
+        AlertCondition::PriceBelow { price } => Some((quote.price < price, None)),
+        AlertCondition::PercentChange { threshold_pct } => {
+            let change_pct = quote.change_pct?;
+            Some((change_pct.abs() >= threshold_pct, Some(change_pct)))
+        }
+        AlertCondition::CrossesAboveMovingAverage { period } => {
+            let average = *moving_averages.get(&(rule.symbol.clone(), period))?;
+            Some((quote.price > average, Some(average)))
+        }
+        AlertCondition::CrossesBelowMovingAverage { period } => {
+            let average = *moving_averages.get(&(rule.symbol.clone(), period))?;
+            Some((quote.price < average, Some(average)))
+        }
+    }
+}
+
+fn in_cooldown(rule: &AlertRule, now_secs: i64) -> bool {
+    rule.last_triggered_at
+        .as_deref()
+        .and_then(rfc3339_to_unix_secs)
+        .is_some_and(|last| now_secs - last < rule.cooldown_seconds as i64)
+}
+
+fn describe_trigger(rule: &AlertRule, price: f64, reference_value: Option<f64>) -> String {
+    let reference = reference_value.unwrap_or_default();
+    let mut args = BTreeMap::from([
+        ("symbol".to_string(), rule.symbol.clone()),
+        ("price".to_string(), format!("{price:.2}")),
+    ]);
+    let reason = match rule.condition {
+        AlertCondition::PriceAbove { price: threshold } => {
+            args.insert("threshold".to_string(), format!("{threshold:.2}"));
+            "price_above"
+        }
+        AlertCondition::PriceBelow { price: threshold } => {
+            args.insert("threshold".to_string(), format!("{threshold:.2}"));
+            "price_below"
+        }
+        AlertCondition::PercentChange { threshold_pct } => {
+            args.insert("change".to_string(), format!("{reference:+.2}"));
+            args.insert("threshold".to_string(), format!("{threshold_pct:.2}"));
+            "percent_change"
+        }
+        AlertCondition::CrossesAboveMovingAverage { period } => {
+            args.insert("period".to_string(), period.to_string());
+            args.insert("average".to_string(), format!("{reference:.2}"));
+            "crosses_above_average"
+        }
+        AlertCondition::CrossesBelowMovingAverage { period } => {
+            args.insert("period".to_string(), period.to_string());
+            args.insert("average".to_string(), format!("{reference:.2}"));
+            "crosses_below_average"
+        }
+    };
+
+    render_message(current_locale(), "alert_message", Some(reason), &args)
+}
+
+fn default_notify() -> bool {
+    true
+}
+
+fn invalid_alert(reason: &str) -> AppError {
+    AppError::validation("invalid_alert").with_reason(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SeriesInterval;
+
+    const NOW: i64 = 1_771_772_400;
+
+    fn rule(condition: AlertCondition) -> AlertRule {
+        validate_alert_rule(
+            "rule-1".to_string(),
+            AlertRuleInput {
+                symbol: "aapl".to_string(),
+                condition,
+                enabled: None,
+                notify: None,
+                cooldown_seconds: Some(600),
+            },
+            "2026-02-22T12:00:00Z".to_string(),
+        )
+        .unwrap()
+    }
+
+    fn quote(price: f64, change_pct: Option<f64>) -> QuoteSummary {
+        QuoteSummary {
+            symbol: "AAPL".to_string(),
+            price,
+            change_abs: None,
+            change_pct,
+            currency: Some("USD".to_string()),
+            last_updated_at: "2026-02-22T15:00:00Z".to_string(),
+            exchange_timezone: None,
+            status: QuoteStatus::Fresh,
+            error_code: None,
+            error_message: None,
+        }
+    }
+
+    #[test]
+    fn price_rule_fires_once_and_rearms_after_condition_clears() {
+        let mut rules = vec![rule(AlertCondition::PriceAbove { price: 200.0 })];
+        let averages = MovingAverages::new();
+
+        let first = evaluate_alerts(&mut rules, &[quote(201.0, None)], &averages, NOW);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].symbol, "AAPL");
+        assert!(!rules[0].armed);
+
+        let still_above = evaluate_alerts(&mut rules, &[quote(205.0, None)], &averages, NOW + 60);
+        assert!(still_above.is_empty());
+
+        evaluate_alerts(&mut rules, &[quote(199.0, None)], &averages, NOW + 120);
+        assert!(rules[0].armed);
+
+        let after_cooldown =
+            evaluate_alerts(&mut rules, &[quote(202.0, None)], &averages, NOW + 900);
+        assert_eq!(after_cooldown.len(), 1);
+    }
+
+    #[test]
+    fn cooldown_holds_back_a_rearmed_rule() {
+        let mut rules = vec![rule(AlertCondition::PriceBelow { price: 100.0 })];
+        let averages = MovingAverages::new();
+
+        evaluate_alerts(&mut rules, &[quote(99.0, None)], &averages, NOW);
+        evaluate_alerts(&mut rules, &[quote(101.0, None)], &averages, NOW + 30);
+        let within = evaluate_alerts(&mut rules, &[quote(98.0, None)], &averages, NOW + 60);
+        assert!(within.is_empty());
+        assert!(rules[0].armed);
+
+        let after = evaluate_alerts(&mut rules, &[quote(98.0, None)], &averages, NOW + 600);
+        assert_eq!(after.len(), 1);
+    }
+
+    #[test]
+    fn percent_change_fires_in_either_direction() {
+        let mut rules = vec![rule(AlertCondition::PercentChange { threshold_pct: 3.0 })];
+        let averages = MovingAverages::new();
+
+        let events = evaluate_alerts(&mut rules, &[quote(90.0, Some(-3.5))], &averages, NOW);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].reference_value, Some(-3.5));
+
+        let mut quiet = vec![rule(AlertCondition::PercentChange { threshold_pct: 3.0 })];
+        assert!(evaluate_alerts(&mut quiet, &[quote(90.0, Some(2.0))], &averages, NOW).is_empty());
+        assert!(evaluate_alerts(&mut quiet, &[quote(90.0, None)], &averages, NOW).is_empty());
+    }
+
+    #[test]
+    fn crossing_rule_needs_to_see_the_other_side_first() {
+        let mut rules = vec![rule(AlertCondition::CrossesAboveMovingAverage {
+            period: 20,
+        })];
+        let mut averages = MovingAverages::new();
+        averages.insert(("AAPL".to_string(), 20), 150.0);
+
+        let already_above = evaluate_alerts(&mut rules, &[quote(155.0, None)], &averages, NOW);
+        assert!(already_above.is_empty());
+
+        evaluate_alerts(&mut rules, &[quote(148.0, None)], &averages, NOW + 60);
+        let crossed = evaluate_alerts(&mut rules, &[quote(152.0, None)], &averages, NOW + 120);
+        assert_eq!(crossed.len(), 1);
+        assert_eq!(crossed[0].reference_value, Some(150.0));
+    }
+
+    #[test]
+    fn trigger_messages_come_from_the_catalogue() {
+        let above = rule(AlertCondition::PriceAbove { price: 200.0 });
+        assert_eq!(
+            describe_trigger(&above, 201.0, Some(200.0)),
+            "AAPL rose above 200.00 (now 201.00)."
+        );
+
+        let moved = rule(AlertCondition::PercentChange { threshold_pct: 3.0 });
+        assert_eq!(
+            describe_trigger(&moved, 90.0, Some(-3.5)),
+            "AAPL moved -3.50% today, beyond 3.00%."
+        );
+
+        let crossed = rule(AlertCondition::CrossesBelowMovingAverage { period: 50 });
+        assert_eq!(
+            describe_trigger(&crossed, 148.0, Some(150.0)),
+            "AAPL crossed below its 50-day average of 150.00 (now 148.00)."
+        );
+    }
+
+    #[test]
+    fn stale_quotes_and_disabled_rules_are_skipped() {
+        let mut rules = vec![rule(AlertCondition::PriceAbove { price: 200.0 })];
+        let averages = MovingAverages::new();
+        let mut stale = quote(250.0, None);
+        stale.status = QuoteStatus::Stale;
+
+        assert!(evaluate_alerts(&mut rules, &[stale], &averages, NOW).is_empty());
+
+        rules[0].enabled = false;
+        assert!(evaluate_alerts(&mut rules, &[quote(250.0, None)], &averages, NOW).is_empty());
+    }
+
+    #[test]
+    fn validate_alert_rule_rejects_bad_thresholds() {
+        let input = |condition| AlertRuleInput {
+            symbol: "AAPL".to_string(),
+            condition,
+            enabled: None,
+            notify: None,
+            cooldown_seconds: None,
+        };
+        let created_at = || "2026-02-22T12:00:00Z".to_string();
+
+        let zero_price = validate_alert_rule(
+            "a".to_string(),
+            input(AlertCondition::PriceAbove { price: 0.0 }),
+            created_at(),
+        );
+        assert_eq!(zero_price.unwrap_err().code, "invalid_alert");
+
+        let short_average = validate_alert_rule(
+            "b".to_string(),
+            input(AlertCondition::CrossesBelowMovingAverage { period: 1 }),
+            created_at(),
+        );
+        assert!(short_average.is_err());
+
+        let long_average = validate_alert_rule(
+            "d".to_string(),
+            input(AlertCondition::CrossesAboveMovingAverage {
+                period: MAX_MOVING_AVERAGE_PERIOD + 1,
+            }),
+            created_at(),
+        );
+        assert!(long_average.is_err());
+        assert_eq!(
+            MAX_MOVING_AVERAGE_PERIOD,
+            SeriesInterval::OneDay.retained_bars()
+        );
+
+        let rule = validate_alert_rule(
+            "c".to_string(),
+            input(AlertCondition::PercentChange { threshold_pct: 5.0 }),
+            created_at(),
+        )
+        .unwrap();
+        assert_eq!(rule.cooldown_seconds, DEFAULT_ALERT_COOLDOWN_SECONDS);
+        assert!(rule.enabled && rule.armed);
+    }
+}
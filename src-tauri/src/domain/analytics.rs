@@ -0,0 +1,305 @@
+use super::statistics::{mean, period_returns, std_dev};
+use super::{PricePoint, QuoteStatus, SymbolPerformance, TimeRange};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodReturn {
+    pub ts: String,
+    pub return_pct: f64,
+}
+
+/// Risk and return figures for one symbol over a range. Percentages are
+/// expressed as 5.0 for 5%; `max_drawdown_pct` is zero or negative. Ratios
+/// and volatility are annualized from bar-to-bar returns and need at least
+/// two of them. Best and worst periods are single bars, so days for the
+/// daily ranges.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolAnalytics {
+    pub symbol: String,
+    pub range: TimeRange,
+    pub risk_free_rate_pct: f64,
+    pub period_return_pct: f64,
+    pub annualized_volatility_pct: Option<f64>,
+    pub max_drawdown_pct: f64,
+    pub drawdown_peak_at: Option<String>,
+    pub drawdown_trough_at: Option<String>,
+    pub sharpe_ratio: Option<f64>,
+    pub sortino_ratio: Option<f64>,
+    pub best_period: Option<PeriodReturn>,
+    pub worst_period: Option<PeriodReturn>,
+    pub status: QuoteStatus,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drawdown {
+    pub depth: f64,
+    pub peak_index: usize,
+    pub trough_index: usize,
+}
+
+pub fn compute_symbol_analytics(
+    performance: &SymbolPerformance,
+    risk_free_rate_pct: f64,
+) -> SymbolAnalytics {
+    let closes: Vec<f64> = performance.points.iter().map(|point| point.close).collect();
+    let returns = period_returns(&closes);
+    let periods_per_year = performance.range.interval().periods_per_year();
+    let risk_free_per_period =
+        (1.0 + risk_free_rate_pct / 100.0).powf(1.0 / periods_per_year) - 1.0;
+
+    let drawdown = max_drawdown(&closes);
+    let (best_period, worst_period) = extreme_periods(&performance.points);
+
+    SymbolAnalytics {
+        symbol: performance.symbol.clone(),
+        range: performance.range,
+        risk_free_rate_pct,
+        period_return_pct: if performance.start > 0.0 {
+            (performance.end / performance.start - 1.0) * 100.0
+        } else {
+            0.0
+        },
+        annualized_volatility_pct: annualized_volatility(&returns, periods_per_year)
+            .map(|volatility| volatility * 100.0),
+        max_drawdown_pct: drawdown.map_or(0.0, |drawdown| drawdown.depth * 100.0),
+        drawdown_peak_at: drawdown
+            .map(|drawdown| performance.points[drawdown.peak_index].ts.clone()),
+        drawdown_trough_at: drawdown
+            .map(|drawdown| performance.points[drawdown.trough_index].ts.clone()),
+        sharpe_ratio: sharpe_ratio(&returns, risk_free_per_period, periods_per_year),
+        sortino_ratio: sortino_ratio(&returns, risk_free_per_period, periods_per_year),
+        best_period,
+        worst_period,
+        status: performance.status,
+        error_code: performance.error_code.clone(),
+        error_message: performance.error_message.clone(),
+    }
+}
+
+pub fn annualized_volatility(returns: &[f64], periods_per_year: f64) -> Option<f64> {
+    std_dev(returns).map(|deviation| deviation * periods_per_year.sqrt())
+}
+
+/// Deepest fall from a running peak, as a fraction (-0.25 for 25%). `None`
+/// when the series never falls below an earlier close.
+pub fn max_drawdown(closes: &[f64]) -> Option<Drawdown> {
+    let mut peak_index = 0;
+    let mut deepest: Option<Drawdown> = None;
+
+    for (index, close) in closes.iter().enumerate() {
+        if *close > closes[peak_index] {
+            peak_index = index;
+            continue;
+        }
+
+        let peak = closes[peak_index];
+        if peak <= 0.0 {
+            continue;
+        }
+
+        let depth = close / peak - 1.0;
+        if depth < deepest.map_or(0.0, |deepest| deepest.depth) {
+            deepest = Some(Drawdown {
+                depth,
+                peak_index,
+                trough_index: index,
+            });
+        }
+    }
+
+    deepest
+}
+
+pub fn sharpe_ratio(
+    returns: &[f64],
+    risk_free_per_period: f64,
+    periods_per_year: f64,
+) -> Option<f64> {
+    let deviation = std_dev(returns)?;
+    if deviation <= 0.0 {
+        return None;
+    }
+
+    Some((mean(returns)? - risk_free_per_period) / deviation * periods_per_year.sqrt())
+}
+
+/// Like Sharpe, but only returns below the risk-free rate count as risk. The
+/// downside deviation uses the same N - 1 denominator as `std_dev`.
+pub fn sortino_ratio(
+    returns: &[f64],
+    risk_free_per_period: f64,
+    periods_per_year: f64,
+) -> Option<f64> {
+    if returns.len() < 2 {
+        return None;
+    }
+
+    let downside = returns
+        .iter()
+        .map(|value| (value - risk_free_per_period).min(0.0).powi(2))
+        .sum::<f64>()
+        / (returns.len() - 1) as f64;
+    if downside <= 0.0 {
+        return None;
+    }
+
+    Some((mean(returns)? - risk_free_per_period) / downside.sqrt() * periods_per_year.sqrt())
+}
+
+fn extreme_periods(points: &[PricePoint]) -> (Option<PeriodReturn>, Option<PeriodReturn>) {
+    let moves: Vec<PeriodReturn> = points
+        .windows(2)
+        .filter(|pair| pair[0].close > 0.0)
+        .map(|pair| PeriodReturn {
+            ts: pair[1].ts.clone(),
+            return_pct: (pair[1].close / pair[0].close - 1.0) * 100.0,
+        })
+        .collect();
+
+    let best = moves
+        .iter()
+        .max_by(|left, right| left.return_pct.total_cmp(&right.return_pct))
+        .cloned();
+    let worst = moves
+        .iter()
+        .min_by(|left, right| left.return_pct.total_cmp(&right.return_pct))
+        .cloned();
+    (best, worst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn performance(closes: &[f64]) -> SymbolPerformance {
+        let points = closes
+            .iter()
+            .enumerate()
+            .map(|(day, close)| {
+                PricePoint::new(format!("2026-01-{:02}T05:00:00Z", day + 1), *close)
+            })
+            .collect();
+        SymbolPerformance::from_points(
+            "AAPL",
+            TimeRange::OneMonth,
+            points,
+            None,
+            QuoteStatus::Fresh,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn drawdown_reports_peak_and_trough_dates() {
+        let analytics =
+            compute_symbol_analytics(&performance(&[100.0, 120.0, 90.0, 110.0, 95.0]), 0.0);
+
+        assert!((analytics.max_drawdown_pct + 25.0).abs() < 1e-9);
+        assert_eq!(
+            analytics.drawdown_peak_at.as_deref(),
+            Some("2026-01-02T05:00:00Z")
+        );
+        assert_eq!(
+            analytics.drawdown_trough_at.as_deref(),
+            Some("2026-01-03T05:00:00Z")
+        );
+        assert!((analytics.period_return_pct + 5.0).abs() < 1e-9);
+        assert_eq!(analytics.best_period.unwrap().ts, "2026-01-04T05:00:00Z");
+        assert_eq!(analytics.worst_period.unwrap().ts, "2026-01-03T05:00:00Z");
+    }
+
+    #[test]
+    fn risk_free_rate_lowers_sharpe() {
+        let closes = [100.0, 101.0, 100.5, 102.0, 103.0, 102.5];
+        let without = compute_symbol_analytics(&performance(&closes), 0.0);
+        let with = compute_symbol_analytics(&performance(&closes), 5.0);
+
+        assert!(with.sharpe_ratio.unwrap() < without.sharpe_ratio.unwrap());
+        assert!(with.sortino_ratio.unwrap() < without.sortino_ratio.unwrap());
+    }
+
+    #[test]
+    fn sortino_uses_the_sample_denominator() {
+        let ratio = sortino_ratio(&[0.02, -0.01], 0.0, 1.0).unwrap();
+
+        assert!((ratio - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flat_series_has_no_ratios() {
+        let analytics = compute_symbol_analytics(&performance(&[50.0, 50.0, 50.0]), 0.0);
+
+        assert_eq!(analytics.annualized_volatility_pct, Some(0.0));
+        assert_eq!(analytics.sharpe_ratio, None);
+        assert_eq!(analytics.max_drawdown_pct, 0.0);
+        assert_eq!(analytics.drawdown_peak_at, None);
+    }
+
+    fn closes_strategy() -> impl Strategy<Value = Vec<f64>> {
+        prop::collection::vec(1.0f64..1_000.0, 2..60)
+    }
+
+    proptest! {
+        #[test]
+        fn drawdown_is_bounded_and_ordered(closes in closes_strategy()) {
+            if let Some(drawdown) = max_drawdown(&closes) {
+                prop_assert!(drawdown.depth < 0.0 && drawdown.depth > -1.0);
+                prop_assert!(drawdown.peak_index < drawdown.trough_index);
+                let peak = closes[drawdown.peak_index];
+                prop_assert!(closes[..=drawdown.trough_index].iter().all(|close| *close <= peak));
+            }
+        }
+
+        #[test]
+        fn returns_compound_to_the_period_return(closes in closes_strategy()) {
+            let analytics = compute_symbol_analytics(&performance(&closes), 0.0);
+            let compounded = period_returns(&closes)
+                .iter()
+                .fold(1.0, |growth, value| growth * (1.0 + value));
+
+            let expected = (compounded - 1.0) * 100.0;
+            let tolerance = 1e-6 * expected.abs().max(1.0);
+            prop_assert!((analytics.period_return_pct - expected).abs() < tolerance);
+        }
+
+        #[test]
+        fn analytics_are_scale_invariant(closes in closes_strategy(), scale in 0.01f64..100.0) {
+            let scaled: Vec<f64> = closes.iter().map(|close| close * scale).collect();
+            let original = compute_symbol_analytics(&performance(&closes), 2.0);
+            let rescaled = compute_symbol_analytics(&performance(&scaled), 2.0);
+
+            prop_assert!((original.max_drawdown_pct - rescaled.max_drawdown_pct).abs() < 1e-6);
+            prop_assert!((original.period_return_pct - rescaled.period_return_pct).abs()
+                < 1e-9 * original.period_return_pct.abs().max(1.0));
+            match (original.annualized_volatility_pct, rescaled.annualized_volatility_pct) {
+                (Some(original), Some(rescaled)) => {
+                    prop_assert!((original - rescaled).abs() < 1e-9 * original.max(1.0));
+                }
+                (original, rescaled) => prop_assert_eq!(original, rescaled),
+            }
+        }
+
+        #[test]
+        fn rising_series_have_no_drawdown(steps in prop::collection::vec(0.0f64..10.0, 1..60)) {
+            let closes: Vec<f64> = steps
+                .iter()
+                .scan(100.0, |close, step| {
+                    *close += step;
+                    Some(*close)
+                })
+                .collect();
+            let mut series = vec![100.0];
+            series.extend(closes);
+
+            prop_assert!(max_drawdown(&series).is_none());
+            let analytics = compute_symbol_analytics(&performance(&series), 0.0);
+            prop_assert!(analytics.worst_period.is_none_or(|worst| worst.return_pct >= 0.0));
+            prop_assert!(analytics.annualized_volatility_pct.unwrap_or(0.0) >= 0.0);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use super::statistics::{correlation, covariance, period_returns};
+use super::{PricePoint, QuoteStatus, TimeRange};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const REBASE_VALUE: f64 = 100.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkPoint {
+    pub ts: String,
+    pub subject: f64,
+    pub benchmark: f64,
+}
+
+/// Subject and benchmark rebased to 100 on their first common timestamp.
+/// Returns are percentages over the range; beta and correlation are computed
+/// from bar-to-bar returns and need at least two of them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkComparison {
+    pub subject: String,
+    pub benchmark: String,
+    pub range: TimeRange,
+    pub points: Vec<BenchmarkPoint>,
+    pub subject_return_pct: f64,
+    pub benchmark_return_pct: f64,
+    pub excess_return_pct: f64,
+    pub beta: Option<f64>,
+    pub correlation: Option<f64>,
+    pub status: QuoteStatus,
+}
+
+/// Aligns both series on the timestamps they share and rebases them. Returns
+/// `None` when fewer than two timestamps overlap or either series starts at a
+/// non-positive value.
+pub fn compare_to_benchmark(
+    subject: &str,
+    subject_points: &[PricePoint],
+    benchmark: &str,
+    benchmark_points: &[PricePoint],
+    range: TimeRange,
+    status: QuoteStatus,
+) -> Option<BenchmarkComparison> {
+    let benchmark_by_ts: HashMap<&str, f64> = benchmark_points
+        .iter()
+        .map(|point| (point.ts.as_str(), point.close))
+        .collect();
+    let aligned: Vec<(&str, f64, f64)> = subject_points
+        .iter()
+        .filter_map(|point| {
+            let benchmark_close = benchmark_by_ts.get(point.ts.as_str())?;
+            Some((point.ts.as_str(), point.close, *benchmark_close))
+        })
+        .collect();
+
+    let (_, subject_base, benchmark_base) = *aligned.first()?;
+    if aligned.len() < 2 || subject_base <= 0.0 || benchmark_base <= 0.0 {
+        return None;
+    }
+
+    let points: Vec<BenchmarkPoint> = aligned
+        .iter()
+        .map(|(ts, subject_close, benchmark_close)| BenchmarkPoint {
+            ts: ts.to_string(),
+            subject: subject_close / subject_base * REBASE_VALUE,
+            benchmark: benchmark_close / benchmark_base * REBASE_VALUE,
+        })
+        .collect();
+
+    let subject_closes: Vec<f64> = aligned.iter().map(|(_, close, _)| *close).collect();
+    let benchmark_closes: Vec<f64> = aligned.iter().map(|(_, _, close)| *close).collect();
+    let subject_returns = period_returns(&subject_closes);
+    let benchmark_returns = period_returns(&benchmark_closes);
+
+    let beta = covariance(&subject_returns, &benchmark_returns).and_then(|cov| {
+        let variance = covariance(&benchmark_returns, &benchmark_returns)?;
+        (variance > 0.0).then_some(cov / variance)
+    });
+
+    let last = points.last()?;
+    let subject_return_pct = last.subject - REBASE_VALUE;
+    let benchmark_return_pct = last.benchmark - REBASE_VALUE;
+
+    Some(BenchmarkComparison {
+        subject: subject.to_string(),
+        benchmark: benchmark.to_string(),
+        range,
+        subject_return_pct,
+        benchmark_return_pct,
+        excess_return_pct: subject_return_pct - benchmark_return_pct,
+        beta,
+        correlation: correlation(&subject_returns, &benchmark_returns),
+        points,
+        status,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(closes: &[(u32, f64)]) -> Vec<PricePoint> {
+        closes
+            .iter()
+            .map(|(day, close)| PricePoint::new(format!("2026-02-{day:02}T05:00:00Z"), *close))
+            .collect()
+    }
+
+    #[test]
+    fn series_are_aligned_on_common_timestamps_and_rebased() {
+        let subject = points(&[(2, 50.0), (3, 55.0), (4, 60.0), (5, 66.0)]);
+        let benchmark = points(&[(1, 390.0), (2, 400.0), (4, 420.0), (5, 440.0)]);
+
+        let comparison = compare_to_benchmark(
+            "AAPL",
+            &subject,
+            "SPY",
+            &benchmark,
+            TimeRange::OneWeek,
+            QuoteStatus::Fresh,
+        )
+        .unwrap();
+
+        let timestamps: Vec<&str> = comparison
+            .points
+            .iter()
+            .map(|point| point.ts.as_str())
+            .collect();
+        assert_eq!(
+            timestamps,
+            vec![
+                "2026-02-02T05:00:00Z",
+                "2026-02-04T05:00:00Z",
+                "2026-02-05T05:00:00Z"
+            ]
+        );
+        assert_eq!(comparison.points[0].subject, 100.0);
+        assert_eq!(comparison.points[0].benchmark, 100.0);
+        assert!((comparison.subject_return_pct - 32.0).abs() < 1e-9);
+        assert!((comparison.benchmark_return_pct - 10.0).abs() < 1e-9);
+        assert!((comparison.excess_return_pct - 22.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn leveraged_series_has_beta_two_and_full_correlation() {
+        let benchmark = points(&[(2, 100.0), (3, 101.0), (4, 99.0), (5, 102.0)]);
+        let mut subject_close = 100.0;
+        let subject_closes: Vec<(u32, f64)> = benchmark
+            .windows(2)
+            .enumerate()
+            .map(|(index, window)| {
+                subject_close *= 1.0 + 2.0 * (window[1].close / window[0].close - 1.0);
+                (index as u32 + 3, subject_close)
+            })
+            .collect();
+        let mut subject = points(&[(2, 100.0)]);
+        subject.extend(points(&subject_closes));
+
+        let comparison = compare_to_benchmark(
+            "TQQQ",
+            &subject,
+            "QQQ",
+            &benchmark,
+            TimeRange::OneWeek,
+            QuoteStatus::Fresh,
+        )
+        .unwrap();
+
+        assert!((comparison.beta.unwrap() - 2.0).abs() < 1e-9);
+        assert!((comparison.correlation.unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn comparison_needs_two_overlapping_points() {
+        let subject = points(&[(2, 50.0), (3, 55.0)]);
+        let benchmark = points(&[(3, 400.0), (4, 420.0)]);
+
+        assert!(
+            compare_to_benchmark(
+                "AAPL",
+                &subject,
+                "SPY",
+                &benchmark,
+                TimeRange::OneWeek,
+                QuoteStatus::Fresh,
+            )
+            .is_none()
+        );
+    }
+}
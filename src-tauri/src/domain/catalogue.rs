@@ -0,0 +1,818 @@
+use super::models::Locale;
+use std::collections::BTreeMap;
+use std::sync::{PoisonError, RwLock};
+
+/// Message templates keyed by error code, or by `code.reason` when one code
+/// covers several failures. `{name}` is replaced by the error's argument of
+/// that name; other braces are left as they are.
+const ENGLISH: &[(&str, &str)] = &[
+    ("unknown_error", "Something went wrong ({code})."),
+    // Settings
+    ("invalid_settings.api_key_required", "API key is required."),
+    (
+        "invalid_settings.api_key_too_short",
+        "API key looks too short.",
+    ),
+    (
+        "invalid_settings.api_key_missing",
+        "Save a valid API key in Settings first.",
+    ),
+    (
+        "invalid_settings.auto_refresh_range",
+        "Auto refresh must be between {min} and {max} seconds.",
+    ),
+    (
+        "invalid_settings.cache_entries_range",
+        "Cache entry limit must be between {min} and {max}.",
+    ),
+    (
+        "invalid_settings.cache_size_range",
+        "Cache size limit must be between {min} and {max} MB.",
+    ),
+    (
+        "invalid_settings.risk_free_rate_range",
+        "Risk-free rate must be between 0 and {max}%.",
+    ),
+    (
+        "invalid_settings.quiet_hours_format",
+        "Quiet hours must use the HH:MM format.",
+    ),
+    (
+        "invalid_settings.quiet_hours_empty",
+        "Quiet hours must start and end at different times.",
+    ),
+    // Symbols and watchlist
+    (
+        "invalid_symbol",
+        "The provider did not recognize the symbol: {detail}",
+    ),
+    ("invalid_symbol.empty", "Symbol cannot be empty."),
+    ("invalid_symbol.too_long", "Symbol is too long."),
+    (
+        "invalid_symbol.invalid_characters",
+        "Symbol contains invalid characters.",
+    ),
+    ("symbol_exists", "{symbol} is already in your watchlist."),
+    ("symbol_not_found", "{symbol} is not in your watchlist."),
+    // Analytics
+    (
+        "insufficient_overlap",
+        "{subject} and {benchmark} share fewer than two data points in this range.",
+    ),
+    (
+        "invalid_comparison.symbol_count",
+        "Compare between 1 and {max} symbols.",
+    ),
+    (
+        "invalid_comparison.correlation_symbols",
+        "Add at least two symbols to compute correlations.",
+    ),
+    (
+        "invalid_comparison.correlation_range",
+        "Correlations use daily returns and are not available for {range}. Choose 1W, 1M or 3M.",
+    ),
+    (
+        "invalid_indicator.count",
+        "Request between 1 and {max} indicators.",
+    ),
+    (
+        "invalid_indicator.macd_periods",
+        "MACD fast period must be shorter than the slow period.",
+    ),
+    (
+        "invalid_indicator.bollinger_width",
+        "Bollinger band width must be greater than zero.",
+    ),
+    (
+        "invalid_indicator.period",
+        "Indicator periods must be between 1 and {max}.",
+    ),
+    // Alerts
+    ("alert_not_found", "Alert rule {id} does not exist."),
+    (
+        "invalid_alert.price",
+        "Alert price must be greater than zero.",
+    ),
+    (
+        "invalid_alert.threshold",
+        "Percent change threshold must be greater than zero.",
+    ),
+    (
+        "invalid_alert.moving_average_period",
+        "Moving average period must be between 2 and {max}.",
+    ),
+    (
+        "invalid_alert.cooldown",
+        "Alert cooldown cannot exceed seven days.",
+    ),
+    (
+        "alert_emit_failed",
+        "Unable to send alert to the window: {detail}",
+    ),
+    (
+        "notification_failed",
+        "Unable to show desktop notification: {detail}",
+    ),
+    // Alert messages
+    ("alert_notification_title", "{symbol} alert"),
+    (
+        "alert_message.price_above",
+        "{symbol} rose above {threshold} (now {price}).",
+    ),
+    (
+        "alert_message.price_below",
+        "{symbol} fell below {threshold} (now {price}).",
+    ),
+    (
+        "alert_message.percent_change",
+        "{symbol} moved {change}% today, beyond {threshold}%.",
+    ),
+    (
+        "alert_message.crosses_above_average",
+        "{symbol} crossed above its {period}-day average of {average} (now {price}).",
+    ),
+    (
+        "alert_message.crosses_below_average",
+        "{symbol} crossed below its {period}-day average of {average} (now {price}).",
+    ),
+    // Transactions
+    (
+        "invalid_transaction.date_format",
+        "Transaction date must use the YYYY-MM-DD format.",
+    ),
+    (
+        "invalid_transaction.negative_fees",
+        "Fees cannot be negative.",
+    ),
+    (
+        "invalid_transaction.quantity",
+        "Quantity must be greater than zero.",
+    ),
+    ("invalid_transaction.price", "Price cannot be negative."),
+    (
+        "invalid_transaction.dividend",
+        "Dividend per share must be greater than zero.",
+    ),
+    (
+        "invalid_transaction.split_ratio",
+        "Split ratio must be greater than zero.",
+    ),
+    (
+        "invalid_transaction.dividend_without_shares",
+        "No {symbol} shares were held on {date} to receive a dividend.",
+    ),
+    (
+        "invalid_transaction.oversold",
+        "Cannot sell {quantity} {symbol} shares on {date}; only {held} were held.",
+    ),
+    ("transaction_not_found", "Transaction {id} does not exist."),
+    // Webhooks
+    ("webhook_not_found", "Webhook {id} does not exist."),
+    (
+        "invalid_webhook.name_length",
+        "Webhook name must be between 1 and {max} characters.",
+    ),
+    (
+        "invalid_webhook.url",
+        "Webhook URL must be an http:// or https:// address.",
+    ),
+    (
+        "invalid_webhook.header_count",
+        "A webhook can have at most {max} headers.",
+    ),
+    (
+        "invalid_webhook.header",
+        "Header \"{header}\" is not valid.",
+    ),
+    (
+        "invalid_webhook.template_too_long",
+        "Body template is too long.",
+    ),
+    (
+        "invalid_webhook.unclosed_placeholder",
+        "Body template has an unclosed {{ placeholder.",
+    ),
+    (
+        "invalid_webhook.unknown_placeholder",
+        "Body template uses unknown placeholder \"{name}\".",
+    ),
+    (
+        "invalid_webhook.invalid_json",
+        "Body template is not valid JSON: {detail}",
+    ),
+    // Provider
+    (
+        "network_timeout",
+        "Connection timed out while contacting the provider.",
+    ),
+    (
+        "network_connect_error",
+        "Unable to connect to provider. Check your network connection.",
+    ),
+    ("network_error", "Provider request failed: {detail}"),
+    (
+        "invalid_api_key",
+        "The provider rejected the API key: {detail}",
+    ),
+    (
+        "rate_limited",
+        "The provider's rate limit was reached: {detail}",
+    ),
+    ("provider_error", "The provider returned an error: {detail}"),
+    (
+        "provider_payload_parse_failed",
+        "Unable to parse provider response: {detail}",
+    ),
+    (
+        "provider_payload_invalid.missing_price",
+        "Quote payload missing price.",
+    ),
+    (
+        "provider_payload_invalid.missing_values",
+        "Time series payload missing values.",
+    ),
+    (
+        "provider_payload_invalid.no_values",
+        "Time series payload contains no values.",
+    ),
+    (
+        "provider_payload_invalid.no_valid_closes",
+        "Time series payload contains no valid close values.",
+    ),
+    (
+        "http_client_init_failed",
+        "Failed to initialize HTTP client: {detail}",
+    ),
+    (
+        "offline.cached_quotes",
+        "Offline mode is on. Showing cached quotes.",
+    ),
+    (
+        "offline.cached_chart",
+        "Offline mode is on. Showing cached chart data.",
+    ),
+    (
+        "offline.no_cached_chart",
+        "Offline mode is on and no cached chart data is available.",
+    ),
+    (
+        "offline.not_contacted",
+        "Offline mode is on. The provider was not contacted.",
+    ),
+    (
+        "offline.webhook_not_sent",
+        "Offline mode is on. The webhook was not sent.",
+    ),
+    // Storage
+    (
+        "cache_clear_failed",
+        "Unable to clear cache directory: {detail}",
+    ),
+    (
+        "timeseries_cache_evict_failed",
+        "Unable to evict timeseries cache entry: {detail}",
+    ),
+    (
+        "timeseries_cache_remove_failed",
+        "Unable to remove timeseries cache entry: {detail}",
+    ),
+    (
+        "diagnostics_bundle_failed",
+        "Unable to build the diagnostics bundle: {detail}",
+    ),
+    (
+        "invalid_diagnostics_path",
+        "Choose an absolute path ending in .zip for the diagnostics bundle.",
+    ),
+    (
+        "log_level_update_failed",
+        "Unable to change the log level: {detail}",
+    ),
+    (
+        "log_file_init_failed",
+        "Unable to open the log file: {detail}",
+    ),
+    (
+        "logging_init_failed",
+        "Unable to install the logger: {detail}",
+    ),
+    ("cli_output_failed", "Unable to format the output: {detail}"),
+    (
+        "*_path_resolution_failed",
+        "Unable to resolve the app data directory: {detail}",
+    ),
+    ("*_read_failed", "Unable to read {file}: {detail}"),
+    ("*_parse_failed", "Unable to parse {file}: {detail}"),
+    ("*_serialize_failed", "Unable to serialize {file}: {detail}"),
+    ("*_write_failed", "Unable to write {file}: {detail}"),
+    ("*_parent_dir_missing", "{file} has no parent directory."),
+    (
+        "*_dir_create_failed",
+        "Unable to create the directory for {file}: {detail}",
+    ),
+];
+
+const GERMAN: &[(&str, &str)] = &[
+    ("unknown_error", "Etwas ist schiefgelaufen ({code})."),
+    // Settings
+    (
+        "invalid_settings.api_key_required",
+        "Ein API-Schlüssel ist erforderlich.",
+    ),
+    (
+        "invalid_settings.api_key_too_short",
+        "Der API-Schlüssel scheint zu kurz zu sein.",
+    ),
+    (
+        "invalid_settings.api_key_missing",
+        "Speichern Sie zuerst einen gültigen API-Schlüssel in den Einstellungen.",
+    ),
+    (
+        "invalid_settings.auto_refresh_range",
+        "Die automatische Aktualisierung muss zwischen {min} und {max} Sekunden liegen.",
+    ),
+    (
+        "invalid_settings.cache_entries_range",
+        "Das Limit für Cache-Einträge muss zwischen {min} und {max} liegen.",
+    ),
+    (
+        "invalid_settings.cache_size_range",
+        "Die Cache-Größe muss zwischen {min} und {max} MB liegen.",
+    ),
+    (
+        "invalid_settings.risk_free_rate_range",
+        "Der risikofreie Zinssatz muss zwischen 0 und {max} % liegen.",
+    ),
+    (
+        "invalid_settings.quiet_hours_format",
+        "Ruhezeiten müssen das Format HH:MM verwenden.",
+    ),
+    (
+        "invalid_settings.quiet_hours_empty",
+        "Beginn und Ende der Ruhezeiten müssen sich unterscheiden.",
+    ),
+    // Symbols and watchlist
+    (
+        "invalid_symbol",
+        "Der Anbieter kennt das Symbol nicht: {detail}",
+    ),
+    ("invalid_symbol.empty", "Das Symbol darf nicht leer sein."),
+    ("invalid_symbol.too_long", "Das Symbol ist zu lang."),
+    (
+        "invalid_symbol.invalid_characters",
+        "Das Symbol enthält ungültige Zeichen.",
+    ),
+    ("symbol_exists", "{symbol} ist bereits in Ihrer Watchlist."),
+    ("symbol_not_found", "{symbol} ist nicht in Ihrer Watchlist."),
+    // Analytics
+    (
+        "insufficient_overlap",
+        "{subject} und {benchmark} haben in diesem Zeitraum weniger als zwei gemeinsame Datenpunkte.",
+    ),
+    (
+        "invalid_comparison.symbol_count",
+        "Vergleichen Sie zwischen 1 und {max} Symbole.",
+    ),
+    (
+        "invalid_comparison.correlation_symbols",
+        "Fügen Sie mindestens zwei Symbole hinzu, um Korrelationen zu berechnen.",
+    ),
+    (
+        "invalid_comparison.correlation_range",
+        "Korrelationen beruhen auf Tagesrenditen und sind für {range} nicht verfügbar. Wählen Sie 1W, 1M oder 3M.",
+    ),
+    (
+        "invalid_indicator.count",
+        "Fordern Sie zwischen 1 und {max} Indikatoren an.",
+    ),
+    (
+        "invalid_indicator.macd_periods",
+        "Der schnelle MACD-Zeitraum muss kürzer als der langsame sein.",
+    ),
+    (
+        "invalid_indicator.bollinger_width",
+        "Die Breite der Bollinger-Bänder muss größer als null sein.",
+    ),
+    (
+        "invalid_indicator.period",
+        "Indikatorzeiträume müssen zwischen 1 und {max} liegen.",
+    ),
+    // Alerts
+    ("alert_not_found", "Die Alarmregel {id} existiert nicht."),
+    (
+        "invalid_alert.price",
+        "Der Alarmpreis muss größer als null sein.",
+    ),
+    (
+        "invalid_alert.threshold",
+        "Die Schwelle für die prozentuale Änderung muss größer als null sein.",
+    ),
+    (
+        "invalid_alert.moving_average_period",
+        "Der Zeitraum des gleitenden Durchschnitts muss zwischen 2 und {max} liegen.",
+    ),
+    (
+        "invalid_alert.cooldown",
+        "Die Alarmpause darf sieben Tage nicht überschreiten.",
+    ),
+    (
+        "alert_emit_failed",
+        "Der Alarm konnte nicht an das Fenster gesendet werden: {detail}",
+    ),
+    (
+        "notification_failed",
+        "Die Desktop-Benachrichtigung konnte nicht angezeigt werden: {detail}",
+    ),
+    // Alert messages
+    ("alert_notification_title", "{symbol}-Alarm"),
+    (
+        "alert_message.price_above",
+        "{symbol} ist über {threshold} gestiegen (jetzt {price}).",
+    ),
+    (
+        "alert_message.price_below",
+        "{symbol} ist unter {threshold} gefallen (jetzt {price}).",
+    ),
+    (
+        "alert_message.percent_change",
+        "{symbol} hat sich heute um {change} % bewegt, mehr als {threshold} %.",
+    ),
+    (
+        "alert_message.crosses_above_average",
+        "{symbol} hat den {period}-Tage-Durchschnitt von {average} überschritten (jetzt {price}).",
+    ),
+    (
+        "alert_message.crosses_below_average",
+        "{symbol} hat den {period}-Tage-Durchschnitt von {average} unterschritten (jetzt {price}).",
+    ),
+    // Transactions
+    (
+        "invalid_transaction.date_format",
+        "Das Transaktionsdatum muss das Format JJJJ-MM-TT verwenden.",
+    ),
+    (
+        "invalid_transaction.negative_fees",
+        "Gebühren dürfen nicht negativ sein.",
+    ),
+    (
+        "invalid_transaction.quantity",
+        "Die Menge muss größer als null sein.",
+    ),
+    (
+        "invalid_transaction.price",
+        "Der Preis darf nicht negativ sein.",
+    ),
+    (
+        "invalid_transaction.dividend",
+        "Die Dividende je Aktie muss größer als null sein.",
+    ),
+    (
+        "invalid_transaction.split_ratio",
+        "Das Split-Verhältnis muss größer als null sein.",
+    ),
+    (
+        "invalid_transaction.dividend_without_shares",
+        "Am {date} wurden keine {symbol}-Aktien für eine Dividende gehalten.",
+    ),
+    (
+        "invalid_transaction.oversold",
+        "Am {date} können nicht {quantity} {symbol}-Aktien verkauft werden; gehalten wurden nur {held}.",
+    ),
+    (
+        "transaction_not_found",
+        "Die Transaktion {id} existiert nicht.",
+    ),
+    // Webhooks
+    ("webhook_not_found", "Der Webhook {id} existiert nicht."),
+    (
+        "invalid_webhook.name_length",
+        "Der Webhook-Name muss zwischen 1 und {max} Zeichen lang sein.",
+    ),
+    (
+        "invalid_webhook.url",
+        "Die Webhook-URL muss eine http://- oder https://-Adresse sein.",
+    ),
+    (
+        "invalid_webhook.header_count",
+        "Ein Webhook kann höchstens {max} Header haben.",
+    ),
+    (
+        "invalid_webhook.header",
+        "Der Header \"{header}\" ist ungültig.",
+    ),
+    (
+        "invalid_webhook.template_too_long",
+        "Die Body-Vorlage ist zu lang.",
+    ),
+    (
+        "invalid_webhook.unclosed_placeholder",
+        "Die Body-Vorlage enthält einen nicht geschlossenen {{-Platzhalter.",
+    ),
+    (
+        "invalid_webhook.unknown_placeholder",
+        "Die Body-Vorlage verwendet den unbekannten Platzhalter \"{name}\".",
+    ),
+    (
+        "invalid_webhook.invalid_json",
+        "Die Body-Vorlage ist kein gültiges JSON: {detail}",
+    ),
+    // Provider
+    (
+        "network_timeout",
+        "Zeitüberschreitung bei der Verbindung zum Anbieter.",
+    ),
+    (
+        "network_connect_error",
+        "Keine Verbindung zum Anbieter. Prüfen Sie Ihre Netzwerkverbindung.",
+    ),
+    (
+        "network_error",
+        "Die Anfrage an den Anbieter ist fehlgeschlagen: {detail}",
+    ),
+    (
+        "invalid_api_key",
+        "Der Anbieter hat den API-Schlüssel abgelehnt: {detail}",
+    ),
+    (
+        "rate_limited",
+        "Das Anfragelimit des Anbieters ist erreicht: {detail}",
+    ),
+    (
+        "provider_error",
+        "Der Anbieter hat einen Fehler gemeldet: {detail}",
+    ),
+    (
+        "provider_payload_parse_failed",
+        "Die Antwort des Anbieters konnte nicht gelesen werden: {detail}",
+    ),
+    (
+        "provider_payload_invalid.missing_price",
+        "Im Kurs des Anbieters fehlt der Preis.",
+    ),
+    (
+        "provider_payload_invalid.missing_values",
+        "In der Zeitreihe des Anbieters fehlen die Werte.",
+    ),
+    (
+        "provider_payload_invalid.no_values",
+        "Die Zeitreihe des Anbieters enthält keine Werte.",
+    ),
+    (
+        "provider_payload_invalid.no_valid_closes",
+        "Die Zeitreihe des Anbieters enthält keine gültigen Schlusskurse.",
+    ),
+    (
+        "http_client_init_failed",
+        "Der HTTP-Client konnte nicht initialisiert werden: {detail}",
+    ),
+    (
+        "offline.cached_quotes",
+        "Der Offlinemodus ist aktiv. Es werden zwischengespeicherte Kurse angezeigt.",
+    ),
+    (
+        "offline.cached_chart",
+        "Der Offlinemodus ist aktiv. Es werden zwischengespeicherte Chartdaten angezeigt.",
+    ),
+    (
+        "offline.no_cached_chart",
+        "Der Offlinemodus ist aktiv und es sind keine zwischengespeicherten Chartdaten verfügbar.",
+    ),
+    (
+        "offline.not_contacted",
+        "Der Offlinemodus ist aktiv. Der Anbieter wurde nicht kontaktiert.",
+    ),
+    (
+        "offline.webhook_not_sent",
+        "Der Offlinemodus ist aktiv. Der Webhook wurde nicht gesendet.",
+    ),
+    // Storage
+    (
+        "cache_clear_failed",
+        "Das Cache-Verzeichnis konnte nicht geleert werden: {detail}",
+    ),
+    (
+        "timeseries_cache_evict_failed",
+        "Ein Zeitreihen-Cacheeintrag konnte nicht verdrängt werden: {detail}",
+    ),
+    (
+        "timeseries_cache_remove_failed",
+        "Ein Zeitreihen-Cacheeintrag konnte nicht entfernt werden: {detail}",
+    ),
+    (
+        "diagnostics_bundle_failed",
+        "Das Diagnosepaket konnte nicht erstellt werden: {detail}",
+    ),
+    (
+        "invalid_diagnostics_path",
+        "Wählen Sie für das Diagnosepaket einen absoluten Pfad mit der Endung .zip.",
+    ),
+    (
+        "log_level_update_failed",
+        "Die Protokollstufe konnte nicht geändert werden: {detail}",
+    ),
+    (
+        "log_file_init_failed",
+        "Die Protokolldatei konnte nicht geöffnet werden: {detail}",
+    ),
+    (
+        "logging_init_failed",
+        "Die Protokollierung konnte nicht eingerichtet werden: {detail}",
+    ),
+    (
+        "cli_output_failed",
+        "Die Ausgabe konnte nicht formatiert werden: {detail}",
+    ),
+    (
+        "*_path_resolution_failed",
+        "Das App-Datenverzeichnis konnte nicht ermittelt werden: {detail}",
+    ),
+    (
+        "*_read_failed",
+        "{file} konnte nicht gelesen werden: {detail}",
+    ),
+    (
+        "*_parse_failed",
+        "{file} konnte nicht verarbeitet werden: {detail}",
+    ),
+    (
+        "*_serialize_failed",
+        "{file} konnte nicht serialisiert werden: {detail}",
+    ),
+    (
+        "*_write_failed",
+        "{file} konnte nicht geschrieben werden: {detail}",
+    ),
+    (
+        "*_parent_dir_missing",
+        "{file} hat kein übergeordnetes Verzeichnis.",
+    ),
+    (
+        "*_dir_create_failed",
+        "Das Verzeichnis für {file} konnte nicht angelegt werden: {detail}",
+    ),
+];
+
+/// Storage codes share a message per operation: `settings_read_failed` and
+/// `ledger_read_failed` both use the `*_read_failed` template.
+const CODE_FAMILIES: [&str; 7] = [
+    "_path_resolution_failed",
+    "_parent_dir_missing",
+    "_dir_create_failed",
+    "_serialize_failed",
+    "_parse_failed",
+    "_read_failed",
+    "_write_failed",
+];
+
+static LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+
+/// Sets the locale new errors are rendered in, from the saved settings.
+pub fn set_locale(locale: Locale) {
+    *LOCALE.write().unwrap_or_else(PoisonError::into_inner) = locale;
+}
+
+pub fn current_locale() -> Locale {
+    *LOCALE.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Renders the message for `code` (and `reason`, if any) in `locale`. A
+/// template missing from the locale falls back to English, and an unknown
+/// code to a generic message naming it.
+pub fn render_message(
+    locale: Locale,
+    code: &str,
+    reason: Option<&str>,
+    args: &BTreeMap<String, String>,
+) -> String {
+    match template(locale, code, reason).or_else(|| template(Locale::En, code, reason)) {
+        Some(template) => fill(template, args),
+        None => {
+            let args = BTreeMap::from([("code".to_string(), code.to_string())]);
+            fill(find(locale, "unknown_error").unwrap_or_default(), &args)
+        }
+    }
+}
+
+fn template(locale: Locale, code: &str, reason: Option<&str>) -> Option<&'static str> {
+    reason
+        .and_then(|reason| find(locale, &format!("{code}.{reason}")))
+        .or_else(|| find(locale, code))
+        .or_else(|| {
+            CODE_FAMILIES
+                .iter()
+                .find(|family| code.ends_with(*family))
+                .and_then(|family| find(locale, &format!("*{family}")))
+        })
+}
+
+fn find(locale: Locale, key: &str) -> Option<&'static str> {
+    let table = match locale {
+        Locale::En => ENGLISH,
+        Locale::De => GERMAN,
+    };
+    table
+        .iter()
+        .find(|(candidate, _)| *candidate == key)
+        .map(|(_, template)| *template)
+}
+
+fn fill(template: &str, args: &BTreeMap<String, String>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        output.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after
+            .find('}')
+            .and_then(|close| args.get(&after[..close]).map(|value| (close, value)));
+        match value {
+            Some((close, value)) => {
+                output.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = after;
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .filter(|name| !name.is_empty() && name.chars().all(|ch| ch.is_ascii_lowercase()))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn every_locale_covers_every_key_with_the_same_placeholders() {
+        assert_eq!(ENGLISH.len(), GERMAN.len());
+        for (key, english) in ENGLISH {
+            let german =
+                find(Locale::De, key).unwrap_or_else(|| panic!("{key} has no German text"));
+            assert_eq!(placeholders(english), placeholders(german), "{key}");
+        }
+    }
+
+    #[test]
+    fn messages_are_rendered_per_locale() {
+        let args = BTreeMap::from([("symbol".to_string(), "AAPL".to_string())]);
+
+        assert_eq!(
+            render_message(Locale::En, "symbol_exists", None, &args),
+            "AAPL is already in your watchlist."
+        );
+        assert_eq!(
+            render_message(Locale::De, "symbol_exists", None, &args),
+            "AAPL ist bereits in Ihrer Watchlist."
+        );
+        assert_eq!(
+            render_message(
+                Locale::De,
+                "invalid_symbol",
+                Some("empty"),
+                &BTreeMap::new()
+            ),
+            "Das Symbol darf nicht leer sein."
+        );
+    }
+
+    #[test]
+    fn storage_codes_share_a_family_and_unknown_codes_fall_back() {
+        let args = BTreeMap::from([
+            ("file".to_string(), "watchlist.json".to_string()),
+            ("detail".to_string(), "disk full".to_string()),
+        ]);
+
+        assert_eq!(
+            render_message(Locale::En, "watchlist_write_failed", None, &args),
+            "Unable to write watchlist.json: disk full"
+        );
+        assert_eq!(
+            render_message(Locale::En, "no_such_code", Some("x"), &args),
+            "Something went wrong (no_such_code)."
+        );
+        assert_eq!(
+            render_message(
+                Locale::En,
+                "invalid_webhook",
+                Some("unclosed_placeholder"),
+                &args
+            ),
+            "Body template has an unclosed {{ placeholder."
+        );
+    }
+}
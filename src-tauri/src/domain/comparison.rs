@@ -0,0 +1,179 @@
+use super::{QuoteStatus, SymbolPerformance, TimeRange};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// How a symbol with no bar at a shared timestamp is filled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GapFill {
+    /// Carry the previous close forward. Timestamps before a symbol's first
+    /// bar stay empty.
+    #[default]
+    Forward,
+    /// Leave the gap empty.
+    None,
+    /// Keep only timestamps every symbol has a bar for.
+    Intersect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonLine {
+    pub symbol: String,
+    pub values: Vec<Option<f64>>,
+    pub status: QuoteStatus,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+/// Percent change from each symbol's first value on a shared timestamp axis;
+/// `values[i]` of every line belongs to `timestamps[i]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonSeries {
+    pub range: TimeRange,
+    pub gap_fill: GapFill,
+    pub timestamps: Vec<String>,
+    pub lines: Vec<ComparisonLine>,
+    pub missing_symbols: Vec<String>,
+}
+
+pub fn build_comparison(
+    range: TimeRange,
+    series: &[SymbolPerformance],
+    gap_fill: GapFill,
+    missing_symbols: Vec<String>,
+) -> ComparisonSeries {
+    let closes_by_symbol: Vec<HashMap<&str, f64>> = series
+        .iter()
+        .map(|performance| {
+            performance
+                .points
+                .iter()
+                .map(|point| (point.ts.as_str(), point.close))
+                .collect()
+        })
+        .collect();
+
+    let timestamps: Vec<&str> = series
+        .iter()
+        .flat_map(|performance| performance.points.iter().map(|point| point.ts.as_str()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|ts| {
+            gap_fill != GapFill::Intersect
+                || closes_by_symbol
+                    .iter()
+                    .all(|closes| closes.contains_key(ts))
+        })
+        .collect();
+
+    let lines = series
+        .iter()
+        .zip(&closes_by_symbol)
+        .map(|(performance, closes)| {
+            let mut last_close = None;
+            let aligned: Vec<Option<f64>> = timestamps
+                .iter()
+                .map(|ts| match closes.get(ts) {
+                    Some(close) => {
+                        last_close = Some(*close);
+                        last_close
+                    }
+                    None if gap_fill == GapFill::Forward => last_close,
+                    None => None,
+                })
+                .collect();
+
+            let base = aligned.iter().flatten().copied().find(|close| *close > 0.0);
+            ComparisonLine {
+                symbol: performance.symbol.clone(),
+                values: aligned
+                    .into_iter()
+                    .map(|close| Some((close? / base? - 1.0) * 100.0))
+                    .collect(),
+                status: performance.status,
+                error_code: performance.error_code.clone(),
+                error_message: performance.error_message.clone(),
+            }
+        })
+        .collect();
+
+    ComparisonSeries {
+        range,
+        gap_fill,
+        timestamps: timestamps.into_iter().map(str::to_string).collect(),
+        lines,
+        missing_symbols,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::PricePoint;
+
+    fn performance(symbol: &str, closes: &[(u32, f64)]) -> SymbolPerformance {
+        let points = closes
+            .iter()
+            .map(|(day, close)| PricePoint::new(format!("2026-02-{day:02}T05:00:00Z"), *close))
+            .collect();
+        SymbolPerformance::from_points(symbol, TimeRange::OneWeek, points, None, QuoteStatus::Fresh)
+            .unwrap()
+    }
+
+    fn sample_series() -> Vec<SymbolPerformance> {
+        vec![
+            performance("AAPL", &[(2, 100.0), (3, 150.0), (4, 200.0)]),
+            performance("SAP", &[(3, 50.0), (5, 75.0)]),
+        ]
+    }
+
+    #[test]
+    fn forward_fill_carries_the_last_close_across_gaps() {
+        let comparison = build_comparison(
+            TimeRange::OneWeek,
+            &sample_series(),
+            GapFill::Forward,
+            Vec::new(),
+        );
+
+        assert_eq!(comparison.timestamps.len(), 4);
+        assert_eq!(
+            comparison.lines[0].values,
+            vec![Some(0.0), Some(50.0), Some(100.0), Some(100.0)]
+        );
+        assert_eq!(
+            comparison.lines[1].values,
+            vec![None, Some(0.0), Some(0.0), Some(50.0)]
+        );
+    }
+
+    #[test]
+    fn no_fill_leaves_gaps_empty() {
+        let comparison = build_comparison(
+            TimeRange::OneWeek,
+            &sample_series(),
+            GapFill::None,
+            Vec::new(),
+        );
+
+        assert_eq!(comparison.lines[0].values[3], None);
+        assert_eq!(comparison.lines[1].values[2], None);
+    }
+
+    #[test]
+    fn intersect_keeps_only_shared_timestamps() {
+        let comparison = build_comparison(
+            TimeRange::OneWeek,
+            &sample_series(),
+            GapFill::Intersect,
+            vec!["MSFT".to_string()],
+        );
+
+        assert_eq!(comparison.timestamps, vec!["2026-02-03T05:00:00Z"]);
+        assert_eq!(comparison.lines[0].values, vec![Some(0.0)]);
+        assert_eq!(comparison.lines[1].values, vec![Some(0.0)]);
+        assert_eq!(comparison.missing_symbols, vec!["MSFT".to_string()]);
+    }
+}
@@ -0,0 +1,196 @@
+use super::statistics::{correlation, period_returns};
+use super::{AppError, SeriesInterval, SymbolPerformance, TimeRange};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Fewest shared returns a pair needs before its correlation is reported.
+pub const MIN_CORRELATION_OVERLAP: usize = 5;
+
+/// Pairwise correlation of daily returns. `matrix[i][j]` and `overlap[i][j]`
+/// refer to `symbols[i]` and `symbols[j]`; a pair with fewer than
+/// `min_overlap` shared returns has no value and both of its symbols are
+/// listed in `insufficient_overlap`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrelationMatrix {
+    pub range: TimeRange,
+    pub symbols: Vec<String>,
+    pub matrix: Vec<Vec<Option<f64>>>,
+    pub overlap: Vec<Vec<usize>>,
+    pub min_overlap: usize,
+    pub insufficient_overlap: Vec<String>,
+    pub fetched_symbols: Vec<String>,
+    pub missing_symbols: Vec<String>,
+}
+
+/// Correlations compare daily returns, so only ranges backed by daily bars
+/// are accepted.
+pub fn validate_correlation_range(range: TimeRange) -> Result<(), AppError> {
+    if range.interval() != SeriesInterval::OneDay {
+        return Err(AppError::validation("invalid_comparison")
+            .with_reason("correlation_range")
+            .with_arg("range", range.as_key()));
+    }
+    Ok(())
+}
+
+/// Each pair is aligned on the timestamps both series have, so a gap in one
+/// symbol does not shorten every other pair.
+pub fn build_correlation_matrix(
+    range: TimeRange,
+    series: &[SymbolPerformance],
+    fetched_symbols: Vec<String>,
+    missing_symbols: Vec<String>,
+) -> CorrelationMatrix {
+    let closes_by_ts: Vec<HashMap<&str, f64>> = series
+        .iter()
+        .map(|performance| {
+            performance
+                .points
+                .iter()
+                .map(|point| (point.ts.as_str(), point.close))
+                .collect()
+        })
+        .collect();
+
+    let size = series.len();
+    let mut matrix = vec![vec![None; size]; size];
+    let mut overlap = vec![vec![0; size]; size];
+    let mut insufficient = vec![false; size];
+
+    for left in 0..size {
+        overlap[left][left] = series[left].points.len().saturating_sub(1);
+        matrix[left][left] = Some(1.0);
+
+        for right in (left + 1)..size {
+            let (left_closes, right_closes): (Vec<f64>, Vec<f64>) = series[left]
+                .points
+                .iter()
+                .filter_map(|point| {
+                    let right_close = closes_by_ts[right].get(point.ts.as_str())?;
+                    Some((point.close, *right_close))
+                })
+                .unzip();
+            let left_returns = period_returns(&left_closes);
+            let right_returns = period_returns(&right_closes);
+            let shared = left_returns.len().min(right_returns.len());
+
+            let value =
+                if left_returns.len() == right_returns.len() && shared >= MIN_CORRELATION_OVERLAP {
+                    correlation(&left_returns, &right_returns)
+                } else {
+                    insufficient[left] = true;
+                    insufficient[right] = true;
+                    None
+                };
+
+            matrix[left][right] = value;
+            matrix[right][left] = value;
+            overlap[left][right] = shared;
+            overlap[right][left] = shared;
+        }
+    }
+
+    CorrelationMatrix {
+        range,
+        symbols: series
+            .iter()
+            .map(|performance| performance.symbol.clone())
+            .collect(),
+        insufficient_overlap: series
+            .iter()
+            .zip(&insufficient)
+            .filter(|(_, flagged)| **flagged)
+            .map(|(performance, _)| performance.symbol.clone())
+            .collect(),
+        matrix,
+        overlap,
+        min_overlap: MIN_CORRELATION_OVERLAP,
+        fetched_symbols,
+        missing_symbols,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{PricePoint, QuoteStatus};
+
+    fn performance(symbol: &str, closes: &[(u32, f64)]) -> SymbolPerformance {
+        let points = closes
+            .iter()
+            .map(|(day, close)| PricePoint::new(format!("2026-02-{day:02}T05:00:00Z"), *close))
+            .collect();
+        SymbolPerformance::from_points(
+            symbol,
+            TimeRange::OneMonth,
+            points,
+            None,
+            QuoteStatus::Fresh,
+        )
+        .unwrap()
+    }
+
+    fn daily(closes: &[f64]) -> Vec<(u32, f64)> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(day, close)| (day as u32 + 1, *close))
+            .collect()
+    }
+
+    #[test]
+    fn matrix_is_symmetric_with_a_unit_diagonal() {
+        let base = [100.0, 102.0, 101.0, 104.0, 103.0, 106.0, 105.0];
+        let inverse: Vec<f64> = base.iter().map(|close| 10_000.0 / close).collect();
+        let series = vec![
+            performance("AAPL", &daily(&base)),
+            performance("MSFT", &daily(&base.map(|close| close * 3.0))),
+            performance("SH", &daily(&inverse)),
+        ];
+
+        let result = build_correlation_matrix(TimeRange::OneMonth, &series, Vec::new(), Vec::new());
+
+        assert_eq!(result.matrix[0][0], Some(1.0));
+        assert!((result.matrix[0][1].unwrap() - 1.0).abs() < 1e-9);
+        assert!(result.matrix[0][2].unwrap() < -0.99);
+        assert_eq!(result.matrix[2][0], result.matrix[0][2]);
+        assert_eq!(result.overlap[0][1], 6);
+        assert!(result.insufficient_overlap.is_empty());
+    }
+
+    #[test]
+    fn only_daily_ranges_are_accepted() {
+        assert!(validate_correlation_range(TimeRange::OneWeek).is_ok());
+        assert!(validate_correlation_range(TimeRange::ThreeMonths).is_ok());
+
+        let err = validate_correlation_range(TimeRange::OneYear).unwrap_err();
+        assert_eq!(err.reason.as_deref(), Some("correlation_range"));
+        assert!(validate_correlation_range(TimeRange::OneDay).is_err());
+    }
+
+    #[test]
+    fn pairs_with_little_overlap_are_flagged() {
+        let series = vec![
+            performance("AAPL", &daily(&[1.0, 2.0, 3.0, 2.0, 4.0, 3.0, 5.0])),
+            performance("MSFT", &daily(&[5.0, 4.0, 6.0, 5.0, 7.0, 6.0, 8.0])),
+            performance("NEWCO", &[(6, 10.0), (7, 11.0)]),
+        ];
+
+        let result = build_correlation_matrix(
+            TimeRange::OneMonth,
+            &series,
+            vec!["NEWCO".to_string()],
+            Vec::new(),
+        );
+
+        assert!(result.matrix[0][1].is_some());
+        assert_eq!(result.matrix[0][2], None);
+        assert_eq!(result.overlap[1][2], 1);
+        assert_eq!(
+            result.insufficient_overlap,
+            vec!["AAPL".to_string(), "MSFT".to_string(), "NEWCO".to_string()]
+        );
+        assert_eq!(result.fetched_symbols, vec!["NEWCO".to_string()]);
+    }
+}
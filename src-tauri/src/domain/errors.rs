@@ -1,40 +1,156 @@
+use super::OFFLINE_REASON;
+use super::catalogue::{current_locale, render_message};
+use super::models::Locale;
+use super::redaction::redact;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 
+/// Which layer an error came from.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorKind {
+    Validation,
+    Provider,
+    Persistence,
+    Internal,
+}
+
+/// What the UI should suggest to the user for an error.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UserAction {
+    FixInput,
+    UpdateApiKey,
+    WaitAndRetry,
+    CheckConnection,
+    Retry,
+    CheckStorage,
+    ReportIssue,
+}
+
+/// Error returned to the UI. `code`, `reason` and `args` are stable and
+/// identify the failure; `message` is rendered from the error catalogue in
+/// the current locale. Arguments and messages pass through `redact`, so
+/// secrets in formatted transport errors never reach the UI or the logs.
+/// `retryable` and `action` are derived from the kind, code and reason;
+/// `retry_after_seconds` is set when the provider says how long to wait.
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct AppError {
+    pub kind: ErrorKind,
     pub code: String,
+    pub reason: Option<String>,
+    pub args: BTreeMap<String, String>,
     pub message: String,
+    pub retryable: bool,
+    pub retry_after_seconds: Option<u64>,
+    pub action: UserAction,
 }
 
 impl AppError {
-    pub fn validation(code: &str, message: impl Into<String>) -> Self {
-        Self {
+    pub fn new(kind: ErrorKind, code: &str) -> Self {
+        let (retryable, action) = classify(kind, code, None);
+        let mut error = Self {
+            kind,
             code: code.to_string(),
-            message: message.into(),
-        }
+            reason: None,
+            args: BTreeMap::new(),
+            message: String::new(),
+            retryable,
+            retry_after_seconds: None,
+            action,
+        };
+        error.render(current_locale());
+        error
     }
 
-    pub fn provider(code: &str, message: impl Into<String>) -> Self {
-        Self {
-            code: code.to_string(),
-            message: message.into(),
-        }
+    pub fn validation(code: &str) -> Self {
+        Self::new(ErrorKind::Validation, code)
     }
 
-    pub fn persistence(code: &str, message: impl Into<String>) -> Self {
-        Self {
-            code: code.to_string(),
-            message: message.into(),
+    pub fn provider(code: &str) -> Self {
+        Self::new(ErrorKind::Provider, code)
+    }
+
+    pub fn persistence(code: &str) -> Self {
+        Self::new(ErrorKind::Persistence, code)
+    }
+
+    pub fn internal(code: &str) -> Self {
+        Self::new(ErrorKind::Internal, code)
+    }
+
+    /// Narrows the code to one of its catalogue messages, such as `empty`
+    /// for `invalid_symbol`. The reason can change the suggested action, so
+    /// the error is classified again unless a retry delay was already set.
+    pub fn with_reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_string());
+        if self.retry_after_seconds.is_none() {
+            (self.retryable, self.action) = classify(self.kind, &self.code, Some(reason));
         }
+        self.render(current_locale());
+        self
     }
 
-    pub fn internal(code: &str, message: impl Into<String>) -> Self {
-        Self {
-            code: code.to_string(),
-            message: message.into(),
+    /// Fills the `{name}` placeholder of the message with `value`.
+    pub fn with_arg(mut self, name: &str, value: impl Display) -> Self {
+        self.args
+            .insert(name.to_string(), redact(&value.to_string()));
+        self.render(current_locale());
+        self
+    }
+
+    /// Renders the message again in `locale`, for callers that do not use
+    /// the app-wide setting.
+    pub fn localized(mut self, locale: Locale) -> Self {
+        self.render(locale);
+        self
+    }
+
+    /// Marks the error as retryable once `seconds` have passed, typically
+    /// from a `Retry-After` header.
+    pub fn with_retry_after(mut self, seconds: u64) -> Self {
+        self.retryable = true;
+        self.retry_after_seconds = Some(seconds);
+        self.action = UserAction::WaitAndRetry;
+        self
+    }
+
+    /// Whether the error only means one symbol's data could not be loaded,
+    /// so views over several symbols can leave that symbol out. Bad keys,
+    /// rate limits and storage failures hit every symbol alike.
+    pub fn is_symbol_scoped(&self) -> bool {
+        self.kind == ErrorKind::Provider
+            && !matches!(self.code.as_str(), "invalid_api_key" | "rate_limited")
+    }
+
+    fn render(&mut self, locale: Locale) {
+        self.message = redact(&render_message(
+            locale,
+            &self.code,
+            self.reason.as_deref(),
+            &self.args,
+        ));
+    }
+}
+
+fn classify(kind: ErrorKind, code: &str, reason: Option<&str>) -> (bool, UserAction) {
+    match (kind, code, reason) {
+        (_, "invalid_api_key", _)
+        | (
+            _,
+            "invalid_settings",
+            Some("api_key_required" | "api_key_too_short" | "api_key_missing"),
+        ) => (false, UserAction::UpdateApiKey),
+        (_, "rate_limited", _) => (true, UserAction::WaitAndRetry),
+        (_, "network_connect_error" | "network_timeout" | "network_error" | OFFLINE_REASON, _) => {
+            (true, UserAction::CheckConnection)
         }
+        (_, "invalid_symbol", _) | (ErrorKind::Validation, _, _) => (false, UserAction::FixInput),
+        (ErrorKind::Provider, _, _) => (true, UserAction::Retry),
+        (ErrorKind::Persistence, _, _) => (false, UserAction::CheckStorage),
+        (ErrorKind::Internal, _, _) => (false, UserAction::ReportIssue),
     }
 }
 
@@ -45,3 +161,94 @@ impl Display for AppError {
 }
 
 impl std::error::Error for AppError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_map_to_retry_hints_and_actions() {
+        let invalid_key = AppError::provider("invalid_api_key");
+        assert_eq!(invalid_key.kind, ErrorKind::Provider);
+        assert!(!invalid_key.retryable);
+        assert_eq!(invalid_key.action, UserAction::UpdateApiKey);
+
+        let timeout = AppError::provider("network_timeout");
+        assert!(timeout.retryable);
+        assert_eq!(timeout.action, UserAction::CheckConnection);
+
+        let bad_symbol = AppError::validation("invalid_symbol").with_reason("empty");
+        assert_eq!(bad_symbol.kind, ErrorKind::Validation);
+        assert!(!bad_symbol.retryable);
+        assert_eq!(bad_symbol.action, UserAction::FixInput);
+
+        let disk = AppError::persistence("settings_write_failed");
+        assert_eq!(
+            (disk.kind, disk.retryable, disk.action),
+            (ErrorKind::Persistence, false, UserAction::CheckStorage)
+        );
+    }
+
+    #[test]
+    fn only_api_key_settings_errors_ask_for_a_new_key() {
+        let missing_key = AppError::validation("invalid_settings").with_reason("api_key_missing");
+        assert_eq!(missing_key.action, UserAction::UpdateApiKey);
+
+        let quiet_hours =
+            AppError::validation("invalid_settings").with_reason("quiet_hours_format");
+        assert_eq!(
+            (quiet_hours.retryable, quiet_hours.action),
+            (false, UserAction::FixInput)
+        );
+
+        let throttled = AppError::provider("provider_error")
+            .with_retry_after(30)
+            .with_reason("busy");
+        assert_eq!(throttled.action, UserAction::WaitAndRetry);
+    }
+
+    #[test]
+    fn only_per_symbol_provider_failures_are_symbol_scoped() {
+        assert!(AppError::provider("provider_payload_invalid").is_symbol_scoped());
+        assert!(AppError::provider(OFFLINE_REASON).is_symbol_scoped());
+        assert!(!AppError::provider("invalid_api_key").is_symbol_scoped());
+        assert!(!AppError::provider("rate_limited").is_symbol_scoped());
+        assert!(!AppError::persistence("cache_read_failed").is_symbol_scoped());
+        assert!(
+            !AppError::validation("invalid_settings")
+                .with_reason("api_key_missing")
+                .is_symbol_scoped()
+        );
+    }
+
+    #[test]
+    fn retry_after_is_serialized_for_the_ui() {
+        let err = AppError::provider("provider_error")
+            .with_arg("detail", "Busy.")
+            .with_retry_after(30);
+
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({
+                "kind": "provider",
+                "code": "provider_error",
+                "reason": null,
+                "args": { "detail": "Busy." },
+                "message": "The provider returned an error: Busy.",
+                "retryable": true,
+                "retryAfterSeconds": 30,
+                "action": "waitAndRetry",
+            })
+        );
+    }
+
+    #[test]
+    fn messages_follow_the_locale_but_codes_and_args_do_not() {
+        let err = AppError::validation("symbol_exists").with_arg("symbol", "MSFT");
+        assert_eq!(err.message, "MSFT is already in your watchlist.");
+
+        let german = err.clone().localized(Locale::De);
+        assert_eq!(german.message, "MSFT ist bereits in Ihrer Watchlist.");
+        assert_eq!((german.code, german.args), (err.code, err.args));
+    }
+}
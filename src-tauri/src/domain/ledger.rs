@@ -0,0 +1,531 @@
+use super::{
+    AppError, Lot, LotMatching, PortfolioSummary, QuoteSummary, normalize_symbol,
+    summarize_portfolio,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const TRANSACTION_DATE_FORMAT: &str = "%Y-%m-%d";
+const QUANTITY_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionKind {
+    Buy,
+    Sell,
+    Dividend,
+    Split,
+}
+
+/// One ledger entry. `quantity` is the share count for buys and sells and the
+/// split ratio for splits (2.0 for a 2-for-1); `price` is the per-share price
+/// for buys and sells and the per-share payout for dividends.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    pub id: String,
+    pub symbol: String,
+    pub kind: TransactionKind,
+    pub date: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub fees: f64,
+    pub recorded_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInput {
+    pub symbol: String,
+    pub kind: TransactionKind,
+    pub date: String,
+    pub quantity: Option<f64>,
+    pub price: Option<f64>,
+    pub fees: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RealizedGain {
+    pub transaction_id: String,
+    pub symbol: String,
+    pub date: String,
+    pub quantity: f64,
+    pub proceeds: f64,
+    pub cost_basis: f64,
+    pub gain: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DividendPayment {
+    pub transaction_id: String,
+    pub symbol: String,
+    pub date: String,
+    pub shares_held: f64,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerReport {
+    pub method: LotMatching,
+    pub open_lots: Vec<Lot>,
+    pub realized: Vec<RealizedGain>,
+    pub dividends: Vec<DividendPayment>,
+    pub total_realized_gain: f64,
+    pub total_dividend_income: f64,
+}
+
+/// Validates a transaction entered by the user and assigns it `id`.
+pub fn validate_transaction(
+    id: String,
+    input: TransactionInput,
+    recorded_at: String,
+) -> Result<Transaction, AppError> {
+    let symbol = normalize_symbol(&input.symbol)?;
+    let date = NaiveDate::parse_from_str(input.date.trim(), TRANSACTION_DATE_FORMAT)
+        .map_err(|_| invalid_transaction("date_format"))?;
+
+    let fees = input.fees.unwrap_or(0.0);
+    if !fees.is_finite() || fees < 0.0 {
+        return Err(invalid_transaction("negative_fees"));
+    }
+
+    let (quantity, price) = match input.kind {
+        TransactionKind::Buy | TransactionKind::Sell => (
+            positive(input.quantity, "quantity")?,
+            non_negative(input.price, "price")?,
+        ),
+        TransactionKind::Dividend => (0.0, positive(input.price, "dividend")?),
+        TransactionKind::Split => (positive(input.quantity, "split_ratio")?, 0.0),
+    };
+
+    Ok(Transaction {
+        id,
+        symbol,
+        kind: input.kind,
+        date: date.format(TRANSACTION_DATE_FORMAT).to_string(),
+        quantity,
+        price,
+        fees,
+        recorded_at,
+    })
+}
+
+/// Returns the ledger with the transaction that shares `updated`'s id
+/// replaced in place. Replay the result before saving it: an edit can leave a
+/// later sell without the shares behind it.
+pub fn replace_transaction(
+    transactions: &[Transaction],
+    updated: Transaction,
+) -> Result<Vec<Transaction>, AppError> {
+    let position = transactions
+        .iter()
+        .position(|transaction| transaction.id == updated.id)
+        .ok_or_else(|| transaction_not_found(&updated.id))?;
+
+    let mut replaced = transactions.to_vec();
+    replaced[position] = updated;
+    Ok(replaced)
+}
+
+/// Returns the ledger without the transaction `id`. Replay the result before
+/// saving it, as with `replace_transaction`.
+pub fn remove_transaction(
+    transactions: &[Transaction],
+    id: &str,
+) -> Result<Vec<Transaction>, AppError> {
+    let remaining: Vec<Transaction> = transactions
+        .iter()
+        .filter(|transaction| transaction.id != id)
+        .cloned()
+        .collect();
+    if remaining.len() == transactions.len() {
+        return Err(transaction_not_found(id));
+    }
+    Ok(remaining)
+}
+
+/// Replays the ledger in date order (ties keep the order they were recorded
+/// in) and derives open lots, realized gains and dividend income. Sells that
+/// exceed the shares held at that date are rejected.
+pub fn replay_ledger(
+    transactions: &[Transaction],
+    method: LotMatching,
+) -> Result<LedgerReport, AppError> {
+    let mut ordered: Vec<&Transaction> = transactions.iter().collect();
+    ordered.sort_by(|left, right| left.date.cmp(&right.date));
+
+    let mut lots_by_symbol: BTreeMap<&str, Vec<Lot>> = BTreeMap::new();
+    let mut report = LedgerReport {
+        method,
+        open_lots: Vec::new(),
+        realized: Vec::new(),
+        dividends: Vec::new(),
+        total_realized_gain: 0.0,
+        total_dividend_income: 0.0,
+    };
+
+    for transaction in ordered {
+        let lots = lots_by_symbol
+            .entry(transaction.symbol.as_str())
+            .or_default();
+
+        match transaction.kind {
+            TransactionKind::Buy => lots.push(Lot {
+                id: transaction.id.clone(),
+                symbol: transaction.symbol.clone(),
+                quantity: transaction.quantity,
+                price: transaction.price,
+                purchased_on: transaction.date.clone(),
+                fees: transaction.fees,
+            }),
+            TransactionKind::Sell => {
+                let cost_basis = close_quantity(lots, transaction, method)?;
+                let proceeds = transaction.quantity * transaction.price - transaction.fees;
+                let gain = proceeds - cost_basis;
+                report.total_realized_gain += gain;
+                report.realized.push(RealizedGain {
+                    transaction_id: transaction.id.clone(),
+                    symbol: transaction.symbol.clone(),
+                    date: transaction.date.clone(),
+                    quantity: transaction.quantity,
+                    proceeds,
+                    cost_basis,
+                    gain,
+                });
+            }
+            TransactionKind::Dividend => {
+                let shares_held = held_quantity(lots);
+                if shares_held <= QUANTITY_EPSILON {
+                    return Err(invalid_transaction("dividend_without_shares")
+                        .with_arg("symbol", &transaction.symbol)
+                        .with_arg("date", &transaction.date));
+                }
+
+                let amount = shares_held * transaction.price - transaction.fees;
+                report.total_dividend_income += amount;
+                report.dividends.push(DividendPayment {
+                    transaction_id: transaction.id.clone(),
+                    symbol: transaction.symbol.clone(),
+                    date: transaction.date.clone(),
+                    shares_held,
+                    amount,
+                });
+            }
+            TransactionKind::Split => {
+                for lot in lots.iter_mut() {
+                    lot.quantity *= transaction.quantity;
+                    lot.price /= transaction.quantity;
+                }
+            }
+        }
+    }
+
+    report.open_lots = lots_by_symbol.into_values().flatten().collect();
+    Ok(report)
+}
+
+/// Builds the portfolio summary from the ledger's open lots and adds the
+/// realized totals.
+pub fn summarize_ledger(report: &LedgerReport, quotes: &[QuoteSummary]) -> PortfolioSummary {
+    let mut summary = summarize_portfolio(&report.open_lots, quotes);
+    summary.total_realized_gain = report.total_realized_gain;
+    summary.total_dividend_income = report.total_dividend_income;
+    summary
+}
+
+/// Removes `sell.quantity` shares from `lots` and returns the cost basis that
+/// left with them. FIFO and LIFO consume whole lots from the oldest or newest
+/// end; average cost shrinks every lot proportionally, which keeps the
+/// per-share average of what remains unchanged.
+fn close_quantity(
+    lots: &mut Vec<Lot>,
+    sell: &Transaction,
+    method: LotMatching,
+) -> Result<f64, AppError> {
+    let held = held_quantity(lots);
+    if sell.quantity > held + QUANTITY_EPSILON {
+        return Err(invalid_transaction("oversold")
+            .with_arg("quantity", sell.quantity)
+            .with_arg("symbol", &sell.symbol)
+            .with_arg("date", &sell.date)
+            .with_arg("held", held));
+    }
+
+    let mut cost_basis = 0.0;
+    match method {
+        LotMatching::Average => {
+            let sold_fraction = (sell.quantity / held).min(1.0);
+            for lot in lots.iter_mut() {
+                cost_basis += lot.cost_basis() * sold_fraction;
+                scale_lot(lot, 1.0 - sold_fraction);
+            }
+        }
+        LotMatching::Fifo | LotMatching::Lifo => {
+            let order: Vec<usize> = if method == LotMatching::Fifo {
+                (0..lots.len()).collect()
+            } else {
+                (0..lots.len()).rev().collect()
+            };
+
+            let mut remaining = sell.quantity;
+            for index in order {
+                if remaining <= QUANTITY_EPSILON {
+                    break;
+                }
+
+                let lot = &mut lots[index];
+                let taken = remaining.min(lot.quantity);
+                cost_basis += lot.cost_basis() * taken / lot.quantity;
+                scale_lot(lot, (lot.quantity - taken) / lot.quantity);
+                remaining -= taken;
+            }
+        }
+    }
+
+    lots.retain(|lot| lot.quantity > QUANTITY_EPSILON);
+    Ok(cost_basis)
+}
+
+fn scale_lot(lot: &mut Lot, factor: f64) {
+    lot.quantity *= factor;
+    lot.fees *= factor;
+}
+
+fn held_quantity(lots: &[Lot]) -> f64 {
+    lots.iter().map(|lot| lot.quantity).sum()
+}
+
+fn positive(value: Option<f64>, reason: &str) -> Result<f64, AppError> {
+    value
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .ok_or_else(|| invalid_transaction(reason))
+}
+
+fn non_negative(value: Option<f64>, reason: &str) -> Result<f64, AppError> {
+    value
+        .filter(|value| value.is_finite() && *value >= 0.0)
+        .ok_or_else(|| invalid_transaction(reason))
+}
+
+fn invalid_transaction(reason: &str) -> AppError {
+    AppError::validation("invalid_transaction").with_reason(reason)
+}
+
+fn transaction_not_found(id: &str) -> AppError {
+    AppError::validation("transaction_not_found").with_arg("id", id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(
+        id: &str,
+        kind: TransactionKind,
+        date: &str,
+        quantity: f64,
+        price: f64,
+        fees: f64,
+    ) -> Transaction {
+        Transaction {
+            id: id.to_string(),
+            symbol: "AAPL".to_string(),
+            kind,
+            date: date.to_string(),
+            quantity,
+            price,
+            fees,
+            recorded_at: "2026-03-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn buy(id: &str, date: &str, quantity: f64, price: f64) -> Transaction {
+        transaction(id, TransactionKind::Buy, date, quantity, price, 0.0)
+    }
+
+    fn sell(id: &str, date: &str, quantity: f64, price: f64) -> Transaction {
+        transaction(id, TransactionKind::Sell, date, quantity, price, 0.0)
+    }
+
+    fn two_buys_and_a_sell(sell_quantity: f64) -> Vec<Transaction> {
+        vec![
+            buy("buy-1", "2026-01-05", 10.0, 100.0),
+            buy("buy-2", "2026-01-20", 10.0, 120.0),
+            sell("sell-1", "2026-02-10", sell_quantity, 130.0),
+        ]
+    }
+
+    #[test]
+    fn fifo_partial_sell_consumes_the_oldest_lot_first() {
+        let report = replay_ledger(&two_buys_and_a_sell(4.0), LotMatching::Fifo).unwrap();
+
+        assert_eq!(report.realized[0].cost_basis, 400.0);
+        assert_eq!(report.realized[0].gain, 120.0);
+        assert_eq!(report.open_lots.len(), 2);
+        assert_eq!(report.open_lots[0].id, "buy-1");
+        assert_eq!(report.open_lots[0].quantity, 6.0);
+        assert_eq!(report.open_lots[1].quantity, 10.0);
+    }
+
+    #[test]
+    fn lifo_partial_sell_consumes_the_newest_lot_first() {
+        let report = replay_ledger(&two_buys_and_a_sell(4.0), LotMatching::Lifo).unwrap();
+
+        assert_eq!(report.realized[0].cost_basis, 480.0);
+        assert_eq!(report.realized[0].gain, 40.0);
+        assert_eq!(report.open_lots[0].quantity, 10.0);
+        assert_eq!(report.open_lots[1].quantity, 6.0);
+    }
+
+    #[test]
+    fn sells_across_several_lots_close_them_in_order() {
+        let fifo = replay_ledger(&two_buys_and_a_sell(15.0), LotMatching::Fifo).unwrap();
+        assert_eq!(fifo.realized[0].cost_basis, 1600.0);
+        assert_eq!(fifo.realized[0].gain, 350.0);
+        assert_eq!(fifo.open_lots.len(), 1);
+        assert_eq!(fifo.open_lots[0].id, "buy-2");
+        assert_eq!(fifo.open_lots[0].quantity, 5.0);
+
+        let lifo = replay_ledger(&two_buys_and_a_sell(15.0), LotMatching::Lifo).unwrap();
+        assert_eq!(lifo.realized[0].cost_basis, 1700.0);
+        assert_eq!(lifo.open_lots[0].id, "buy-1");
+        assert_eq!(lifo.open_lots[0].quantity, 5.0);
+    }
+
+    #[test]
+    fn average_cost_uses_the_pooled_cost_per_share() {
+        let report = replay_ledger(&two_buys_and_a_sell(15.0), LotMatching::Average).unwrap();
+
+        assert!((report.realized[0].cost_basis - 1650.0).abs() < 1e-9);
+        let remaining_cost: f64 = report.open_lots.iter().map(Lot::cost_basis).sum();
+        let remaining_quantity: f64 = report.open_lots.iter().map(|lot| lot.quantity).sum();
+        assert!((remaining_quantity - 5.0).abs() < 1e-9);
+        assert!((remaining_cost / remaining_quantity - 110.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fees_are_split_across_partially_sold_lots() {
+        let transactions = vec![
+            transaction(
+                "buy-1",
+                TransactionKind::Buy,
+                "2026-01-05",
+                10.0,
+                100.0,
+                10.0,
+            ),
+            transaction(
+                "sell-1",
+                TransactionKind::Sell,
+                "2026-02-10",
+                5.0,
+                110.0,
+                2.0,
+            ),
+        ];
+        let report = replay_ledger(&transactions, LotMatching::Fifo).unwrap();
+
+        assert_eq!(report.realized[0].proceeds, 548.0);
+        assert_eq!(report.realized[0].cost_basis, 505.0);
+        assert_eq!(report.open_lots[0].fees, 5.0);
+    }
+
+    #[test]
+    fn splits_and_dividends_adjust_holdings_and_income() {
+        let transactions = vec![
+            buy("buy-1", "2026-01-05", 10.0, 100.0),
+            transaction(
+                "split-1",
+                TransactionKind::Split,
+                "2026-01-10",
+                2.0,
+                0.0,
+                0.0,
+            ),
+            transaction(
+                "div-1",
+                TransactionKind::Dividend,
+                "2026-01-15",
+                0.0,
+                0.5,
+                0.0,
+            ),
+        ];
+        let report = replay_ledger(&transactions, LotMatching::Fifo).unwrap();
+
+        assert_eq!(report.open_lots[0].quantity, 20.0);
+        assert_eq!(report.open_lots[0].price, 50.0);
+        assert_eq!(report.total_dividend_income, 10.0);
+        assert_eq!(report.dividends[0].shares_held, 20.0);
+    }
+
+    #[test]
+    fn replay_orders_by_date_and_rejects_oversells() {
+        let backdated = vec![
+            sell("sell-1", "2026-02-10", 5.0, 130.0),
+            buy("buy-1", "2026-01-05", 10.0, 100.0),
+        ];
+        let report = replay_ledger(&backdated, LotMatching::Fifo).unwrap();
+        assert_eq!(report.total_realized_gain, 150.0);
+
+        let oversold = two_buys_and_a_sell(25.0);
+        let err = replay_ledger(&oversold, LotMatching::Fifo).unwrap_err();
+        assert_eq!(err.code, "invalid_transaction");
+    }
+
+    #[test]
+    fn corrections_replace_or_remove_by_id_and_replay_rejects_broken_ledgers() {
+        let ledger = two_buys_and_a_sell(15.0);
+
+        let mut fixed_price = ledger[0].clone();
+        fixed_price.price = 90.0;
+        let replaced = replace_transaction(&ledger, fixed_price).unwrap();
+        assert_eq!(replaced.len(), ledger.len());
+        assert_eq!(replaced[0].price, 90.0);
+        assert!(replay_ledger(&replaced, LotMatching::Fifo).is_ok());
+
+        let without_second_buy = remove_transaction(&ledger, &ledger[1].id).unwrap();
+        let err = replay_ledger(&without_second_buy, LotMatching::Fifo).unwrap_err();
+        assert_eq!(err.reason.as_deref(), Some("oversold"));
+
+        let err = remove_transaction(&ledger, "missing").unwrap_err();
+        assert_eq!(err.code, "transaction_not_found");
+        assert_eq!(
+            replace_transaction(&ledger, buy("missing", "2026-01-05", 1.0, 1.0))
+                .unwrap_err()
+                .code,
+            "transaction_not_found"
+        );
+    }
+
+    #[test]
+    fn validate_transaction_checks_fields_per_kind() {
+        let input = TransactionInput {
+            symbol: " msft ".to_string(),
+            kind: TransactionKind::Dividend,
+            date: "2026-03-02".to_string(),
+            quantity: Some(3.0),
+            price: Some(0.75),
+            fees: None,
+        };
+        let validated =
+            validate_transaction("tx-1".to_string(), input.clone(), "now".to_string()).unwrap();
+        assert_eq!(validated.symbol, "MSFT");
+        assert_eq!(validated.quantity, 0.0);
+
+        let mut missing_quantity = input.clone();
+        missing_quantity.kind = TransactionKind::Buy;
+        missing_quantity.quantity = None;
+        assert!(
+            validate_transaction("tx-2".to_string(), missing_quantity, "now".to_string()).is_err()
+        );
+
+        let mut bad_date = input;
+        bad_date.date = "03/02/2026".to_string();
+        assert!(validate_transaction("tx-3".to_string(), bad_date, "now".to_string()).is_err());
+    }
+}
@@ -1,77 +1,160 @@
+mod alerts;
+mod analytics;
+mod benchmark;
+mod catalogue;
+mod comparison;
+mod correlation;
 mod errors;
+mod ledger;
 mod models;
+mod portfolio;
+mod portfolio_performance;
+mod redaction;
+mod statistics;
+mod time;
+mod webhooks;
 
-pub use errors::AppError;
+pub use alerts::{
+    AlertCondition, AlertEvent, AlertRule, AlertRuleInput, DEFAULT_ALERT_COOLDOWN_SECONDS,
+    MovingAverages, evaluate_alerts, validate_alert_rule,
+};
+pub use analytics::{PeriodReturn, SymbolAnalytics, compute_symbol_analytics};
+pub use benchmark::{BenchmarkComparison, BenchmarkPoint, compare_to_benchmark};
+pub use catalogue::{current_locale, render_message, set_locale};
+pub use comparison::{ComparisonLine, ComparisonSeries, GapFill, build_comparison};
+pub use correlation::{CorrelationMatrix, build_correlation_matrix, validate_correlation_range};
+pub use errors::{AppError, ErrorKind, UserAction};
+pub use ledger::{
+    DividendPayment, LedgerReport, RealizedGain, Transaction, TransactionInput, TransactionKind,
+    remove_transaction, replace_transaction, replay_ledger, summarize_ledger, validate_transaction,
+};
 pub use models::{
-    AppProvider, AppSettings, AppSettingsInput, BootstrapPayload, PricePoint, ProviderTestResult,
-    QuoteStatus, QuoteSummary, SymbolPerformance, TimeRange, WatchlistItem,
+    ApiCredits, AppProvider, AppSettings, AppSettingsInput, BootstrapPayload, CacheEntryInfo,
+    CacheInvalidationResult, CacheMetrics, CacheStats, CacheStoreStats, CircuitState,
+    CommandMetrics, ConnectivityStatus, DEFAULT_CACHE_MAX_ENTRIES, DEFAULT_CACHE_MAX_MEGABYTES,
+    DiagnosticsExport, EndpointStatus, HistogramBucket, Locale, LogLevel, LotMatching,
+    MetricsSnapshot, PricePoint, PriceSeries, ProviderCallMetrics, ProviderEndpoint,
+    ProviderErrorRecord, ProviderStatusReport, ProviderTestResult, QuietHours, QuoteStatus,
+    QuoteSummary, SeriesInterval, SeriesSource, SymbolPerformance, TimeRange, WatchlistItem,
+};
+pub use portfolio::{
+    Lot, PortfolioSummary, PositionSummary, summarize_portfolio, summarize_positions,
+};
+pub use portfolio_performance::{
+    PortfolioPerformance, PortfolioValuePoint, build_portfolio_performance, symbols_held_since,
+};
+pub use redaction::{REDACTED, redact, register_secret};
+pub use time::{
+    now_rfc3339, provider_datetime_to_rfc3339, rfc3339_to_unix_secs, unix_secs_to_rfc3339,
 };
+pub use webhooks::{
+    Webhook, WebhookDelivery, WebhookInput, WebhookTrigger, render_webhook_body, validate_webhook,
+};
+
+/// Error code and stale reason used when data is served from cache because
+/// the app is offline.
+pub const OFFLINE_REASON: &str = "offline";
 
 const MIN_API_KEY_LEN: usize = 8;
 const MIN_AUTO_REFRESH_SECONDS: u32 = 15;
 const MAX_AUTO_REFRESH_SECONDS: u32 = 3600;
 const MAX_SYMBOL_LEN: usize = 12;
+const MIN_CACHE_MAX_ENTRIES: u32 = 1;
+const MAX_CACHE_MAX_ENTRIES: u32 = 10_000;
+const MIN_CACHE_MAX_MEGABYTES: u32 = 1;
+const MAX_CACHE_MAX_MEGABYTES: u32 = 1024;
+const MAX_RISK_FREE_RATE_PCT: f64 = 20.0;
 
 pub fn validate_settings(input: AppSettingsInput) -> Result<AppSettings, AppError> {
     let api_key = input.api_key.trim().to_string();
     if api_key.is_empty() {
-        return Err(AppError::validation(
-            "invalid_settings",
-            "API key is required.",
-        ));
+        return Err(AppError::validation("invalid_settings").with_reason("api_key_required"));
     }
 
     if api_key.len() < MIN_API_KEY_LEN {
-        return Err(AppError::validation(
-            "invalid_settings",
-            "API key looks too short.",
-        ));
+        return Err(AppError::validation("invalid_settings").with_reason("api_key_too_short"));
     }
 
     if !(MIN_AUTO_REFRESH_SECONDS..=MAX_AUTO_REFRESH_SECONDS).contains(&input.auto_refresh_seconds)
     {
-        return Err(AppError::validation(
-            "invalid_settings",
-            format!(
-                "Auto refresh must be between {} and {} seconds.",
-                MIN_AUTO_REFRESH_SECONDS, MAX_AUTO_REFRESH_SECONDS
-            ),
-        ));
+        return Err(AppError::validation("invalid_settings")
+            .with_reason("auto_refresh_range")
+            .with_arg("min", MIN_AUTO_REFRESH_SECONDS)
+            .with_arg("max", MAX_AUTO_REFRESH_SECONDS));
     }
 
+    if !(MIN_CACHE_MAX_ENTRIES..=MAX_CACHE_MAX_ENTRIES).contains(&input.cache_max_entries) {
+        return Err(AppError::validation("invalid_settings")
+            .with_reason("cache_entries_range")
+            .with_arg("min", MIN_CACHE_MAX_ENTRIES)
+            .with_arg("max", MAX_CACHE_MAX_ENTRIES));
+    }
+
+    if !(MIN_CACHE_MAX_MEGABYTES..=MAX_CACHE_MAX_MEGABYTES).contains(&input.cache_max_megabytes) {
+        return Err(AppError::validation("invalid_settings")
+            .with_reason("cache_size_range")
+            .with_arg("min", MIN_CACHE_MAX_MEGABYTES)
+            .with_arg("max", MAX_CACHE_MAX_MEGABYTES));
+    }
+
+    if !input.risk_free_rate_pct.is_finite()
+        || !(0.0..=MAX_RISK_FREE_RATE_PCT).contains(&input.risk_free_rate_pct)
+    {
+        return Err(AppError::validation("invalid_settings")
+            .with_reason("risk_free_rate_range")
+            .with_arg("max", MAX_RISK_FREE_RATE_PCT));
+    }
+
+    let quiet_hours = match input.quiet_hours {
+        Some(quiet_hours) => {
+            let (start, end) = quiet_hours.window().ok_or_else(|| {
+                AppError::validation("invalid_settings").with_reason("quiet_hours_format")
+            })?;
+            if start == end {
+                return Err(
+                    AppError::validation("invalid_settings").with_reason("quiet_hours_empty")
+                );
+            }
+            Some(QuietHours {
+                start: start.format("%H:%M").to_string(),
+                end: end.format("%H:%M").to_string(),
+            })
+        }
+        None => None,
+    };
+
     Ok(AppSettings {
         provider: input.provider,
         api_key,
         default_range: input.default_range,
         auto_refresh_seconds: input.auto_refresh_seconds,
         notifications_enabled: input.notifications_enabled,
+        cache_max_entries: input.cache_max_entries,
+        cache_max_megabytes: input.cache_max_megabytes,
+        offline_mode: input.offline_mode,
+        lot_matching: input.lot_matching,
+        risk_free_rate_pct: input.risk_free_rate_pct,
+        quiet_hours,
+        log_level: input.log_level,
+        locale: input.locale,
     })
 }
 
 pub fn normalize_symbol(input: &str) -> Result<String, AppError> {
     let normalized = input.trim().to_uppercase();
     if normalized.is_empty() {
-        return Err(AppError::validation(
-            "invalid_symbol",
-            "Symbol cannot be empty.",
-        ));
+        return Err(AppError::validation("invalid_symbol").with_reason("empty"));
     }
 
     if normalized.len() > MAX_SYMBOL_LEN {
-        return Err(AppError::validation(
-            "invalid_symbol",
-            "Symbol is too long.",
-        ));
+        return Err(AppError::validation("invalid_symbol").with_reason("too_long"));
     }
 
     if !normalized
         .chars()
         .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '.' || ch == '-')
     {
-        return Err(AppError::validation(
-            "invalid_symbol",
-            "Symbol contains invalid characters.",
-        ));
+        return Err(AppError::validation("invalid_symbol").with_reason("invalid_characters"));
     }
 
     Ok(normalized)
@@ -95,6 +178,14 @@ mod tests {
             default_range: TimeRange::OneMonth,
             auto_refresh_seconds,
             notifications_enabled: true,
+            cache_max_entries: DEFAULT_CACHE_MAX_ENTRIES,
+            cache_max_megabytes: DEFAULT_CACHE_MAX_MEGABYTES,
+            offline_mode: false,
+            lot_matching: LotMatching::Fifo,
+            risk_free_rate_pct: 0.0,
+            quiet_hours: None,
+            log_level: LogLevel::Info,
+            locale: Locale::En,
         }
     }
 
@@ -116,6 +207,46 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn validate_settings_rejects_zero_cache_limits() {
+        let mut input = sample_input("valid-key-123", 60);
+        input.cache_max_entries = 0;
+        assert!(validate_settings(input).is_err());
+
+        let mut input = sample_input("valid-key-123", 60);
+        input.cache_max_megabytes = 0;
+        assert!(validate_settings(input).is_err());
+    }
+
+    #[test]
+    fn validate_settings_rejects_out_of_range_risk_free_rate() {
+        let mut input = sample_input("valid-key-123", 60);
+        input.risk_free_rate_pct = -1.0;
+        assert!(validate_settings(input).is_err());
+
+        let mut input = sample_input("valid-key-123", 60);
+        input.risk_free_rate_pct = f64::NAN;
+        assert!(validate_settings(input).is_err());
+    }
+
+    #[test]
+    fn validate_settings_normalizes_quiet_hours() {
+        let mut input = sample_input("valid-key-123", 60);
+        input.quiet_hours = Some(QuietHours {
+            start: " 22:00".to_string(),
+            end: "7:30".to_string(),
+        });
+        let settings = validate_settings(input).unwrap();
+        assert_eq!(settings.quiet_hours.unwrap().end, "07:30");
+
+        let mut input = sample_input("valid-key-123", 60);
+        input.quiet_hours = Some(QuietHours {
+            start: "late".to_string(),
+            end: "07:00".to_string(),
+        });
+        assert!(validate_settings(input).is_err());
+    }
+
     #[test]
     fn validate_settings_trims_and_accepts_valid_input() {
         let result = validate_settings(sample_input("  valid-key-123  ", 60));
@@ -129,6 +260,48 @@ mod tests {
         assert_eq!(symbol, "AAPL");
     }
 
+    #[test]
+    fn symbol_performance_from_points_computes_metrics() {
+        let points = vec![
+            PricePoint::new("2026-02-19T15:00:00Z", 200.0),
+            PricePoint::new("2026-02-20T15:00:00Z", 199.0),
+            PricePoint::new("2026-02-21T15:00:00Z", 201.0),
+        ];
+
+        let performance = SymbolPerformance::from_points(
+            "AAPL",
+            TimeRange::OneWeek,
+            points,
+            None,
+            QuoteStatus::Fresh,
+        )
+        .unwrap();
+        assert_eq!(performance.start, 200.0);
+        assert_eq!(performance.end, 201.0);
+        assert_eq!(performance.min, 199.0);
+        assert_eq!(performance.max, 201.0);
+        assert_eq!(performance.last_updated_at, "2026-02-21T15:00:00Z");
+    }
+
+    #[test]
+    fn symbol_performance_from_points_rejects_empty_series() {
+        let performance = SymbolPerformance::from_points(
+            "AAPL",
+            TimeRange::OneWeek,
+            Vec::new(),
+            None,
+            QuoteStatus::Fresh,
+        );
+        assert!(performance.is_none());
+    }
+
+    #[test]
+    fn daily_ranges_share_one_interval() {
+        assert_eq!(TimeRange::OneMonth.interval(), SeriesInterval::OneDay);
+        assert_eq!(TimeRange::ThreeMonths.interval(), SeriesInterval::OneDay);
+        assert_eq!(SeriesInterval::OneDay.retained_bars(), 90);
+    }
+
     #[test]
     fn normalize_symbol_rejects_invalid_characters() {
         let result = normalize_symbol("AAPL$");
@@ -1,5 +1,10 @@
+use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
 
+pub const DEFAULT_CACHE_MAX_ENTRIES: u32 = 200;
+pub const DEFAULT_CACHE_MAX_MEGABYTES: u32 = 50;
+const QUIET_HOURS_FORMAT: &str = "%H:%M";
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum AppProvider {
@@ -7,6 +12,58 @@ pub enum AppProvider {
     Twelvedata,
 }
 
+/// How sells are matched against open lots when computing realized gains.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LotMatching {
+    #[default]
+    Fifo,
+    Lifo,
+    Average,
+}
+
+/// Minimum level written to the log file.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+/// Language used for error messages.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::De => "de",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum TimeRange {
     #[serde(rename = "1D")]
@@ -23,6 +80,14 @@ pub enum TimeRange {
 }
 
 impl TimeRange {
+    pub const ALL: [TimeRange; 5] = [
+        TimeRange::OneDay,
+        TimeRange::OneWeek,
+        TimeRange::OneMonth,
+        TimeRange::ThreeMonths,
+        TimeRange::OneYear,
+    ];
+
     pub fn as_key(&self) -> &'static str {
         match self {
             TimeRange::OneDay => "1D",
@@ -32,6 +97,83 @@ impl TimeRange {
             TimeRange::OneYear => "1Y",
         }
     }
+
+    pub fn interval(&self) -> SeriesInterval {
+        match self {
+            TimeRange::OneDay => SeriesInterval::OneHour,
+            TimeRange::OneWeek | TimeRange::OneMonth | TimeRange::ThreeMonths => {
+                SeriesInterval::OneDay
+            }
+            TimeRange::OneYear => SeriesInterval::OneWeek,
+        }
+    }
+
+    pub fn bar_count(&self) -> usize {
+        match self {
+            TimeRange::OneDay => 24,
+            TimeRange::OneWeek => 7,
+            TimeRange::OneMonth => 30,
+            TimeRange::ThreeMonths => 90,
+            TimeRange::OneYear => 52,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SeriesInterval {
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "1day")]
+    OneDay,
+    #[serde(rename = "1week")]
+    OneWeek,
+}
+
+impl SeriesInterval {
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            SeriesInterval::OneHour => "1h",
+            SeriesInterval::OneDay => "1day",
+            SeriesInterval::OneWeek => "1week",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "1h" => Some(SeriesInterval::OneHour),
+            "1day" => Some(SeriesInterval::OneDay),
+            "1week" => Some(SeriesInterval::OneWeek),
+            _ => None,
+        }
+    }
+
+    pub fn seconds(&self) -> u64 {
+        match self {
+            SeriesInterval::OneHour => 3_600,
+            SeriesInterval::OneDay => 86_400,
+            SeriesInterval::OneWeek => 604_800,
+        }
+    }
+
+    /// Bars per year, assuming 252 trading days of 6.5 hours.
+    pub fn periods_per_year(&self) -> f64 {
+        match self {
+            SeriesInterval::OneHour => 252.0 * 6.5,
+            SeriesInterval::OneDay => 252.0,
+            SeriesInterval::OneWeek => 52.0,
+        }
+    }
+
+    /// Number of bars kept in the canonical series: enough to serve the
+    /// longest range backed by this interval.
+    pub fn retained_bars(&self) -> usize {
+        TimeRange::ALL
+            .iter()
+            .filter(|range| range.interval() == *self)
+            .map(TimeRange::bar_count)
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -59,16 +201,61 @@ pub struct QuoteSummary {
     pub change_pct: Option<f64>,
     pub currency: Option<String>,
     pub last_updated_at: String,
+    /// IANA timezone of the quote's exchange, for showing the quote time in
+    /// exchange-local time.
+    pub exchange_timezone: Option<String>,
     pub status: QuoteStatus,
     pub error_code: Option<String>,
     pub error_message: Option<String>,
 }
 
+/// Where a loaded series came from: a fresh cache entry, the provider, or a
+/// stale cache entry served because the provider was unavailable.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SeriesSource {
+    Cache,
+    Fetched,
+    StaleCache,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct PricePoint {
     pub ts: String,
     pub close: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f64>,
+}
+
+impl PricePoint {
+    /// A bar with only a close, as stored by caches written before OHLCV
+    /// fields existed.
+    pub fn new(ts: impl Into<String>, close: f64) -> Self {
+        Self {
+            ts: ts.into(),
+            close,
+            open: None,
+            high: None,
+            low: None,
+            volume: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceSeries {
+    pub symbol: String,
+    pub interval: SeriesInterval,
+    pub points: Vec<PricePoint>,
+    pub exchange_timezone: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -82,17 +269,99 @@ pub struct SymbolPerformance {
     pub start: f64,
     pub end: f64,
     pub last_updated_at: String,
+    pub exchange_timezone: Option<String>,
     pub status: QuoteStatus,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
 }
 
+impl SymbolPerformance {
+    /// Builds range metrics from points sorted oldest first. Returns `None`
+    /// when there are no points to summarize.
+    pub fn from_points(
+        symbol: &str,
+        range: TimeRange,
+        points: Vec<PricePoint>,
+        exchange_timezone: Option<String>,
+        status: QuoteStatus,
+    ) -> Option<Self> {
+        let start = points.first()?.close;
+        let last = points.last()?;
+        let end = last.close;
+        let last_updated_at = last.ts.clone();
+        let min = points
+            .iter()
+            .fold(f64::INFINITY, |acc, point| acc.min(point.close));
+        let max = points
+            .iter()
+            .fold(f64::NEG_INFINITY, |acc, point| acc.max(point.close));
+
+        Some(Self {
+            symbol: symbol.to_string(),
+            range,
+            points,
+            min,
+            max,
+            start,
+            end,
+            last_updated_at,
+            exchange_timezone,
+            status,
+            error_code: None,
+            error_message: None,
+        })
+    }
+}
+
+/// Daily window, in local "HH:MM" times, during which desktop notifications
+/// are held back. A window whose end is before its start runs past midnight.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    pub start: String,
+    pub end: String,
+}
+
+impl QuietHours {
+    pub fn window(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let start = NaiveTime::parse_from_str(self.start.trim(), QUIET_HOURS_FORMAT).ok()?;
+        let end = NaiveTime::parse_from_str(self.end.trim(), QUIET_HOURS_FORMAT).ok()?;
+        Some((start, end))
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        match self.window() {
+            Some((start, end)) if start <= end => start <= time && time < end,
+            Some((start, end)) => time >= start || time < end,
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct AppSettings {
     pub provider: AppProvider,
     pub api_key: String,
     pub default_range: TimeRange,
     pub auto_refresh_seconds: u32,
     pub notifications_enabled: bool,
+    #[serde(default = "default_cache_max_entries")]
+    pub cache_max_entries: u32,
+    #[serde(default = "default_cache_max_megabytes")]
+    pub cache_max_megabytes: u32,
+    #[serde(default)]
+    pub offline_mode: bool,
+    #[serde(default)]
+    pub lot_matching: LotMatching,
+    #[serde(default)]
+    pub risk_free_rate_pct: f64,
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    #[serde(default)]
+    pub log_level: LogLevel,
+    #[serde(default)]
+    pub locale: Locale,
 }
 
 impl Default for AppSettings {
@@ -103,11 +372,27 @@ impl Default for AppSettings {
             default_range: TimeRange::default(),
             auto_refresh_seconds: 60,
             notifications_enabled: false,
+            cache_max_entries: DEFAULT_CACHE_MAX_ENTRIES,
+            cache_max_megabytes: DEFAULT_CACHE_MAX_MEGABYTES,
+            offline_mode: false,
+            lot_matching: LotMatching::default(),
+            risk_free_rate_pct: 0.0,
+            quiet_hours: None,
+            log_level: LogLevel::default(),
+            locale: Locale::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+fn default_cache_max_entries() -> u32 {
+    DEFAULT_CACHE_MAX_ENTRIES
+}
+
+fn default_cache_max_megabytes() -> u32 {
+    DEFAULT_CACHE_MAX_MEGABYTES
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct AppSettingsInput {
     pub provider: AppProvider,
@@ -115,6 +400,22 @@ pub struct AppSettingsInput {
     pub default_range: TimeRange,
     pub auto_refresh_seconds: u32,
     pub notifications_enabled: bool,
+    #[serde(default = "default_cache_max_entries")]
+    pub cache_max_entries: u32,
+    #[serde(default = "default_cache_max_megabytes")]
+    pub cache_max_megabytes: u32,
+    #[serde(default)]
+    pub offline_mode: bool,
+    #[serde(default)]
+    pub lot_matching: LotMatching,
+    #[serde(default)]
+    pub risk_free_rate_pct: f64,
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    #[serde(default)]
+    pub log_level: LogLevel,
+    #[serde(default)]
+    pub locale: Locale,
 }
 
 impl From<AppSettings> for AppSettingsInput {
@@ -125,6 +426,14 @@ impl From<AppSettings> for AppSettingsInput {
             default_range: settings.default_range,
             auto_refresh_seconds: settings.auto_refresh_seconds,
             notifications_enabled: settings.notifications_enabled,
+            cache_max_entries: settings.cache_max_entries,
+            cache_max_megabytes: settings.cache_max_megabytes,
+            offline_mode: settings.offline_mode,
+            lot_matching: settings.lot_matching,
+            risk_free_rate_pct: settings.risk_free_rate_pct,
+            quiet_hours: settings.quiet_hours,
+            log_level: settings.log_level,
+            locale: settings.locale,
         }
     }
 }
@@ -139,6 +448,176 @@ pub struct ProviderTestResult {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+pub struct CacheEntryInfo {
+    pub key: String,
+    pub cached_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStoreStats {
+    pub entry_count: usize,
+    pub bytes_on_disk: u64,
+    pub oldest_entry: Option<CacheEntryInfo>,
+    pub newest_entry: Option<CacheEntryInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub quotes: CacheStoreStats,
+    pub timeseries: CacheStoreStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheInvalidationResult {
+    pub removed_quotes: Vec<String>,
+    pub removed_timeseries: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityStatus {
+    pub offline: bool,
+    pub manual_offline: bool,
+    pub auto_offline: bool,
+    pub consecutive_failures: u32,
+    pub retry_at: Option<String>,
+}
+
+/// Provider endpoints the health check probes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderEndpoint {
+    Quote,
+    TimeSeries,
+    SymbolSearch,
+}
+
+impl ProviderEndpoint {
+    pub const ALL: [Self; 3] = [Self::Quote, Self::TimeSeries, Self::SymbolSearch];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Quote => "quote",
+            Self::TimeSeries => "time_series",
+            Self::SymbolSearch => "symbol_search",
+        }
+    }
+}
+
+/// The connectivity monitor seen as a circuit breaker: `Open` while the app
+/// is automatically offline, `HalfOpen` once the retry window has passed
+/// and the next request is a probe.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// API credit usage reported in the provider's response headers.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiCredits {
+    pub used: Option<u32>,
+    pub left: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointStatus {
+    pub endpoint: ProviderEndpoint,
+    pub ok: bool,
+    /// Absent when the endpoint was not probed.
+    pub latency_ms: Option<u64>,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+    pub last_error: Option<ProviderErrorRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderStatusReport {
+    pub provider: AppProvider,
+    pub checked_at: String,
+    pub circuit: CircuitState,
+    pub consecutive_failures: u32,
+    pub retry_at: Option<String>,
+    pub credits: ApiCredits,
+    pub endpoints: Vec<EndpointStatus>,
+}
+
+/// Cumulative count of observations at or below `le_ms` milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistogramBucket {
+    pub le_ms: u64,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandMetrics {
+    pub command: String,
+    pub count: u64,
+    pub error_count: u64,
+    pub total_ms: u64,
+    pub buckets: Vec<HistogramBucket>,
+}
+
+/// Provider calls to one endpoint that ended with `code`, which is `ok` for
+/// successful calls and the `AppError` code otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderCallMetrics {
+    pub endpoint: String,
+    pub code: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheMetrics {
+    pub cache: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_ratio: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderErrorRecord {
+    pub endpoint: String,
+    pub code: String,
+    pub occurred_at: String,
+}
+
+/// Where `export_diagnostics` wrote the bundle and what it contains.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsExport {
+    pub path: String,
+    pub size_bytes: u64,
+    pub entries: Vec<String>,
+}
+
+/// In-process metrics since the app started.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSnapshot {
+    pub collected_at: String,
+    pub commands: Vec<CommandMetrics>,
+    pub provider_calls: Vec<ProviderCallMetrics>,
+    pub caches: Vec<CacheMetrics>,
+    pub bytes_fetched: u64,
+    pub prometheus_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct BootstrapPayload {
     pub settings: AppSettings,
     pub watchlist: Vec<WatchlistItem>,
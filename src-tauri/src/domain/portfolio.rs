@@ -0,0 +1,211 @@
+use super::{QuoteStatus, QuoteSummary};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// An open position lot. Lots are derived from the transaction ledger; a
+/// partially sold lot keeps its original price and a pro-rated share of fees.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Lot {
+    pub id: String,
+    pub symbol: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub purchased_on: String,
+    pub fees: f64,
+}
+
+impl Lot {
+    pub fn cost_basis(&self) -> f64 {
+        self.quantity * self.price + self.fees
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionSummary {
+    pub symbol: String,
+    pub quantity: f64,
+    pub cost_basis: f64,
+    pub average_cost: f64,
+    pub last_price: Option<f64>,
+    pub market_value: Option<f64>,
+    pub unrealized_pnl: Option<f64>,
+    pub unrealized_pnl_pct: Option<f64>,
+    pub quote_status: QuoteStatus,
+    pub last_updated_at: Option<String>,
+}
+
+/// Totals only include positions with a usable quote; `unpriced_symbols`
+/// lists the ones left out.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioSummary {
+    pub positions: Vec<PositionSummary>,
+    pub total_market_value: f64,
+    pub total_cost_basis: f64,
+    pub total_unrealized_pnl: f64,
+    pub total_unrealized_pnl_pct: Option<f64>,
+    pub unpriced_symbols: Vec<String>,
+    pub total_realized_gain: f64,
+    pub total_dividend_income: f64,
+}
+
+pub fn summarize_portfolio(lots: &[Lot], quotes: &[QuoteSummary]) -> PortfolioSummary {
+    let mut holdings: BTreeMap<&str, (f64, f64)> = BTreeMap::new();
+    for lot in lots {
+        let holding = holdings.entry(lot.symbol.as_str()).or_default();
+        holding.0 += lot.quantity;
+        holding.1 += lot.cost_basis();
+    }
+
+    summarize_positions(
+        holdings
+            .into_iter()
+            .map(|(symbol, (quantity, cost_basis))| (symbol.to_string(), quantity, cost_basis)),
+        quotes,
+    )
+}
+
+/// Builds the summary from `(symbol, quantity, cost_basis)` positions.
+pub fn summarize_positions(
+    positions: impl IntoIterator<Item = (String, f64, f64)>,
+    quotes: &[QuoteSummary],
+) -> PortfolioSummary {
+    let quotes_by_symbol: HashMap<&str, &QuoteSummary> = quotes
+        .iter()
+        .map(|quote| (quote.symbol.as_str(), quote))
+        .collect();
+
+    let mut summary = PortfolioSummary {
+        positions: Vec::new(),
+        total_market_value: 0.0,
+        total_cost_basis: 0.0,
+        total_unrealized_pnl: 0.0,
+        total_unrealized_pnl_pct: None,
+        unpriced_symbols: Vec::new(),
+        total_realized_gain: 0.0,
+        total_dividend_income: 0.0,
+    };
+    let mut priced_cost_basis = 0.0;
+
+    for (symbol, quantity, cost_basis) in positions {
+        let quote = quotes_by_symbol
+            .get(symbol.as_str())
+            .filter(|quote| quote.status != QuoteStatus::Error);
+        let last_price = quote.map(|quote| quote.price);
+        let market_value = last_price.map(|price| price * quantity);
+        let unrealized_pnl = market_value.map(|value| value - cost_basis);
+
+        summary.total_cost_basis += cost_basis;
+        match (market_value, unrealized_pnl) {
+            (Some(value), Some(pnl)) => {
+                summary.total_market_value += value;
+                summary.total_unrealized_pnl += pnl;
+                priced_cost_basis += cost_basis;
+            }
+            _ => summary.unpriced_symbols.push(symbol.clone()),
+        }
+
+        summary.positions.push(PositionSummary {
+            average_cost: if quantity > 0.0 {
+                cost_basis / quantity
+            } else {
+                0.0
+            },
+            last_price,
+            market_value,
+            unrealized_pnl,
+            unrealized_pnl_pct: unrealized_pnl.and_then(|pnl| percent_of(pnl, cost_basis)),
+            quote_status: quote.map_or(QuoteStatus::Error, |quote| quote.status),
+            last_updated_at: quote.map(|quote| quote.last_updated_at.clone()),
+            symbol,
+            quantity,
+            cost_basis,
+        });
+    }
+
+    summary.total_unrealized_pnl_pct = percent_of(summary.total_unrealized_pnl, priced_cost_basis);
+    summary
+}
+
+fn percent_of(value: f64, base: f64) -> Option<f64> {
+    (base > 0.0).then(|| value / base * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lot(symbol: &str, quantity: f64, price: f64, fees: f64) -> Lot {
+        Lot {
+            id: format!("{symbol}-{quantity}-{price}"),
+            symbol: symbol.to_string(),
+            quantity,
+            price,
+            purchased_on: "2026-01-05".to_string(),
+            fees,
+        }
+    }
+
+    fn quote(symbol: &str, price: f64, status: QuoteStatus) -> QuoteSummary {
+        QuoteSummary {
+            symbol: symbol.to_string(),
+            price,
+            change_abs: None,
+            change_pct: None,
+            currency: Some("USD".to_string()),
+            last_updated_at: "2026-02-22T15:00:00Z".to_string(),
+            exchange_timezone: None,
+            status,
+            error_code: None,
+            error_message: None,
+        }
+    }
+
+    #[test]
+    fn summarize_portfolio_combines_lots_per_symbol() {
+        let lots = vec![
+            lot("AAPL", 10.0, 100.0, 5.0),
+            lot("AAPL", 5.0, 130.0, 5.0),
+            lot("MSFT", 2.0, 400.0, 0.0),
+        ];
+        let quotes = vec![
+            quote("AAPL", 120.0, QuoteStatus::Fresh),
+            quote("MSFT", 380.0, QuoteStatus::Stale),
+        ];
+
+        let summary = summarize_portfolio(&lots, &quotes);
+        let aapl = &summary.positions[0];
+        assert_eq!(aapl.symbol, "AAPL");
+        assert_eq!(aapl.quantity, 15.0);
+        assert_eq!(aapl.cost_basis, 1660.0);
+        assert_eq!(aapl.market_value, Some(1800.0));
+        assert_eq!(aapl.unrealized_pnl, Some(140.0));
+
+        let msft = &summary.positions[1];
+        assert_eq!(msft.unrealized_pnl, Some(-40.0));
+        assert_eq!(msft.quote_status, QuoteStatus::Stale);
+
+        assert_eq!(summary.total_market_value, 2560.0);
+        assert_eq!(summary.total_cost_basis, 2460.0);
+        assert_eq!(summary.total_unrealized_pnl, 100.0);
+        assert!(summary.unpriced_symbols.is_empty());
+    }
+
+    #[test]
+    fn summarize_portfolio_leaves_unpriced_positions_out_of_totals() {
+        let lots = vec![lot("AAPL", 10.0, 100.0, 0.0), lot("NVDA", 1.0, 500.0, 0.0)];
+        let quotes = vec![
+            quote("AAPL", 110.0, QuoteStatus::Fresh),
+            quote("NVDA", 0.0, QuoteStatus::Error),
+        ];
+
+        let summary = summarize_portfolio(&lots, &quotes);
+        assert_eq!(summary.unpriced_symbols, vec!["NVDA".to_string()]);
+        assert_eq!(summary.positions[1].market_value, None);
+        assert_eq!(summary.total_market_value, 1100.0);
+        assert_eq!(summary.total_cost_basis, 1500.0);
+        assert_eq!(summary.total_unrealized_pnl_pct, Some(10.0));
+    }
+}
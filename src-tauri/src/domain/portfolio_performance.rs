@@ -0,0 +1,467 @@
+use super::{
+    PricePoint, QuoteStatus, SymbolPerformance, TimeRange, Transaction, TransactionKind,
+    rfc3339_to_unix_secs,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+const VALUE_EPSILON: f64 = 1e-9;
+const GROWTH_INDEX_BASE: f64 = 100.0;
+const IRR_MIN_RATE: f64 = -0.9999;
+const IRR_MAX_RATE: f64 = 100.0;
+const IRR_ITERATIONS: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioValuePoint {
+    pub ts: String,
+    pub value: f64,
+    pub net_flow: f64,
+}
+
+/// Portfolio value over a range. The portfolio holds no cash, so a buy counts
+/// as a deposit of its cost and a sell or dividend as a withdrawal of its
+/// proceeds. Returns are percentages over the whole range, not annualized.
+/// `status` is stale when any series is stale or any held symbol is missing.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioPerformance {
+    pub range: TimeRange,
+    pub points: Vec<PortfolioValuePoint>,
+    pub start_value: f64,
+    pub end_value: f64,
+    pub net_flows: f64,
+    pub time_weighted_return_pct: Option<f64>,
+    pub money_weighted_return_pct: Option<f64>,
+    pub status: QuoteStatus,
+    pub missing_symbols: Vec<String>,
+}
+
+impl PortfolioPerformance {
+    /// Flow-adjusted value index that starts at 100 on the first non-empty bar
+    /// and moves with the time-weighted return, so deposits and withdrawals do
+    /// not show up as performance.
+    pub fn growth_index(&self) -> Vec<PricePoint> {
+        let mut index: Vec<PricePoint> = Vec::new();
+        let mut level = GROWTH_INDEX_BASE;
+
+        for (position, point) in self.points.iter().enumerate() {
+            let previous = position
+                .checked_sub(1)
+                .map(|previous| &self.points[previous]);
+            match previous {
+                Some(previous) if previous.value > VALUE_EPSILON && !index.is_empty() => {
+                    level *= (point.value - point.net_flow) / previous.value;
+                }
+                _ if index.is_empty() && point.value <= VALUE_EPSILON => continue,
+                _ => {}
+            }
+
+            index.push(PricePoint::new(point.ts.clone(), level));
+        }
+
+        index
+    }
+}
+
+/// Symbols that were held on `since_date` or traded on or after it, i.e.
+/// every symbol whose history is needed to value the portfolio from that day.
+pub fn symbols_held_since(transactions: &[Transaction], since_date: &str) -> Vec<String> {
+    let mut shares: HashMap<&str, f64> = HashMap::new();
+    let mut symbols = BTreeSet::new();
+
+    for transaction in ordered_by_date(transactions) {
+        if transaction.date.as_str() >= since_date {
+            symbols.insert(transaction.symbol.clone());
+            continue;
+        }
+        apply_to_shares(&mut shares, transaction);
+    }
+
+    symbols.extend(
+        shares
+            .into_iter()
+            .filter(|(_, quantity)| *quantity > VALUE_EPSILON)
+            .map(|(symbol, _)| symbol.to_string()),
+    );
+    symbols.into_iter().collect()
+}
+
+/// Values the ledger's holdings at every bar in `series`, carrying each
+/// symbol's last close forward across gaps. Trades are assumed to settle at
+/// the close of the bar on their date. Trades in `missing_symbols` are left
+/// out entirely, so their flows do not distort the returns of the holdings
+/// that are valued.
+pub fn build_portfolio_performance(
+    range: TimeRange,
+    transactions: &[Transaction],
+    series: &[SymbolPerformance],
+    missing_symbols: Vec<String>,
+) -> PortfolioPerformance {
+    let timeline: BTreeSet<&str> = series
+        .iter()
+        .flat_map(|performance| performance.points.iter().map(|point| point.ts.as_str()))
+        .collect();
+    let series_by_symbol: BTreeMap<&str, &SymbolPerformance> = series
+        .iter()
+        .map(|performance| (performance.symbol.as_str(), performance))
+        .collect();
+
+    let ordered: Vec<&Transaction> = ordered_by_date(transactions)
+        .into_iter()
+        .filter(|transaction| !missing_symbols.contains(&transaction.symbol))
+        .collect();
+    let mut next_transaction = 0;
+    let mut shares: HashMap<&str, f64> = HashMap::new();
+    let mut points: Vec<PortfolioValuePoint> = Vec::with_capacity(timeline.len());
+
+    for ts in timeline {
+        let date = ts.get(..10).unwrap_or(ts);
+        let mut net_flow = 0.0;
+        while let Some(transaction) = ordered.get(next_transaction)
+            && transaction.date.as_str() <= date
+        {
+            // Trades before the first bar only establish the opening holdings.
+            if !points.is_empty() {
+                net_flow += external_flow(&shares, transaction);
+            }
+            apply_to_shares(&mut shares, transaction);
+            next_transaction += 1;
+        }
+
+        let value = shares
+            .iter()
+            .filter_map(|(symbol, quantity)| {
+                let close = close_at(series_by_symbol.get(symbol)?, ts)?;
+                Some(quantity * close)
+            })
+            .sum();
+        points.push(PortfolioValuePoint {
+            ts: ts.to_string(),
+            value,
+            net_flow,
+        });
+    }
+
+    let status = if !missing_symbols.is_empty()
+        || series
+            .iter()
+            .any(|performance| performance.status != QuoteStatus::Fresh)
+    {
+        QuoteStatus::Stale
+    } else {
+        QuoteStatus::Fresh
+    };
+
+    PortfolioPerformance {
+        range,
+        start_value: points.first().map_or(0.0, |point| point.value),
+        end_value: points.last().map_or(0.0, |point| point.value),
+        net_flows: points.iter().skip(1).map(|point| point.net_flow).sum(),
+        time_weighted_return_pct: time_weighted_return(&points).map(to_percent),
+        money_weighted_return_pct: money_weighted_return(&points).map(to_percent),
+        points,
+        status,
+        missing_symbols,
+    }
+}
+
+/// Chains the return of every sub-period between two bars, removing the flow
+/// that landed on the closing bar. Periods that start from an empty
+/// portfolio carry no return.
+fn time_weighted_return(points: &[PortfolioValuePoint]) -> Option<f64> {
+    let mut growth = 1.0;
+    let mut measured = false;
+
+    for window in points.windows(2) {
+        let (previous, current) = (&window[0], &window[1]);
+        if previous.value <= VALUE_EPSILON {
+            continue;
+        }
+
+        growth *= (current.value - current.net_flow) / previous.value;
+        measured = true;
+    }
+
+    measured.then_some(growth - 1.0)
+}
+
+/// Internal rate of return over the range, with time measured as a fraction
+/// of the range so the result is a period return rather than an annual one.
+fn money_weighted_return(points: &[PortfolioValuePoint]) -> Option<f64> {
+    let first = points.first()?;
+    let last = points.last()?;
+    let start = rfc3339_to_unix_secs(&first.ts)?;
+    let span = (rfc3339_to_unix_secs(&last.ts)? - start) as f64;
+    if span <= 0.0 {
+        return None;
+    }
+
+    let mut cash_flows: Vec<(f64, f64)> = Vec::with_capacity(points.len() + 1);
+    cash_flows.push((0.0, -first.value));
+    for point in points.iter().skip(1) {
+        if point.net_flow.abs() > VALUE_EPSILON {
+            let elapsed = (rfc3339_to_unix_secs(&point.ts)? - start) as f64;
+            cash_flows.push((elapsed / span, -point.net_flow));
+        }
+    }
+    cash_flows.push((1.0, last.value));
+
+    let net_present_value = |rate: f64| -> f64 {
+        cash_flows
+            .iter()
+            .map(|(time, amount)| amount / (1.0 + rate).powf(*time))
+            .sum()
+    };
+
+    let (mut low, mut high) = (IRR_MIN_RATE, IRR_MAX_RATE);
+    let (mut low_value, high_value) = (net_present_value(low), net_present_value(high));
+    if !low_value.is_finite() || !high_value.is_finite() || low_value * high_value > 0.0 {
+        return None;
+    }
+
+    for _ in 0..IRR_ITERATIONS {
+        let middle = (low + high) / 2.0;
+        let middle_value = net_present_value(middle);
+        if middle_value.abs() < VALUE_EPSILON {
+            return Some(middle);
+        }
+        if middle_value * low_value > 0.0 {
+            low = middle;
+            low_value = middle_value;
+        } else {
+            high = middle;
+        }
+    }
+
+    Some((low + high) / 2.0)
+}
+
+/// Money moved into (positive) or out of (negative) the holdings by a
+/// transaction. Called before the transaction is applied so dividends see the
+/// shares held on their date.
+fn external_flow(shares: &HashMap<&str, f64>, transaction: &Transaction) -> f64 {
+    match transaction.kind {
+        TransactionKind::Buy => transaction.quantity * transaction.price + transaction.fees,
+        TransactionKind::Sell => -(transaction.quantity * transaction.price - transaction.fees),
+        TransactionKind::Dividend => {
+            let held = shares
+                .get(transaction.symbol.as_str())
+                .copied()
+                .unwrap_or(0.0);
+            -(held * transaction.price - transaction.fees)
+        }
+        TransactionKind::Split => 0.0,
+    }
+}
+
+fn apply_to_shares<'a>(shares: &mut HashMap<&'a str, f64>, transaction: &'a Transaction) {
+    let held = shares.entry(transaction.symbol.as_str()).or_default();
+    match transaction.kind {
+        TransactionKind::Buy => *held += transaction.quantity,
+        TransactionKind::Sell => *held -= transaction.quantity,
+        TransactionKind::Split => *held *= transaction.quantity,
+        TransactionKind::Dividend => {}
+    }
+}
+
+/// Last close at or before `ts`; bars before the series starts use its first
+/// close.
+fn close_at(performance: &SymbolPerformance, ts: &str) -> Option<f64> {
+    let index = performance
+        .points
+        .partition_point(|point| point.ts.as_str() <= ts);
+    performance
+        .points
+        .get(index.saturating_sub(1))
+        .map(|point| point.close)
+}
+
+fn ordered_by_date(transactions: &[Transaction]) -> Vec<&Transaction> {
+    let mut ordered: Vec<&Transaction> = transactions.iter().collect();
+    ordered.sort_by(|left, right| left.date.cmp(&right.date));
+    ordered
+}
+
+fn to_percent(value: f64) -> f64 {
+    value * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::PricePoint;
+
+    fn series(symbol: &str, closes: &[f64]) -> SymbolPerformance {
+        let points = closes
+            .iter()
+            .enumerate()
+            .map(|(day, close)| {
+                PricePoint::new(format!("2026-02-{:02}T05:00:00Z", day + 2), *close)
+            })
+            .collect();
+        SymbolPerformance::from_points(symbol, TimeRange::OneWeek, points, None, QuoteStatus::Fresh)
+            .unwrap()
+    }
+
+    fn trade(kind: TransactionKind, date: &str, quantity: f64, price: f64) -> Transaction {
+        Transaction {
+            id: format!("{kind:?}-{date}"),
+            symbol: "AAPL".to_string(),
+            kind,
+            date: date.to_string(),
+            quantity,
+            price,
+            fees: 0.0,
+            recorded_at: "2026-02-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn returns_match_the_price_change_without_flows() {
+        let transactions = vec![trade(TransactionKind::Buy, "2026-01-15", 2.0, 90.0)];
+        let performance = build_portfolio_performance(
+            TimeRange::OneWeek,
+            &transactions,
+            &[series("AAPL", &[100.0, 105.0, 110.0])],
+            Vec::new(),
+        );
+
+        assert_eq!(performance.start_value, 200.0);
+        assert_eq!(performance.end_value, 220.0);
+        assert_eq!(performance.net_flows, 0.0);
+        assert_close(performance.time_weighted_return_pct, 10.0);
+        assert_close(performance.money_weighted_return_pct, 10.0);
+    }
+
+    #[test]
+    fn deposits_do_not_count_as_returns() {
+        let transactions = vec![
+            trade(TransactionKind::Buy, "2026-01-15", 1.0, 90.0),
+            trade(TransactionKind::Buy, "2026-02-03", 1.0, 110.0),
+        ];
+        let performance = build_portfolio_performance(
+            TimeRange::OneWeek,
+            &transactions,
+            &[series("AAPL", &[100.0, 110.0, 121.0])],
+            Vec::new(),
+        );
+
+        let values: Vec<f64> = performance.points.iter().map(|point| point.value).collect();
+        assert_eq!(values, vec![100.0, 220.0, 242.0]);
+        assert_eq!(performance.net_flows, 110.0);
+        assert_close(performance.time_weighted_return_pct, 21.0);
+        assert_close(performance.money_weighted_return_pct, 21.0);
+
+        let index: Vec<f64> = performance
+            .growth_index()
+            .iter()
+            .map(|point| point.close)
+            .collect();
+        assert_close(index.last().copied(), 121.0);
+    }
+
+    #[test]
+    fn withdrawals_do_not_count_as_losses() {
+        let transactions = vec![
+            trade(TransactionKind::Buy, "2026-01-15", 2.0, 100.0),
+            trade(TransactionKind::Sell, "2026-02-03", 1.0, 100.0),
+        ];
+        let performance = build_portfolio_performance(
+            TimeRange::OneWeek,
+            &transactions,
+            &[series("AAPL", &[100.0, 100.0, 100.0])],
+            Vec::new(),
+        );
+
+        assert_eq!(performance.end_value, 100.0);
+        assert_eq!(performance.net_flows, -100.0);
+        assert_close(performance.time_weighted_return_pct, 0.0);
+        assert_close(performance.money_weighted_return_pct, 0.0);
+    }
+
+    #[test]
+    fn money_weighted_return_reflects_flow_timing() {
+        let transactions = vec![
+            trade(TransactionKind::Buy, "2026-01-15", 1.0, 100.0),
+            trade(TransactionKind::Buy, "2026-02-03", 9.0, 120.0),
+        ];
+        let performance = build_portfolio_performance(
+            TimeRange::OneWeek,
+            &transactions,
+            &[series("AAPL", &[100.0, 120.0, 108.0])],
+            Vec::new(),
+        );
+
+        assert_close(performance.time_weighted_return_pct, 8.0);
+        assert!(performance.money_weighted_return_pct.unwrap() < 0.0);
+    }
+
+    #[test]
+    fn symbols_held_since_includes_open_and_recently_traded_positions() {
+        let mut closed = trade(TransactionKind::Buy, "2026-01-02", 1.0, 10.0);
+        closed.symbol = "MSFT".to_string();
+        let mut closed_sell = trade(TransactionKind::Sell, "2026-01-03", 1.0, 12.0);
+        closed_sell.symbol = "MSFT".to_string();
+        let mut recent = trade(TransactionKind::Buy, "2026-02-04", 1.0, 10.0);
+        recent.symbol = "NVDA".to_string();
+        let transactions = vec![
+            trade(TransactionKind::Buy, "2026-01-02", 1.0, 10.0),
+            closed,
+            closed_sell,
+            recent,
+        ];
+
+        assert_eq!(
+            symbols_held_since(&transactions, "2026-02-01"),
+            vec!["AAPL".to_string(), "NVDA".to_string()]
+        );
+    }
+
+    #[test]
+    fn missing_symbols_do_not_contribute_flows() {
+        let mut missing_buy = trade(TransactionKind::Buy, "2026-02-03", 5.0, 50.0);
+        missing_buy.symbol = "MSFT".to_string();
+        let transactions = vec![
+            trade(TransactionKind::Buy, "2026-01-15", 2.0, 90.0),
+            missing_buy,
+        ];
+        let performance = build_portfolio_performance(
+            TimeRange::OneWeek,
+            &transactions,
+            &[series("AAPL", &[100.0, 105.0, 110.0])],
+            vec!["MSFT".to_string()],
+        );
+
+        assert_eq!(performance.net_flows, 0.0);
+        assert_close(performance.time_weighted_return_pct, 10.0);
+        assert_close(performance.money_weighted_return_pct, 10.0);
+        assert_eq!(performance.missing_symbols, vec!["MSFT".to_string()]);
+    }
+
+    #[test]
+    fn missing_symbols_mark_the_value_stale() {
+        let transactions = vec![trade(TransactionKind::Buy, "2026-01-15", 2.0, 90.0)];
+        let series = [series("AAPL", &[100.0, 105.0, 110.0])];
+
+        let complete =
+            build_portfolio_performance(TimeRange::OneWeek, &transactions, &series, Vec::new());
+        let partial = build_portfolio_performance(
+            TimeRange::OneWeek,
+            &transactions,
+            &series,
+            vec!["MSFT".to_string()],
+        );
+
+        assert_eq!(complete.status, QuoteStatus::Fresh);
+        assert_eq!(partial.status, QuoteStatus::Stale);
+    }
+}
@@ -0,0 +1,223 @@
+use std::sync::{PoisonError, RwLock};
+
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Query and form parameters whose values are always secret. A bare `key` is
+/// left out: cache keys and the like use it for ordinary values.
+const SECRET_PARAMS: [&str; 9] = [
+    "apikey",
+    "api_key",
+    "api-key",
+    "access_token",
+    "refresh_token",
+    "token",
+    "client_secret",
+    "secret",
+    "password",
+];
+const BEARER_PREFIX: &str = "bearer ";
+/// Shorter values are too likely to match ordinary text.
+const MIN_SECRET_LEN: usize = 8;
+
+static KNOWN_SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Remembers a secret (an API key, a webhook URL or header value) so that
+/// `redact` removes it wherever it shows up, not only in recognizable
+/// `apikey=` parameters.
+pub fn register_secret(secret: &str) {
+    let secret = secret.trim();
+    if secret.len() < MIN_SECRET_LEN {
+        return;
+    }
+
+    let mut secrets = KNOWN_SECRETS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if !secrets.iter().any(|known| known == secret) {
+        secrets.push(secret.to_string());
+        // Longest first, so a secret containing another is replaced whole.
+        secrets.sort_by_key(|known| std::cmp::Reverse(known.len()));
+    }
+}
+
+/// Replaces registered secrets, secret-looking parameter values and bearer
+/// tokens in `input` with `REDACTED`.
+pub fn redact(input: &str) -> String {
+    let mut output = input.to_string();
+    for secret in KNOWN_SECRETS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+    {
+        if output.contains(secret.as_str()) {
+            output = output.replace(secret.as_str(), REDACTED);
+        }
+    }
+
+    redact_bearer_tokens(&redact_secret_params(&output))
+}
+
+fn redact_secret_params(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let at_boundary = index == 0 || is_param_boundary(bytes[index - 1]);
+        let param = at_boundary
+            .then(|| {
+                SECRET_PARAMS.iter().find(|name| {
+                    let end = index + name.len();
+                    bytes.get(end) == Some(&b'=')
+                        && bytes[index..end].eq_ignore_ascii_case(name.as_bytes())
+                })
+            })
+            .flatten();
+
+        match param {
+            Some(name) => {
+                let value_start = index + name.len() + 1;
+                output.extend_from_slice(&bytes[index..value_start]);
+                output.extend_from_slice(REDACTED.as_bytes());
+                index = value_start;
+                // A registered secret may already have been replaced here.
+                if bytes[index..].starts_with(REDACTED.as_bytes()) {
+                    index += REDACTED.len();
+                }
+                while index < bytes.len() && !is_value_end(bytes[index]) {
+                    index += 1;
+                }
+            }
+            None => {
+                output.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    // Only whole ASCII runs were replaced, so the bytes are still UTF-8.
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+fn redact_bearer_tokens(input: &str) -> String {
+    let lower = input.to_ascii_lowercase();
+    let mut output = String::with_capacity(input.len());
+    let mut index = 0;
+
+    while let Some(found) = lower[index..].find(BEARER_PREFIX) {
+        let token_start = index + found + BEARER_PREFIX.len();
+        output.push_str(&input[index..token_start]);
+        let token_len = input[token_start..]
+            .find(|ch: char| ch.is_whitespace() || matches!(ch, '"' | '\'' | ',' | ';'))
+            .unwrap_or(input.len() - token_start);
+        if token_len > 0 {
+            output.push_str(REDACTED);
+        }
+        index = token_start + token_len;
+    }
+
+    output.push_str(&input[index..]);
+    output
+}
+
+fn is_param_boundary(byte: u8) -> bool {
+    matches!(
+        byte,
+        b'?' | b'&' | b';' | b',' | b'"' | b'\'' | b'(' | b'{' | b'['
+    ) || byte.is_ascii_whitespace()
+}
+
+fn is_value_end(byte: u8) -> bool {
+    matches!(
+        byte,
+        b'&' | b'#' | b'"' | b'\'' | b')' | b',' | b';' | b'>' | b']' | b'}'
+    ) || byte.is_ascii_whitespace()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::AppError;
+    use proptest::prelude::*;
+
+    #[test]
+    fn query_parameters_are_redacted() {
+        let message = "error sending request for url \
+            (https://api.twelvedata.com/quote?symbol=AAPL&apikey=abc123XYZ&format=json)";
+
+        assert_eq!(
+            redact(message),
+            "error sending request for url \
+            (https://api.twelvedata.com/quote?symbol=AAPL&apikey=[REDACTED]&format=json)"
+        );
+        assert_eq!(
+            redact("token=t0k3n api_key=k"),
+            "token=[REDACTED] api_key=[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn bearer_tokens_are_redacted() {
+        assert_eq!(
+            redact(r#"{"Authorization":"Bearer eyJhbGciOi.J9"}"#),
+            r#"{"Authorization":"Bearer [REDACTED]"}"#
+        );
+    }
+
+    #[test]
+    fn ordinary_text_is_left_alone() {
+        let text = "monkey=3 keyboard=qwerty symbol=AAPL range=1M";
+        assert_eq!(redact(text), text);
+        assert_eq!(redact("Prix : 12,50 €"), "Prix : 12,50 €");
+    }
+
+    #[test]
+    fn a_bare_key_parameter_is_not_a_secret() {
+        let text = "cache miss for key=AAPL:1day&range=1M";
+        assert_eq!(redact(text), text);
+        assert_eq!(
+            redact("key=AAPL client_secret=s3cr3t"),
+            "key=AAPL client_secret=[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn registered_secrets_are_redacted_anywhere() {
+        register_secret("sk-live-4f8a2c9e");
+        register_secret("short");
+
+        assert_eq!(
+            redact("key sk-live-4f8a2c9e rejected"),
+            "key [REDACTED] rejected"
+        );
+        assert_eq!(redact("short"), "short");
+        assert_eq!(
+            redact("quote?apikey=sk-live-4f8a2c9e&symbol=AAPL"),
+            "quote?apikey=[REDACTED]&symbol=AAPL"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn registered_keys_never_survive(key in "[A-Za-z0-9]{8,40}", prefix in "[a-z ]{0,20}") {
+            register_secret(&key);
+            let url = format!("https://api.twelvedata.com/quote?symbol=AAPL&apikey={key}");
+            let messages = [
+                redact(&format!("{prefix}{key}")),
+                redact(&format!("Provider request failed: error sending request for url ({url})")),
+                AppError::provider("network_error").with_arg("detail", format!("{prefix} {url}")).message,
+                AppError::internal("diagnostics_bundle_failed").with_arg("detail", format!("Bearer {key}")).message,
+            ];
+
+            for message in messages {
+                prop_assert!(!message.contains(&key), "{message} leaks {key}");
+            }
+        }
+
+        #[test]
+        fn apikey_parameters_never_survive(key in "[A-Za-z0-9_.~-]{1,40}") {
+            let message = redact(&format!("GET /time_series?apikey={key}&symbol=MSFT failed"));
+            prop_assert_eq!(message, "GET /time_series?apikey=[REDACTED]&symbol=MSFT failed");
+        }
+    }
+}
@@ -0,0 +1,42 @@
+/// Simple returns between consecutive values. Pairs starting from a
+/// non-positive value are skipped.
+pub fn period_returns(values: &[f64]) -> Vec<f64> {
+    values
+        .windows(2)
+        .filter(|window| window[0] > 0.0)
+        .map(|window| window[1] / window[0] - 1.0)
+        .collect()
+}
+
+pub fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Sample covariance; `None` with fewer than two observations or mismatched
+/// lengths.
+pub fn covariance(left: &[f64], right: &[f64]) -> Option<f64> {
+    if left.len() != right.len() || left.len() < 2 {
+        return None;
+    }
+
+    let left_mean = mean(left)?;
+    let right_mean = mean(right)?;
+    let sum: f64 = left
+        .iter()
+        .zip(right)
+        .map(|(l, r)| (l - left_mean) * (r - right_mean))
+        .sum();
+    Some(sum / (left.len() - 1) as f64)
+}
+
+pub fn std_dev(values: &[f64]) -> Option<f64> {
+    covariance(values, values).map(f64::sqrt)
+}
+
+/// Pearson correlation; `None` when either side has no variance.
+pub fn correlation(left: &[f64], right: &[f64]) -> Option<f64> {
+    let denominator = std_dev(left)? * std_dev(right)?;
+    (denominator > 0.0)
+        .then_some(covariance(left, right)? / denominator)
+        .map(|value| value.clamp(-1.0, 1.0))
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
+
+const PROVIDER_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const PROVIDER_DATE_FORMAT: &str = "%Y-%m-%d";
+
+pub fn now_rfc3339() -> String {
+    format_rfc3339(Utc::now())
+}
+
+pub fn unix_secs_to_rfc3339(secs: i64) -> Option<String> {
+    DateTime::<Utc>::from_timestamp(secs, 0).map(format_rfc3339)
+}
+
+pub fn rfc3339_to_unix_secs(value: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|datetime| datetime.timestamp())
+}
+
+/// Converts a naive provider datetime ("2026-02-22 10:00:00" or "2026-02-22")
+/// expressed in `timezone` into an RFC 3339 UTC string. Unknown or missing
+/// timezones are treated as UTC.
+pub fn provider_datetime_to_rfc3339(value: &str, timezone: Option<&str>) -> Option<String> {
+    let trimmed = value.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(trimmed) {
+        return Some(format_rfc3339(datetime.with_timezone(&Utc)));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(trimmed, PROVIDER_DATETIME_FORMAT)
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(trimmed, PROVIDER_DATE_FORMAT)
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })?;
+
+    let tz = timezone.and_then(|name| name.parse::<Tz>().ok());
+    let utc = match tz {
+        Some(tz) => tz
+            .from_local_datetime(&naive)
+            .earliest()?
+            .with_timezone(&Utc),
+        None => Utc.from_utc_datetime(&naive),
+    };
+
+    Some(format_rfc3339(utc))
+}
+
+fn format_rfc3339(datetime: DateTime<Utc>) -> String {
+    datetime.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_datetime_is_converted_from_exchange_timezone() {
+        let converted =
+            provider_datetime_to_rfc3339("2026-02-20 10:00:00", Some("America/New_York"));
+        assert_eq!(converted.as_deref(), Some("2026-02-20T15:00:00Z"));
+    }
+
+    #[test]
+    fn provider_date_only_values_use_local_midnight() {
+        let converted = provider_datetime_to_rfc3339("2026-07-01", Some("America/New_York"));
+        assert_eq!(converted.as_deref(), Some("2026-07-01T04:00:00Z"));
+    }
+
+    #[test]
+    fn unknown_timezone_falls_back_to_utc() {
+        let converted = provider_datetime_to_rfc3339("2026-02-20 10:00:00", Some("Mars/Olympus"));
+        assert_eq!(converted.as_deref(), Some("2026-02-20T10:00:00Z"));
+    }
+
+    #[test]
+    fn rfc3339_values_are_normalized_to_utc() {
+        let converted = provider_datetime_to_rfc3339("2026-02-20T10:00:00+01:00", None);
+        assert_eq!(converted.as_deref(), Some("2026-02-20T09:00:00Z"));
+    }
+
+    #[test]
+    fn unparseable_values_are_rejected() {
+        assert!(provider_datetime_to_rfc3339("yesterday", None).is_none());
+    }
+
+    #[test]
+    fn unix_secs_roundtrip() {
+        let formatted = unix_secs_to_rfc3339(1_771_754_400).unwrap();
+        assert_eq!(formatted, "2026-02-22T10:00:00Z");
+        assert_eq!(rfc3339_to_unix_secs(&formatted), Some(1_771_754_400));
+    }
+}
@@ -0,0 +1,295 @@
+use super::{AlertEvent, AppError, QuoteSummary};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+
+const MAX_WEBHOOK_NAME_LEN: usize = 60;
+const MAX_WEBHOOK_HEADERS: usize = 20;
+const MAX_BODY_TEMPLATE_LEN: usize = 8 * 1024;
+
+/// An outgoing webhook. `body_template` is JSON text in which `{{name}}`
+/// placeholders are replaced by JSON values: `event`, `payload` and
+/// `sentAt` always, `symbol`, `message`, `price` and `triggeredAt` for
+/// alerts, and `quotes` and `quoteCount` for refreshes. Without a template the
+/// body is `{"event": ..., "sentAt": ..., "payload": ...}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub headers: BTreeMap<String, String>,
+    pub body_template: Option<String>,
+    pub on_alert: bool,
+    pub on_refresh: bool,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookInput {
+    pub name: String,
+    pub url: String,
+    pub headers: Option<BTreeMap<String, String>>,
+    pub body_template: Option<String>,
+    pub on_alert: Option<bool>,
+    pub on_refresh: Option<bool>,
+    pub enabled: Option<bool>,
+}
+
+/// What a webhook is being sent for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookTrigger {
+    Alert(AlertEvent),
+    Refresh(Vec<QuoteSummary>),
+}
+
+impl WebhookTrigger {
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            Self::Alert(_) => "alert",
+            Self::Refresh(_) => "refresh",
+        }
+    }
+
+    pub fn wanted_by(&self, webhook: &Webhook) -> bool {
+        webhook.enabled
+            && match self {
+                Self::Alert(_) => webhook.on_alert,
+                Self::Refresh(_) => webhook.on_refresh,
+            }
+    }
+}
+
+/// One delivery attempt sequence for a webhook, successful or not.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub event: String,
+    pub attempts: u32,
+    pub status_code: Option<u16>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub started_at: String,
+    pub finished_at: String,
+}
+
+/// Validates a webhook entered by the user and assigns it `id`. The body
+/// template must render to valid JSON for every trigger the webhook is on for.
+pub fn validate_webhook(
+    id: String,
+    input: WebhookInput,
+    created_at: String,
+) -> Result<Webhook, AppError> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() || name.len() > MAX_WEBHOOK_NAME_LEN {
+        return Err(invalid_webhook("name_length").with_arg("max", MAX_WEBHOOK_NAME_LEN));
+    }
+
+    let url = input.url.trim().to_string();
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .and_then(|rest| rest.split(['/', '?', '#']).next())
+        .unwrap_or_default();
+    if host.is_empty() || url.chars().any(char::is_whitespace) {
+        return Err(invalid_webhook("url"));
+    }
+
+    let headers = input.headers.unwrap_or_default();
+    if headers.len() > MAX_WEBHOOK_HEADERS {
+        return Err(invalid_webhook("header_count").with_arg("max", MAX_WEBHOOK_HEADERS));
+    }
+    for (header, value) in &headers {
+        let valid_name = !header.is_empty()
+            && header
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(ch));
+        if !valid_name || value.contains(['\r', '\n']) {
+            return Err(invalid_webhook("header").with_arg("header", header));
+        }
+    }
+
+    let on_alert = input.on_alert.unwrap_or(true);
+    let on_refresh = input.on_refresh.unwrap_or(false);
+    let body_template = input
+        .body_template
+        .map(|template| template.trim().to_string())
+        .filter(|template| !template.is_empty());
+    if let Some(template) = body_template.as_deref() {
+        if template.len() > MAX_BODY_TEMPLATE_LEN {
+            return Err(invalid_webhook("template_too_long"));
+        }
+        let [alert_sample, refresh_sample] = sample_triggers();
+        if on_alert {
+            render_webhook_body(Some(template), &alert_sample, &created_at)?;
+        }
+        if on_refresh {
+            render_webhook_body(Some(template), &refresh_sample, &created_at)?;
+        }
+    }
+
+    Ok(Webhook {
+        id,
+        name,
+        url,
+        headers,
+        body_template,
+        on_alert,
+        on_refresh,
+        enabled: input.enabled.unwrap_or(true),
+        created_at,
+    })
+}
+
+/// Renders the request body for `trigger`, failing when the template does
+/// not produce valid JSON.
+pub fn render_webhook_body(
+    template: Option<&str>,
+    trigger: &WebhookTrigger,
+    sent_at: &str,
+) -> Result<String, AppError> {
+    let mut variables = BTreeMap::from([
+        ("event", json!(trigger.event_name())),
+        ("sentAt", json!(sent_at)),
+    ]);
+    match trigger {
+        WebhookTrigger::Alert(event) => {
+            variables.insert("payload", json!(event));
+            variables.insert("symbol", json!(event.symbol));
+            variables.insert("message", json!(event.message));
+            variables.insert("price", json!(event.price));
+            variables.insert("triggeredAt", json!(event.triggered_at));
+        }
+        WebhookTrigger::Refresh(quotes) => {
+            variables.insert("payload", json!({ "quotes": quotes }));
+            variables.insert("quotes", json!(quotes));
+            variables.insert("quoteCount", json!(quotes.len()));
+        }
+    }
+
+    let Some(template) = template else {
+        return Ok(json!({
+            "event": variables["event"],
+            "sentAt": variables["sentAt"],
+            "payload": variables["payload"],
+        })
+        .to_string());
+    };
+
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let end = after_open
+            .find("}}")
+            .ok_or_else(|| invalid_webhook("unclosed_placeholder"))?;
+        let name = after_open[..end].trim();
+        let value = variables
+            .get(name)
+            .ok_or_else(|| invalid_webhook("unknown_placeholder").with_arg("name", name))?;
+        rendered.push_str(&value.to_string());
+        rest = &after_open[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    serde_json::from_str::<Value>(&rendered)
+        .map_err(|err| invalid_webhook("invalid_json").with_arg("detail", err))?;
+    Ok(rendered)
+}
+
+fn sample_triggers() -> [WebhookTrigger; 2] {
+    [
+        WebhookTrigger::Alert(AlertEvent {
+            id: "sample".to_string(),
+            rule_id: "sample".to_string(),
+            symbol: "AAPL".to_string(),
+            condition: super::AlertCondition::PriceAbove { price: 1.0 },
+            price: 1.0,
+            reference_value: None,
+            message: "Sample alert".to_string(),
+            triggered_at: String::new(),
+        }),
+        WebhookTrigger::Refresh(Vec::new()),
+    ]
+}
+
+fn invalid_webhook(reason: &str) -> AppError {
+    AppError::validation("invalid_webhook").with_reason(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(url: &str, body_template: Option<&str>) -> WebhookInput {
+        WebhookInput {
+            name: "Chat".to_string(),
+            url: url.to_string(),
+            headers: Some(BTreeMap::from([(
+                "Authorization".to_string(),
+                "Bearer token".to_string(),
+            )])),
+            body_template: body_template.map(str::to_string),
+            on_alert: None,
+            on_refresh: Some(true),
+            enabled: None,
+        }
+    }
+
+    fn validate(input: WebhookInput) -> Result<Webhook, AppError> {
+        validate_webhook(
+            "hook-1".to_string(),
+            input,
+            "2026-02-22T12:00:00Z".to_string(),
+        )
+    }
+
+    #[test]
+    fn template_placeholders_become_json_values() {
+        let trigger = sample_triggers()[0].clone();
+        let body = render_webhook_body(
+            Some(r#"{"text": {{message}}, "price": {{ price }}, "kind": {{event}}}"#),
+            &trigger,
+            "2026-02-22T15:00:00Z",
+        )
+        .unwrap();
+
+        let parsed: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(parsed["text"], "Sample alert");
+        assert_eq!(parsed["price"], 1.0);
+        assert_eq!(parsed["kind"], "alert");
+    }
+
+    #[test]
+    fn default_body_wraps_the_payload() {
+        let body = render_webhook_body(None, &WebhookTrigger::Refresh(Vec::new()), "now").unwrap();
+        let parsed: Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(parsed["event"], "refresh");
+        assert_eq!(parsed["payload"]["quotes"], json!([]));
+    }
+
+    #[test]
+    fn validate_webhook_rejects_bad_urls_headers_and_templates() {
+        assert!(validate(input("ftp://example.com", None)).is_err());
+        assert!(validate(input("https://", None)).is_err());
+        assert!(validate(input("https://example.com", Some(r#"{"text": {{nope}}}"#))).is_err());
+        assert!(validate(input("https://example.com", Some("{{message}"))).is_err());
+        // Alert-only placeholders have no value on refresh.
+        assert!(validate(input("https://example.com", Some(r#"{"s": {{symbol}}}"#))).is_err());
+
+        let mut bad_header = input("https://example.com", None);
+        bad_header.headers = Some(BTreeMap::from([("X Bad".to_string(), "1".to_string())]));
+        assert!(validate(bad_header).is_err());
+
+        let webhook = validate(input(" https://example.com/hook ", Some(" {{payload}} "))).unwrap();
+        assert_eq!(webhook.url, "https://example.com/hook");
+        assert_eq!(webhook.body_template.as_deref(), Some("{{payload}}"));
+        assert!(webhook.on_alert && webhook.on_refresh && webhook.enabled);
+    }
+}
@@ -5,10 +5,5 @@ pub fn build_http_client(timeout_seconds: u64) -> Result<reqwest::Client, AppErr
     reqwest::Client::builder()
         .timeout(Duration::from_secs(timeout_seconds))
         .build()
-        .map_err(|err| {
-            AppError::internal(
-                "http_client_init_failed",
-                format!("Failed to initialize HTTP client: {err}"),
-            )
-        })
+        .map_err(|err| AppError::internal("http_client_init_failed").with_arg("detail", err))
 }
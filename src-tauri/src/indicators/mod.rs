@@ -0,0 +1,552 @@
+use crate::domain::{AppError, PricePoint, QuoteStatus, SymbolPerformance, TimeRange};
+use serde::{Deserialize, Serialize};
+
+const MAX_INDICATOR_PERIOD: usize = 500;
+const MAX_INDICATOR_SPECS: usize = 10;
+
+/// One indicator request. Periods are counted in bars of the range's
+/// interval.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum IndicatorSpec {
+    Sma {
+        period: usize,
+    },
+    Ema {
+        period: usize,
+    },
+    Rsi {
+        period: usize,
+    },
+    Macd {
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    },
+    Bollinger {
+        period: usize,
+        std_devs: f64,
+    },
+    Atr {
+        period: usize,
+    },
+    Vwap,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndicatorLine {
+    pub name: String,
+    pub values: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndicatorResult {
+    pub spec: IndicatorSpec,
+    pub lines: Vec<IndicatorLine>,
+}
+
+/// Indicator values aligned with `timestamps`; a value is `None` while the
+/// indicator is still warming up or when the bar lacks the fields it needs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndicatorSet {
+    pub symbol: String,
+    pub range: TimeRange,
+    pub timestamps: Vec<String>,
+    pub indicators: Vec<IndicatorResult>,
+    pub status: QuoteStatus,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+pub fn validate_specs(specs: &[IndicatorSpec]) -> Result<(), AppError> {
+    if specs.is_empty() || specs.len() > MAX_INDICATOR_SPECS {
+        return Err(invalid_indicator("count").with_arg("max", MAX_INDICATOR_SPECS));
+    }
+
+    for spec in specs {
+        let periods: Vec<usize> = match *spec {
+            IndicatorSpec::Sma { period }
+            | IndicatorSpec::Ema { period }
+            | IndicatorSpec::Rsi { period }
+            | IndicatorSpec::Atr { period } => vec![period],
+            IndicatorSpec::Macd {
+                fast_period,
+                slow_period,
+                signal_period,
+            } => {
+                if fast_period >= slow_period {
+                    return Err(invalid_indicator("macd_periods"));
+                }
+                vec![fast_period, slow_period, signal_period]
+            }
+            IndicatorSpec::Bollinger { period, std_devs } => {
+                if !std_devs.is_finite() || std_devs <= 0.0 {
+                    return Err(invalid_indicator("bollinger_width"));
+                }
+                vec![period]
+            }
+            IndicatorSpec::Vwap => Vec::new(),
+        };
+
+        if periods
+            .iter()
+            .any(|period| !(1..=MAX_INDICATOR_PERIOD).contains(period))
+        {
+            return Err(invalid_indicator("period").with_arg("max", MAX_INDICATOR_PERIOD));
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes `specs` over `history`, the full cached series, so indicators are
+/// already warmed up at the start of the range, then keeps the bars of
+/// `performance`. VWAP is anchored at the first bar of the range.
+pub fn build_indicator_set(
+    performance: &SymbolPerformance,
+    history: &[PricePoint],
+    specs: &[IndicatorSpec],
+) -> IndicatorSet {
+    let range_len = performance.points.len();
+    let history = match performance.points.first() {
+        Some(first)
+            if history
+                .iter()
+                .rev()
+                .nth(range_len.saturating_sub(1))
+                .is_some_and(|point| point.ts == first.ts) =>
+        {
+            history
+        }
+        _ => performance.points.as_slice(),
+    };
+    let offset = history.len() - range_len;
+
+    let indicators = specs
+        .iter()
+        .map(|spec| IndicatorResult {
+            spec: *spec,
+            lines: compute_indicator(*spec, history, offset)
+                .into_iter()
+                .map(|mut line| {
+                    line.values.drain(..offset);
+                    line
+                })
+                .collect(),
+        })
+        .collect();
+
+    IndicatorSet {
+        symbol: performance.symbol.clone(),
+        range: performance.range,
+        timestamps: performance
+            .points
+            .iter()
+            .map(|point| point.ts.clone())
+            .collect(),
+        indicators,
+        status: performance.status,
+        error_code: performance.error_code.clone(),
+        error_message: performance.error_message.clone(),
+    }
+}
+
+/// Computes one indicator over `points`; every line has one value per point.
+pub fn compute_indicator(
+    spec: IndicatorSpec,
+    points: &[PricePoint],
+    vwap_anchor: usize,
+) -> Vec<IndicatorLine> {
+    let closes: Vec<f64> = points.iter().map(|point| point.close).collect();
+    match spec {
+        IndicatorSpec::Sma { period } => vec![line("sma", sma(&closes, period))],
+        IndicatorSpec::Ema { period } => vec![line("ema", ema(&closes, period))],
+        IndicatorSpec::Rsi { period } => vec![line("rsi", rsi(&closes, period))],
+        IndicatorSpec::Macd {
+            fast_period,
+            slow_period,
+            signal_period,
+        } => {
+            let fast = ema(&closes, fast_period);
+            let slow = ema(&closes, slow_period);
+            let macd: Vec<Option<f64>> = fast
+                .iter()
+                .zip(&slow)
+                .map(|(fast, slow)| Some((*fast)? - (*slow)?))
+                .collect();
+            let signal = ema_of_defined(&macd, signal_period);
+            let histogram = macd
+                .iter()
+                .zip(&signal)
+                .map(|(macd, signal)| Some((*macd)? - (*signal)?))
+                .collect();
+            vec![
+                line("macd", macd),
+                line("signal", signal),
+                line("histogram", histogram),
+            ]
+        }
+        IndicatorSpec::Bollinger { period, std_devs } => {
+            let middle = sma(&closes, period);
+            let deviations = rolling_std_dev(&closes, period);
+            let band = |sign: f64| -> Vec<Option<f64>> {
+                middle
+                    .iter()
+                    .zip(&deviations)
+                    .map(|(middle, deviation)| Some((*middle)? + sign * std_devs * (*deviation)?))
+                    .collect()
+            };
+            vec![
+                line("upper", band(1.0)),
+                line("middle", middle.clone()),
+                line("lower", band(-1.0)),
+            ]
+        }
+        IndicatorSpec::Atr { period } => vec![line("atr", atr(points, period))],
+        IndicatorSpec::Vwap => vec![line("vwap", vwap(points, vwap_anchor))],
+    }
+}
+
+pub fn sma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 {
+        return result;
+    }
+
+    let mut sum = 0.0;
+    for (index, value) in values.iter().enumerate() {
+        sum += value;
+        if index >= period {
+            sum -= values[index - period];
+        }
+        if index + 1 >= period {
+            result[index] = Some(sum / period as f64);
+        }
+    }
+    result
+}
+
+/// Exponential moving average seeded with the simple average of the first
+/// `period` values.
+pub fn ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return result;
+    }
+
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut current = values[..period].iter().sum::<f64>() / period as f64;
+    result[period - 1] = Some(current);
+    for index in period..values.len() {
+        current += alpha * (values[index] - current);
+        result[index] = Some(current);
+    }
+    result
+}
+
+/// Relative strength index with Wilder's smoothing.
+pub fn rsi(closes: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; closes.len()];
+    if period == 0 || closes.len() <= period {
+        return result;
+    }
+
+    let changes: Vec<f64> = closes.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let mut average_gain = changes[..period]
+        .iter()
+        .map(|change| change.max(0.0))
+        .sum::<f64>()
+        / period as f64;
+    let mut average_loss = changes[..period]
+        .iter()
+        .map(|change| (-change).max(0.0))
+        .sum::<f64>()
+        / period as f64;
+    result[period] = Some(rsi_value(average_gain, average_loss));
+
+    for (offset, change) in changes[period..].iter().enumerate() {
+        average_gain = (average_gain * (period - 1) as f64 + change.max(0.0)) / period as f64;
+        average_loss = (average_loss * (period - 1) as f64 + (-change).max(0.0)) / period as f64;
+        result[period + offset + 1] = Some(rsi_value(average_gain, average_loss));
+    }
+    result
+}
+
+/// Average true range with Wilder's smoothing. Bars without a high or low
+/// break the chain, so the values after them restart their warm-up.
+pub fn atr(points: &[PricePoint], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; points.len()];
+    if period == 0 {
+        return result;
+    }
+
+    let mut window: Vec<f64> = Vec::with_capacity(period);
+    let mut current: Option<f64> = None;
+    for index in 1..points.len() {
+        let (point, previous_close) = (&points[index], points[index - 1].close);
+        let (Some(high), Some(low)) = (point.high, point.low) else {
+            window.clear();
+            current = None;
+            continue;
+        };
+
+        let true_range = (high - low)
+            .max((high - previous_close).abs())
+            .max((low - previous_close).abs());
+        current = match current {
+            Some(average) => Some((average * (period - 1) as f64 + true_range) / period as f64),
+            None => {
+                window.push(true_range);
+                (window.len() == period).then(|| window.iter().sum::<f64>() / period as f64)
+            }
+        };
+        result[index] = current;
+    }
+    result
+}
+
+/// Volume-weighted average of the typical price, accumulated from `anchor`.
+/// Bars without high, low or volume are skipped.
+pub fn vwap(points: &[PricePoint], anchor: usize) -> Vec<Option<f64>> {
+    let mut weighted_sum = 0.0;
+    let mut volume_sum = 0.0;
+    points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            if index < anchor {
+                return None;
+            }
+            if let (Some(high), Some(low), Some(volume)) = (point.high, point.low, point.volume)
+                && volume > 0.0
+            {
+                weighted_sum += (high + low + point.close) / 3.0 * volume;
+                volume_sum += volume;
+            }
+            (volume_sum > 0.0).then(|| weighted_sum / volume_sum)
+        })
+        .collect()
+}
+
+fn rsi_value(average_gain: f64, average_loss: f64) -> f64 {
+    if average_loss == 0.0 {
+        return if average_gain == 0.0 { 50.0 } else { 100.0 };
+    }
+    100.0 - 100.0 / (1.0 + average_gain / average_loss)
+}
+
+fn rolling_std_dev(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let means = sma(values, period);
+    means
+        .iter()
+        .enumerate()
+        .map(|(index, mean)| {
+            let mean = (*mean)?;
+            let window = &values[index + 1 - period..=index];
+            let variance = window
+                .iter()
+                .map(|value| (value - mean).powi(2))
+                .sum::<f64>()
+                / period as f64;
+            Some(variance.sqrt())
+        })
+        .collect()
+}
+
+/// EMA over the defined tail of `values`, which must be `None` only at the
+/// start (as MACD is).
+fn ema_of_defined(values: &[Option<f64>], period: usize) -> Vec<Option<f64>> {
+    let start = values
+        .iter()
+        .position(Option::is_some)
+        .unwrap_or(values.len());
+    let defined: Vec<f64> = values[start..].iter().flatten().copied().collect();
+    let mut result = vec![None; start];
+    result.extend(ema(&defined, period));
+    result
+}
+
+fn line(name: &str, values: Vec<Option<f64>>) -> IndicatorLine {
+    IndicatorLine {
+        name: name.to_string(),
+        values,
+    }
+}
+
+fn invalid_indicator(reason: &str) -> AppError {
+    AppError::validation("invalid_indicator").with_reason(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bars(closes: &[f64]) -> Vec<PricePoint> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(day, close)| {
+                PricePoint::new(format!("2026-02-{:02}T05:00:00Z", day + 1), *close)
+            })
+            .collect()
+    }
+
+    fn ohlcv(close: f64, high: f64, low: f64, volume: f64) -> PricePoint {
+        PricePoint {
+            high: Some(high),
+            low: Some(low),
+            volume: Some(volume),
+            ..PricePoint::new("2026-02-01T05:00:00Z", close)
+        }
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn sma_and_ema_warm_up_before_producing_values() {
+        let closes = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(
+            sma(&closes, 3),
+            vec![None, None, Some(2.0), Some(3.0), Some(4.0)]
+        );
+
+        let ema = ema(&closes, 3);
+        assert_eq!(ema[1], None);
+        assert_close(ema[2], 2.0);
+        assert_close(ema[3], 3.0);
+        assert_close(ema[4], 4.0);
+    }
+
+    #[test]
+    fn rsi_is_bounded_by_one_sided_moves() {
+        let rising: Vec<f64> = (0..20).map(f64::from).collect();
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+
+        assert_eq!(rsi(&rising, 14)[13], None);
+        assert_eq!(rsi(&rising, 14)[19], Some(100.0));
+        assert_eq!(rsi(&falling, 14)[19], Some(0.0));
+    }
+
+    #[test]
+    fn macd_of_a_linear_trend_has_a_constant_spread() {
+        let closes: Vec<f64> = (0..60).map(|day| 100.0 + f64::from(day)).collect();
+        let lines = compute_indicator(
+            IndicatorSpec::Macd {
+                fast_period: 12,
+                slow_period: 26,
+                signal_period: 9,
+            },
+            &bars(&closes),
+            0,
+        );
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].values[24], None);
+        assert_close(lines[0].values[59], 7.0);
+        assert_close(lines[1].values[59], 7.0);
+        assert_close(lines[2].values[59], 0.0);
+    }
+
+    #[test]
+    fn bollinger_bands_collapse_on_a_flat_series() {
+        let lines = compute_indicator(
+            IndicatorSpec::Bollinger {
+                period: 3,
+                std_devs: 2.0,
+            },
+            &bars(&[10.0, 10.0, 10.0, 10.0]),
+            0,
+        );
+
+        assert_eq!(lines[0].values[3], Some(10.0));
+        assert_eq!(lines[1].values[3], Some(10.0));
+        assert_eq!(lines[2].values[3], Some(10.0));
+    }
+
+    #[test]
+    fn atr_uses_gaps_against_the_previous_close() {
+        let points = vec![
+            ohlcv(10.0, 10.5, 9.5, 100.0),
+            ohlcv(12.0, 12.5, 11.5, 100.0),
+            ohlcv(11.0, 11.5, 10.5, 100.0),
+        ];
+
+        let values = atr(&points, 2);
+        assert_eq!(values[1], None);
+        assert_close(values[2], 2.0);
+        assert_eq!(atr(&bars(&[1.0, 2.0, 3.0]), 1), vec![None, None, None]);
+    }
+
+    #[test]
+    fn vwap_weights_typical_price_by_volume_from_the_anchor() {
+        let points = vec![
+            ohlcv(50.0, 50.0, 50.0, 1_000.0),
+            ohlcv(10.0, 10.0, 10.0, 100.0),
+            ohlcv(20.0, 20.0, 20.0, 300.0),
+        ];
+
+        let values = vwap(&points, 1);
+        assert_eq!(values[0], None);
+        assert_close(values[1], 10.0);
+        assert_close(values[2], 17.5);
+    }
+
+    #[test]
+    fn indicator_set_is_warmed_up_from_history_and_sliced_to_the_range() {
+        let history = bars(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let performance = SymbolPerformance::from_points(
+            "AAPL",
+            TimeRange::OneWeek,
+            history[3..].to_vec(),
+            None,
+            QuoteStatus::Fresh,
+        )
+        .unwrap();
+
+        let set = build_indicator_set(&performance, &history, &[IndicatorSpec::Sma { period: 3 }]);
+        assert_eq!(set.timestamps.len(), 2);
+        assert_eq!(
+            set.indicators[0].lines[0].values,
+            vec![Some(3.0), Some(4.0)]
+        );
+    }
+
+    #[test]
+    fn specs_are_validated() {
+        assert!(validate_specs(&[IndicatorSpec::Vwap]).is_ok());
+        assert!(validate_specs(&[]).is_err());
+        assert!(validate_specs(&[IndicatorSpec::Sma { period: 0 }]).is_err());
+        assert!(
+            validate_specs(&[IndicatorSpec::Macd {
+                fast_period: 26,
+                slow_period: 12,
+                signal_period: 9,
+            }])
+            .is_err()
+        );
+
+        let spec: IndicatorSpec =
+            serde_json::from_str(r#"{ "kind": "bollinger", "period": 20, "stdDevs": 2.0 }"#)
+                .unwrap();
+        assert_eq!(
+            spec,
+            IndicatorSpec::Bollinger {
+                period: 20,
+                std_devs: 2.0
+            }
+        );
+    }
+}
@@ -1,9 +1,17 @@
 pub mod commands;
+pub mod connectivity;
+pub mod diagnostics;
 pub mod domain;
 pub mod http;
+pub mod indicators;
+pub mod notifications;
 pub mod observability;
 pub mod persistence;
 pub mod provider;
+pub mod services;
+pub mod webhooks;
+
+use tauri::Manager;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -15,6 +23,31 @@ fn greet(name: &str) -> String {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .manage(connectivity::ConnectivityMonitor::new())
+        .manage(persistence::DeliveryLogLock::new())
+        .setup(|app| {
+            let handle = app.handle();
+            let settings = persistence::SettingsStore::from_app(handle)
+                .and_then(|store| store.load())
+                .unwrap_or_default();
+            domain::set_locale(settings.locale);
+            let level = settings.log_level;
+            match observability::init_logging_for_app(handle, level) {
+                Ok(controller) => {
+                    handle.manage(controller);
+                }
+                Err(err) => {
+                    observability::init_stderr_logging(level);
+                    tracing::warn!(
+                        error_code = %err.code,
+                        error_message = %err.message,
+                        "file_logging_unavailable"
+                    );
+                }
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             commands::bootstrap::get_app_version,
@@ -22,13 +55,48 @@ pub fn run() {
             commands::settings::get_settings,
             commands::settings::save_settings,
             commands::settings::test_provider_connection,
+            commands::settings::get_provider_status,
             commands::watchlist::get_watchlist,
             commands::watchlist::add_symbol,
             commands::watchlist::remove_symbol,
             commands::market_data::refresh_watchlist_quotes,
             commands::market_data::get_symbol_performance,
             commands::market_data::refresh_symbol_performance,
-            commands::diagnostics::clear_cache
+            commands::diagnostics::clear_cache,
+            commands::diagnostics::get_cache_stats,
+            commands::diagnostics::get_metrics,
+            commands::diagnostics::export_diagnostics,
+            commands::diagnostics::invalidate_symbol_cache,
+            commands::diagnostics::invalidate_range_cache,
+            commands::diagnostics::clear_quote_cache,
+            commands::diagnostics::clear_timeseries_cache,
+            commands::diagnostics::prune_cache_to_watchlist,
+            commands::connectivity::get_connectivity_status,
+            commands::connectivity::set_offline_mode,
+            commands::portfolio::get_transactions,
+            commands::portfolio::add_transaction,
+            commands::portfolio::update_transaction,
+            commands::portfolio::delete_transaction,
+            commands::portfolio::get_portfolio_lots,
+            commands::portfolio::get_ledger_report,
+            commands::portfolio::get_portfolio_summary,
+            commands::portfolio::get_portfolio_performance,
+            commands::analytics::get_benchmark_comparison,
+            commands::analytics::get_comparison,
+            commands::analytics::get_indicators,
+            commands::analytics::get_symbol_analytics,
+            commands::analytics::get_correlation_matrix,
+            commands::alerts::get_alert_rules,
+            commands::alerts::add_alert_rule,
+            commands::alerts::update_alert_rule,
+            commands::alerts::delete_alert_rule,
+            commands::alerts::get_alert_history,
+            commands::webhooks::get_webhooks,
+            commands::webhooks::add_webhook,
+            commands::webhooks::update_webhook,
+            commands::webhooks::delete_webhook,
+            commands::webhooks::get_webhook_deliveries,
+            commands::webhooks::test_webhook
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
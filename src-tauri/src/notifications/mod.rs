@@ -0,0 +1,264 @@
+use crate::domain::{AlertEvent, AlertRule, AppError, AppSettings, current_locale, render_message};
+use chrono::NaiveTime;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+/// Somewhere a notification can be shown.
+pub trait NotificationSink {
+    fn show(&self, title: &str, body: &str) -> Result<(), AppError>;
+}
+
+/// Native desktop notifications through the Tauri notification plugin.
+pub struct DesktopNotificationSink<'a> {
+    app: &'a AppHandle,
+}
+
+impl<'a> DesktopNotificationSink<'a> {
+    pub fn new(app: &'a AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl NotificationSink for DesktopNotificationSink<'_> {
+    fn show(&self, title: &str, body: &str) -> Result<(), AppError> {
+        self.app
+            .notification()
+            .builder()
+            .title(title)
+            .body(body)
+            .show()
+            .map_err(|err| AppError::internal("notification_failed").with_arg("detail", err))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShownNotification {
+    pub title: String,
+    pub body: String,
+}
+
+/// Keeps notifications in memory instead of showing them, for tests and
+/// sessions without a desktop.
+#[derive(Debug, Default)]
+pub struct LocalNotificationSink {
+    shown: Mutex<Vec<ShownNotification>>,
+}
+
+impl LocalNotificationSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shown(&self) -> Vec<ShownNotification> {
+        self.shown
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl NotificationSink for LocalNotificationSink {
+    fn show(&self, title: &str, body: &str) -> Result<(), AppError> {
+        self.shown
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(ShownNotification {
+                title: title.to_string(),
+                body: body.to_string(),
+            });
+        Ok(())
+    }
+}
+
+/// Shows one notification per triggered alert whose rule has notifications
+/// on. Nothing is shown when notifications are off in settings or
+/// `local_time` falls inside the quiet hours; the alerts are still in the
+/// history. A notification that fails is logged and the rest are still
+/// shown. Returns how many notifications were shown.
+pub fn notify_alerts(
+    sink: &dyn NotificationSink,
+    settings: &AppSettings,
+    rules: &[AlertRule],
+    events: &[AlertEvent],
+    local_time: NaiveTime,
+) -> usize {
+    if !settings.notifications_enabled
+        || settings
+            .quiet_hours
+            .as_ref()
+            .is_some_and(|quiet_hours| quiet_hours.contains(local_time))
+    {
+        return 0;
+    }
+
+    let mut shown = 0;
+    for event in events {
+        let notify = rules
+            .iter()
+            .find(|rule| rule.id == event.rule_id)
+            .is_some_and(|rule| rule.notify);
+        if !notify {
+            continue;
+        }
+
+        let args = BTreeMap::from([("symbol".to_string(), event.symbol.clone())]);
+        let title = render_message(current_locale(), "alert_notification_title", None, &args);
+        match sink.show(&title, &event.message) {
+            Ok(()) => shown += 1,
+            Err(err) => tracing::warn!(
+                alert_id = %event.id,
+                error_code = %err.code,
+                error_message = %err.message,
+                "alert_notification_failed"
+            ),
+        }
+    }
+
+    shown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{AlertCondition, QuietHours};
+
+    fn rule(id: &str, notify: bool) -> AlertRule {
+        AlertRule {
+            id: id.to_string(),
+            symbol: "AAPL".to_string(),
+            condition: AlertCondition::PriceAbove { price: 200.0 },
+            enabled: true,
+            notify,
+            cooldown_seconds: 900,
+            created_at: "2026-02-22T12:00:00Z".to_string(),
+            armed: false,
+            last_triggered_at: None,
+        }
+    }
+
+    fn event(rule_id: &str) -> AlertEvent {
+        AlertEvent {
+            id: format!("{rule_id}-1"),
+            rule_id: rule_id.to_string(),
+            symbol: "AAPL".to_string(),
+            condition: AlertCondition::PriceAbove { price: 200.0 },
+            price: 201.0,
+            reference_value: None,
+            message: "AAPL rose above 200.00 (now 201.00).".to_string(),
+            triggered_at: "2026-02-22T15:00:00Z".to_string(),
+        }
+    }
+
+    fn settings(quiet_hours: Option<QuietHours>) -> AppSettings {
+        AppSettings {
+            notifications_enabled: true,
+            quiet_hours,
+            ..AppSettings::default()
+        }
+    }
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn only_rules_with_notifications_on_are_shown() {
+        let sink = LocalNotificationSink::new();
+        let rules = vec![rule("loud", true), rule("silent", false)];
+
+        let shown = notify_alerts(
+            &sink,
+            &settings(None),
+            &rules,
+            &[event("loud"), event("silent")],
+            at(12, 0),
+        );
+
+        assert_eq!(shown, 1);
+        assert_eq!(
+            sink.shown(),
+            vec![ShownNotification {
+                title: "AAPL alert".to_string(),
+                body: "AAPL rose above 200.00 (now 201.00).".to_string(),
+            }]
+        );
+    }
+
+    /// Fails the first notification, then shows the rest.
+    struct FailsOnceSink {
+        failed: Mutex<bool>,
+        inner: LocalNotificationSink,
+    }
+
+    impl NotificationSink for FailsOnceSink {
+        fn show(&self, title: &str, body: &str) -> Result<(), AppError> {
+            let mut failed = self.failed.lock().unwrap();
+            if !*failed {
+                *failed = true;
+                return Err(AppError::internal("notification_failed").with_arg("detail", "busy"));
+            }
+            self.inner.show(title, body)
+        }
+    }
+
+    #[test]
+    fn a_failed_notification_does_not_hold_back_the_rest() {
+        let sink = FailsOnceSink {
+            failed: Mutex::new(false),
+            inner: LocalNotificationSink::new(),
+        };
+        let rules = vec![rule("first", true), rule("second", true)];
+
+        let shown = notify_alerts(
+            &sink,
+            &settings(None),
+            &rules,
+            &[event("first"), event("second")],
+            at(12, 0),
+        );
+
+        assert_eq!(shown, 1);
+        assert_eq!(sink.inner.shown().len(), 1);
+    }
+
+    #[test]
+    fn quiet_hours_spanning_midnight_hold_notifications_back() {
+        let sink = LocalNotificationSink::new();
+        let rules = vec![rule("loud", true)];
+        let quiet = settings(Some(QuietHours {
+            start: "22:00".to_string(),
+            end: "07:00".to_string(),
+        }));
+
+        for time in [at(23, 30), at(3, 0), at(6, 59)] {
+            assert_eq!(
+                notify_alerts(&sink, &quiet, &rules, &[event("loud")], time),
+                0
+            );
+        }
+        assert_eq!(
+            notify_alerts(&sink, &quiet, &rules, &[event("loud")], at(7, 0)),
+            1
+        );
+        assert_eq!(sink.shown().len(), 1);
+    }
+
+    #[test]
+    fn notifications_setting_turns_everything_off() {
+        let sink = LocalNotificationSink::new();
+        let mut disabled = settings(None);
+        disabled.notifications_enabled = false;
+
+        let shown = notify_alerts(
+            &sink,
+            &disabled,
+            &[rule("loud", true)],
+            &[event("loud")],
+            at(12, 0),
+        );
+        assert_eq!(shown, 0);
+        assert!(sink.shown().is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::domain::{AppError, LogLevel, redact};
+use serde_json::{Map, Value};
+use std::io::{self, Write};
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tracing::{Event, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::format::{FormatEvent, FormatFields, JsonFields, Writer};
+use tracing_subscriber::fmt::{FmtContext, MakeWriter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry, fmt, reload};
+
+/// Environment variable that overrides the level from settings, using
+/// `EnvFilter` syntax (for example `info,tauri_appy_lib=debug`).
+pub const LOG_FILTER_ENV: &str = "STOCKS_LOG";
+
+pub const LOG_FILE_PREFIX: &str = "stocks";
+pub const LOG_FILE_SUFFIX: &str = "log";
+const MAX_LOG_FILES: usize = 7;
+
+/// Keeps the log writer alive and lets settings change the level at runtime.
+pub struct LogController {
+    filter: reload::Handle<EnvFilter, Registry>,
+    _guard: WorkerGuard,
+}
+
+impl LogController {
+    pub fn set_level(&self, level: LogLevel) -> Result<(), AppError> {
+        self.filter
+            .reload(build_filter(level))
+            .map_err(|err| AppError::internal("log_level_update_failed").with_arg("detail", err))
+    }
+}
+
+pub fn init_logging_for_app(app: &AppHandle, level: LogLevel) -> Result<LogController, AppError> {
+    let log_dir = app.path().app_log_dir().map_err(|err| {
+        AppError::persistence("log_path_resolution_failed").with_arg("detail", err)
+    })?;
+
+    init_logging(&log_dir, level)
+}
+
+/// Installs the global subscriber: JSON lines in a daily rotated file under
+/// `log_dir` keeping the last `MAX_LOG_FILES` days, plus readable output on
+/// stderr in debug builds.
+pub fn init_logging(log_dir: &Path, level: LogLevel) -> Result<LogController, AppError> {
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir)
+        .map_err(|err| AppError::persistence("log_file_init_failed").with_arg("detail", err))?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let (filter, filter_handle) = reload::Layer::new(build_filter(level));
+    let console = cfg!(debug_assertions).then(|| {
+        fmt::layer()
+            .compact()
+            .with_writer(RedactingWriter::new(io::stderr))
+    });
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(json_layer(writer))
+        .with(console)
+        .try_init()
+        .map_err(|err| AppError::internal("logging_init_failed").with_arg("detail", err))?;
+
+    Ok(LogController {
+        filter: filter_handle,
+        _guard: guard,
+    })
+}
+
+/// Stands in for `init_logging` when the log file cannot be set up: readable
+/// output on stderr only, so the failure and later events are not lost. Does
+/// nothing if a subscriber is already installed.
+pub fn init_stderr_logging(level: LogLevel) {
+    let _ = tracing_subscriber::registry()
+        .with(build_filter(level))
+        .with(
+            fmt::layer()
+                .compact()
+                .with_writer(RedactingWriter::new(io::stderr)),
+        )
+        .try_init();
+}
+
+/// One JSON object per line, with event fields at the top level, the
+/// caller's `fields` as a nested object and the enclosing command span under
+/// `span`. Lines are redacted before writing.
+pub fn json_layer<S, W>(writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    fmt::layer()
+        .fmt_fields(JsonFields::new())
+        .event_format(NestedFields(
+            fmt::format()
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .with_span_list(false),
+        ))
+        .with_writer(RedactingWriter::new(writer))
+}
+
+/// Formats like the wrapped JSON format, then parses the `fields` string back
+/// into an object. tracing has no stable way to record a map as one value, so
+/// `CommandSpan` hands the fields over as JSON text.
+struct NestedFields<F>(F);
+
+impl<S, N, F> FormatEvent<S, N> for NestedFields<F>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    N: for<'writer> FormatFields<'writer> + 'static,
+    F: FormatEvent<S, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let mut line = String::new();
+        self.0.format_event(ctx, Writer::new(&mut line), event)?;
+
+        let Ok(mut object) = serde_json::from_str::<Map<String, Value>>(&line) else {
+            return writer.write_str(&line);
+        };
+        if let Some(Value::String(fields)) = object.get("fields")
+            && let Ok(nested @ Value::Object(_)) = serde_json::from_str(fields)
+        {
+            object.insert("fields".to_string(), nested);
+        }
+        writeln!(writer, "{}", Value::Object(object))
+    }
+}
+
+/// Wraps a writer so every formatted line passes through `redact`. The
+/// formatter hands over one whole line per write.
+pub struct RedactingWriter<M> {
+    inner: M,
+}
+
+impl<M> RedactingWriter<M> {
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'writer, M: MakeWriter<'writer>> MakeWriter<'writer> for RedactingWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'writer self) -> Self::Writer {
+        RedactingWriter::new(self.inner.make_writer())
+    }
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = String::from_utf8_lossy(buf);
+        self.inner.write_all(redact(&line).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn build_filter(level: LogLevel) -> EnvFilter {
+    std::env::var(LOG_FILTER_ENV)
+        .ok()
+        .and_then(|directives| EnvFilter::try_new(directives).ok())
+        .unwrap_or_else(|| EnvFilter::new(level.as_str()))
+}
@@ -0,0 +1,406 @@
+use crate::domain::{
+    AppError, CacheMetrics, CommandMetrics, HistogramBucket, MetricsSnapshot, ProviderCallMetrics,
+    ProviderErrorRecord, now_rfc3339,
+};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// Upper bounds of the command latency buckets, in milliseconds.
+const LATENCY_BUCKETS_MS: [u64; 11] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+const OK_CODE: &str = "ok";
+const METRIC_PREFIX: &str = "stocks";
+const MAX_RECENT_PROVIDER_ERRORS: usize = 50;
+
+static METRICS: MetricsRegistry = MetricsRegistry::new();
+
+/// The registry every `CommandSpan`, provider call and cache lookup reports to.
+pub fn metrics() -> &'static MetricsRegistry {
+    &METRICS
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CacheKind {
+    Quotes,
+    Timeseries,
+}
+
+impl CacheKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Quotes => "quotes",
+            Self::Timeseries => "timeseries",
+        }
+    }
+}
+
+/// Counters and latency histograms kept in memory for the life of the
+/// process. Nothing is persisted; `snapshot` copies the current values.
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    state: Mutex<MetricsState>,
+}
+
+#[derive(Debug, Default)]
+struct MetricsState {
+    commands: BTreeMap<&'static str, LatencyHistogram>,
+    provider_calls: BTreeMap<(&'static str, String), u64>,
+    caches: BTreeMap<CacheKind, (u64, u64)>,
+    bytes_fetched: u64,
+    recent_provider_errors: VecDeque<ProviderErrorRecord>,
+}
+
+#[derive(Debug, Default)]
+struct LatencyHistogram {
+    bucket_counts: [u64; LATENCY_BUCKETS_MS.len()],
+    count: u64,
+    error_count: u64,
+    total_ms: u64,
+}
+
+impl MetricsRegistry {
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(MetricsState {
+                commands: BTreeMap::new(),
+                provider_calls: BTreeMap::new(),
+                caches: BTreeMap::new(),
+                bytes_fetched: 0,
+                recent_provider_errors: VecDeque::new(),
+            }),
+        }
+    }
+
+    pub fn record_command(&self, command: &'static str, duration: Duration, failed: bool) {
+        let elapsed_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        let mut state = self.lock();
+        let histogram = state.commands.entry(command).or_default();
+        histogram.count += 1;
+        histogram.error_count += u64::from(failed);
+        histogram.total_ms = histogram.total_ms.saturating_add(elapsed_ms);
+        if let Some(bucket) = LATENCY_BUCKETS_MS.iter().position(|&le| elapsed_ms <= le) {
+            histogram.bucket_counts[bucket] += 1;
+        }
+    }
+
+    /// Counts the call by outcome code and keeps failures in a short log of
+    /// recent provider errors.
+    pub fn record_provider_call<T>(&self, endpoint: &'static str, result: &Result<T, AppError>) {
+        let mut state = self.lock();
+        let code = match result {
+            Ok(_) => OK_CODE.to_string(),
+            Err(err) => {
+                if state.recent_provider_errors.len() == MAX_RECENT_PROVIDER_ERRORS {
+                    state.recent_provider_errors.pop_front();
+                }
+                state.recent_provider_errors.push_back(ProviderErrorRecord {
+                    endpoint: endpoint.to_string(),
+                    code: err.code.clone(),
+                    occurred_at: now_rfc3339(),
+                });
+                err.code.clone()
+            }
+        };
+        *state.provider_calls.entry((endpoint, code)).or_default() += 1;
+    }
+
+    pub fn record_cache(&self, cache: CacheKind, hit: bool) {
+        let mut state = self.lock();
+        let (hits, misses) = state.caches.entry(cache).or_default();
+        if hit {
+            *hits += 1;
+        } else {
+            *misses += 1;
+        }
+    }
+
+    pub fn record_bytes_fetched(&self, bytes: u64) {
+        let mut state = self.lock();
+        state.bytes_fetched = state.bytes_fetched.saturating_add(bytes);
+    }
+
+    /// Provider failures since startup, oldest first.
+    pub fn recent_provider_errors(&self) -> Vec<ProviderErrorRecord> {
+        self.lock().recent_provider_errors.iter().cloned().collect()
+    }
+
+    pub fn snapshot(&self, collected_at: String) -> MetricsSnapshot {
+        let state = self.lock();
+        MetricsSnapshot {
+            collected_at,
+            commands: state
+                .commands
+                .iter()
+                .map(|(command, histogram)| CommandMetrics {
+                    command: command.to_string(),
+                    count: histogram.count,
+                    error_count: histogram.error_count,
+                    total_ms: histogram.total_ms,
+                    buckets: LATENCY_BUCKETS_MS
+                        .iter()
+                        .zip(histogram.bucket_counts.iter().scan(0, |total, count| {
+                            *total += count;
+                            Some(*total)
+                        }))
+                        .map(|(&le_ms, count)| HistogramBucket { le_ms, count })
+                        .collect(),
+                })
+                .collect(),
+            provider_calls: state
+                .provider_calls
+                .iter()
+                .map(|((endpoint, code), count)| ProviderCallMetrics {
+                    endpoint: endpoint.to_string(),
+                    code: code.clone(),
+                    count: *count,
+                })
+                .collect(),
+            caches: state
+                .caches
+                .iter()
+                .map(|(cache, &(hits, misses))| CacheMetrics {
+                    cache: cache.as_str().to_string(),
+                    hits,
+                    misses,
+                    hit_ratio: (hits + misses > 0).then(|| hits as f64 / (hits + misses) as f64),
+                })
+                .collect(),
+            bytes_fetched: state.bytes_fetched,
+            prometheus_file: None,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MetricsState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Renders `snapshot` in the Prometheus text exposition format.
+pub fn to_prometheus(snapshot: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+
+    let name = format!("{METRIC_PREFIX}_command_duration_milliseconds");
+    let _ = writeln!(out, "# HELP {name} Command latency in milliseconds.");
+    let _ = writeln!(out, "# TYPE {name} histogram");
+    for command in &snapshot.commands {
+        let label = escape_label(&command.command);
+        for bucket in &command.buckets {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{command=\"{label}\",le=\"{}\"}} {}",
+                bucket.le_ms, bucket.count
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{command=\"{label}\",le=\"+Inf\"}} {}",
+            command.count
+        );
+        let _ = writeln!(
+            out,
+            "{name}_sum{{command=\"{label}\"}} {}",
+            command.total_ms
+        );
+        let _ = writeln!(out, "{name}_count{{command=\"{label}\"}} {}", command.count);
+    }
+
+    let name = format!("{METRIC_PREFIX}_command_errors_total");
+    let _ = writeln!(out, "# HELP {name} Commands that returned an error.");
+    let _ = writeln!(out, "# TYPE {name} counter");
+    for command in &snapshot.commands {
+        let label = escape_label(&command.command);
+        let _ = writeln!(out, "{name}{{command=\"{label}\"}} {}", command.error_count);
+    }
+
+    let name = format!("{METRIC_PREFIX}_provider_calls_total");
+    let _ = writeln!(
+        out,
+        "# HELP {name} Provider calls by endpoint and outcome code."
+    );
+    let _ = writeln!(out, "# TYPE {name} counter");
+    for call in &snapshot.provider_calls {
+        let _ = writeln!(
+            out,
+            "{name}{{endpoint=\"{}\",code=\"{}\"}} {}",
+            escape_label(&call.endpoint),
+            escape_label(&call.code),
+            call.count
+        );
+    }
+
+    let name = format!("{METRIC_PREFIX}_cache_lookups_total");
+    let _ = writeln!(out, "# HELP {name} Cache lookups by cache and result.");
+    let _ = writeln!(out, "# TYPE {name} counter");
+    for cache in &snapshot.caches {
+        let label = escape_label(&cache.cache);
+        let _ = writeln!(
+            out,
+            "{name}{{cache=\"{label}\",result=\"hit\"}} {}",
+            cache.hits
+        );
+        let _ = writeln!(
+            out,
+            "{name}{{cache=\"{label}\",result=\"miss\"}} {}",
+            cache.misses
+        );
+    }
+
+    let name = format!("{METRIC_PREFIX}_provider_bytes_fetched_total");
+    let _ = writeln!(
+        out,
+        "# HELP {name} Response bytes received from the provider."
+    );
+    let _ = writeln!(out, "# TYPE {name} counter");
+    let _ = writeln!(out, "{name} {}", snapshot.bytes_fetched);
+
+    out
+}
+
+/// Writes the Prometheus text next to `path` and renames it into place, so a
+/// scraper never reads a half-written file.
+pub fn write_prometheus_file(path: &Path, snapshot: &MetricsSnapshot) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            AppError::persistence("metrics_dir_create_failed")
+                .with_arg("detail", err)
+                .with_arg("file", path.display())
+        })?;
+    }
+
+    let temp_path = path.with_extension("prom.tmp");
+    fs::write(&temp_path, to_prometheus(snapshot))
+        .and_then(|()| fs::rename(&temp_path, path))
+        .map_err(|err| {
+            AppError::persistence("metrics_write_failed")
+                .with_arg("detail", err)
+                .with_arg("file", path.display())
+        })
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_buckets_are_cumulative() {
+        let registry = MetricsRegistry::new();
+        registry.record_command("get_watchlist", Duration::from_millis(3), false);
+        registry.record_command("get_watchlist", Duration::from_millis(40), false);
+        registry.record_command("get_watchlist", Duration::from_millis(30_000), true);
+
+        let snapshot = registry.snapshot("now".to_string());
+        let command = &snapshot.commands[0];
+
+        assert_eq!(command.command, "get_watchlist");
+        assert_eq!(command.count, 3);
+        assert_eq!(command.error_count, 1);
+        assert_eq!(command.total_ms, 30_043);
+        let count_at = |le_ms| {
+            command
+                .buckets
+                .iter()
+                .find(|bucket| bucket.le_ms == le_ms)
+                .unwrap()
+                .count
+        };
+        assert_eq!(count_at(5), 1);
+        assert_eq!(count_at(25), 1);
+        assert_eq!(count_at(50), 2);
+        assert_eq!(count_at(10000), 2);
+    }
+
+    #[test]
+    fn provider_calls_are_counted_by_code_and_caches_by_result() {
+        let registry = MetricsRegistry::new();
+        registry.record_provider_call("quote", &Ok::<(), AppError>(()));
+        registry.record_provider_call("quote", &Ok::<(), AppError>(()));
+        registry.record_provider_call("quote", &Err::<(), _>(AppError::provider("rate_limited")));
+        registry.record_cache(CacheKind::Quotes, true);
+        registry.record_cache(CacheKind::Quotes, true);
+        registry.record_cache(CacheKind::Quotes, true);
+        registry.record_cache(CacheKind::Quotes, false);
+        registry.record_bytes_fetched(512);
+        registry.record_bytes_fetched(128);
+
+        let snapshot = registry.snapshot("now".to_string());
+
+        assert_eq!(
+            snapshot.provider_calls,
+            vec![
+                ProviderCallMetrics {
+                    endpoint: "quote".to_string(),
+                    code: "ok".to_string(),
+                    count: 2,
+                },
+                ProviderCallMetrics {
+                    endpoint: "quote".to_string(),
+                    code: "rate_limited".to_string(),
+                    count: 1,
+                },
+            ]
+        );
+        let errors = registry.recent_provider_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            (errors[0].endpoint.as_str(), errors[0].code.as_str()),
+            ("quote", "rate_limited")
+        );
+        assert_eq!(snapshot.caches[0].cache, "quotes");
+        assert_eq!(snapshot.caches[0].hit_ratio, Some(0.75));
+        assert_eq!(snapshot.bytes_fetched, 640);
+    }
+
+    #[test]
+    fn prometheus_text_lists_every_series() {
+        let registry = MetricsRegistry::new();
+        registry.record_command(
+            "refresh_watchlist_quotes",
+            Duration::from_millis(120),
+            false,
+        );
+        registry.record_provider_call("time_series", &Ok::<(), AppError>(()));
+        registry.record_cache(CacheKind::Timeseries, false);
+        registry.record_bytes_fetched(2048);
+
+        let text = to_prometheus(&registry.snapshot("now".to_string()));
+
+        for line in [
+            "# TYPE stocks_command_duration_milliseconds histogram",
+            r#"stocks_command_duration_milliseconds_bucket{command="refresh_watchlist_quotes",le="100"} 0"#,
+            r#"stocks_command_duration_milliseconds_bucket{command="refresh_watchlist_quotes",le="250"} 1"#,
+            r#"stocks_command_duration_milliseconds_bucket{command="refresh_watchlist_quotes",le="+Inf"} 1"#,
+            r#"stocks_command_duration_milliseconds_sum{command="refresh_watchlist_quotes"} 120"#,
+            r#"stocks_provider_calls_total{endpoint="time_series",code="ok"} 1"#,
+            r#"stocks_cache_lookups_total{cache="timeseries",result="miss"} 1"#,
+            "stocks_provider_bytes_fetched_total 2048",
+        ] {
+            assert!(
+                text.lines().any(|candidate| candidate == line),
+                "missing {line}"
+            );
+        }
+    }
+
+    #[test]
+    fn prometheus_file_is_written_in_place() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("metrics").join("stocks.prom");
+        let snapshot = MetricsRegistry::new().snapshot("now".to_string());
+
+        write_prometheus_file(&path, &snapshot).unwrap();
+
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.contains("stocks_provider_bytes_fetched_total 0"));
+        assert!(!path.with_extension("prom.tmp").exists());
+    }
+}
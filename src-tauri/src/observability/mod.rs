@@ -1,62 +1,171 @@
+mod logging;
+mod metrics;
+
 use crate::domain::AppError;
+use serde_json::{Map, Value};
 use std::time::Instant;
 
+pub use logging::{
+    LOG_FILE_PREFIX, LOG_FILE_SUFFIX, LOG_FILTER_ENV, LogController, RedactingWriter, init_logging,
+    init_logging_for_app, init_stderr_logging, json_layer,
+};
+pub use metrics::{CacheKind, MetricsRegistry, metrics, to_prometheus, write_prometheus_file};
+
+/// Logs the start and outcome of one command invocation. Every line carries
+/// the command name and a correlation id shared by all lines of the same
+/// invocation; the caller's extra fields are logged as an object under
+/// `fields`. The duration and outcome also go to the latency histogram in
+/// `metrics()`.
 pub struct CommandSpan {
     command: &'static str,
+    correlation_id: String,
+    span: tracing::Span,
     started: Instant,
 }
 
 impl CommandSpan {
     pub fn start(command: &'static str, fields: &[(&str, String)]) -> Self {
-        log_info("command_start", command, fields);
+        let correlation_id = uuid::Uuid::new_v4().simple().to_string();
+        let span = tracing::info_span!("command", command, correlation_id = %correlation_id);
+        tracing::info!(parent: &span, fields = %fields_json(fields), "command_start");
         Self {
             command,
+            correlation_id,
+            span,
             started: Instant::now(),
         }
     }
 
+    pub fn correlation_id(&self) -> &str {
+        &self.correlation_id
+    }
+
     pub fn ok(self, fields: &[(&str, String)]) {
-        let mut all_fields = vec![(
-            "duration_ms",
-            self.started.elapsed().as_millis().to_string(),
-        )];
-        all_fields.extend(fields.iter().cloned());
-        log_info("command_done", self.command, &all_fields);
+        let elapsed = self.started.elapsed();
+        metrics().record_command(self.command, elapsed, false);
+        tracing::info!(
+            parent: &self.span,
+            duration_ms = elapsed.as_millis() as u64,
+            fields = %fields_json(fields),
+            "command_done"
+        );
     }
 
     pub fn err(self, err: &AppError, fields: &[(&str, String)]) {
-        let mut all_fields = vec![
-            (
-                "duration_ms",
-                self.started.elapsed().as_millis().to_string(),
-            ),
-            ("error_code", err.code.clone()),
-            ("error_message", err.message.clone()),
-        ];
-        all_fields.extend(fields.iter().cloned());
-        log_warn("command_failed", self.command, &all_fields);
+        let elapsed = self.started.elapsed();
+        metrics().record_command(self.command, elapsed, true);
+        tracing::warn!(
+            parent: &self.span,
+            duration_ms = elapsed.as_millis() as u64,
+            error_code = %err.code,
+            error_message = %err.message,
+            fields = %fields_json(fields),
+            "command_failed"
+        );
     }
 }
 
-fn log_info(event: &str, command: &str, fields: &[(&str, String)]) {
-    println!("{}", format_log_line("info", event, command, fields));
+fn fields_json(fields: &[(&str, String)]) -> Value {
+    Value::Object(
+        fields
+            .iter()
+            .map(|(key, value)| (key.to_string(), Value::String(value.clone())))
+            .collect::<Map<String, Value>>(),
+    )
 }
 
-fn log_warn(event: &str, command: &str, fields: &[(&str, String)]) {
-    eprintln!("{}", format_log_line("warn", event, command, fields));
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
 
-fn format_log_line(level: &str, event: &str, command: &str, fields: &[(&str, String)]) -> String {
-    let mut line = format!("level={level} event={event} command={command}");
-    for (key, value) in fields {
-        line.push(' ');
-        line.push_str(key);
-        line.push('=');
-        line.push_str(&sanitize(value));
+    impl io::Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
     }
-    line
-}
 
-fn sanitize(input: &str) -> String {
-    input.replace(['\n', '\r', '\t'], " ").replace(' ', "_")
+    fn capture(run: impl FnOnce()) -> Vec<Value> {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(json_layer(move || writer.clone()));
+        tracing::subscriber::with_default(subscriber, run);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn command_lines_share_a_correlation_id() {
+        let mut correlation_id = String::new();
+        let lines = capture(|| {
+            let span = CommandSpan::start("add_symbol", &[("symbol", "BRK.B".to_string())]);
+            correlation_id = span.correlation_id().to_string();
+            span.ok(&[("note", "kept as is".to_string())]);
+        });
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["message"], "command_start");
+        assert_eq!(lines[0]["level"], "INFO");
+        assert_eq!(lines[1]["message"], "command_done");
+        for line in &lines {
+            assert_eq!(line["span"]["command"], "add_symbol");
+            assert_eq!(line["span"]["correlation_id"], correlation_id.as_str());
+        }
+        assert!(lines[1]["duration_ms"].is_u64());
+        assert_eq!(lines[0]["fields"]["symbol"], "BRK.B");
+        assert_eq!(lines[1]["fields"]["note"], "kept as is");
+    }
+
+    #[test]
+    fn failures_are_logged_as_warnings_with_the_error() {
+        let lines = capture(|| {
+            let err = AppError::validation("invalid_symbol").with_reason("empty");
+            CommandSpan::start("add_symbol", &[]).err(&err, &[]);
+        });
+
+        assert_eq!(lines[1]["level"], "WARN");
+        assert_eq!(lines[1]["message"], "command_failed");
+        assert_eq!(lines[1]["error_code"], "invalid_symbol");
+        assert_eq!(lines[1]["error_message"], "Symbol cannot be empty.");
+    }
+
+    #[test]
+    fn secrets_never_reach_the_log() {
+        let api_key = "td-9c1e77f0b2a4";
+        crate::domain::register_secret(api_key);
+        let lines = capture(|| {
+            let span = CommandSpan::start("refresh_watchlist_quotes", &[]);
+            tracing::info!(url = %format!("https://api.twelvedata.com/quote?apikey={api_key}"));
+            span.err(
+                &AppError {
+                    message: format!("Provider request failed: token={api_key}"),
+                    ..AppError::provider("network_error")
+                },
+                &[("raw", format!("x-api-key {api_key}"))],
+            );
+        });
+
+        assert_eq!(lines.len(), 3);
+        for line in &lines {
+            assert!(!line.to_string().contains(api_key), "{line} leaks the key");
+        }
+        assert_eq!(
+            lines[1]["url"],
+            "https://api.twelvedata.com/quote?apikey=[REDACTED]"
+        );
+    }
 }
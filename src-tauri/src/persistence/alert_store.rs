@@ -0,0 +1,107 @@
+use super::json_list::{append_capped, read_list, write_list};
+use crate::domain::{AlertEvent, AlertRule, AppError};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+const ALERT_RULES_FILE_NAME: &str = "alerts.json";
+const ALERT_HISTORY_FILE_NAME: &str = "alert_history.json";
+const MAX_ALERT_HISTORY: usize = 200;
+const STORE: &str = "alerts";
+
+/// Alert rules plus a capped history of triggered alerts, newest last.
+#[derive(Debug, Clone)]
+pub struct AlertStore {
+    rules_path: PathBuf,
+    history_path: PathBuf,
+}
+
+impl AlertStore {
+    pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
+        let base_dir = app.path().app_config_dir().map_err(|err| {
+            AppError::persistence("alerts_path_resolution_failed").with_arg("detail", err)
+        })?;
+
+        Ok(Self::new(base_dir))
+    }
+
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self {
+            rules_path: base_dir.join(ALERT_RULES_FILE_NAME),
+            history_path: base_dir.join(ALERT_HISTORY_FILE_NAME),
+        }
+    }
+
+    pub fn load_rules(&self) -> Result<Vec<AlertRule>, AppError> {
+        read_list(&self.rules_path, STORE)
+    }
+
+    pub fn save_rules(&self, rules: &[AlertRule]) -> Result<Vec<AlertRule>, AppError> {
+        write_list(&self.rules_path, rules, STORE)?;
+        Ok(rules.to_vec())
+    }
+
+    pub fn load_history(&self) -> Result<Vec<AlertEvent>, AppError> {
+        read_list(&self.history_path, STORE)
+    }
+
+    /// Appends `events` and drops the oldest entries beyond the history cap.
+    pub fn append_history(&self, events: &[AlertEvent]) -> Result<Vec<AlertEvent>, AppError> {
+        append_capped(&self.history_path, events, MAX_ALERT_HISTORY, STORE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::AlertCondition;
+    use tempfile::tempdir;
+
+    fn event(index: usize) -> AlertEvent {
+        AlertEvent {
+            id: format!("event-{index}"),
+            rule_id: "rule-1".to_string(),
+            symbol: "AAPL".to_string(),
+            condition: AlertCondition::PriceAbove { price: 200.0 },
+            price: 201.0,
+            reference_value: None,
+            message: "AAPL rose above 200.00 (now 201.00).".to_string(),
+            triggered_at: "2026-02-22T15:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn save_and_load_roundtrip_rules() {
+        let temp = tempdir().unwrap();
+        let store = AlertStore::new(temp.path().to_path_buf());
+        assert!(store.load_rules().unwrap().is_empty());
+
+        let rules = vec![AlertRule {
+            id: "rule-1".to_string(),
+            symbol: "AAPL".to_string(),
+            condition: AlertCondition::CrossesBelowMovingAverage { period: 50 },
+            enabled: true,
+            notify: false,
+            cooldown_seconds: 900,
+            created_at: "2026-02-22T12:00:00Z".to_string(),
+            armed: false,
+            last_triggered_at: None,
+        }];
+        store.save_rules(&rules).unwrap();
+
+        assert_eq!(store.load_rules().unwrap(), rules);
+    }
+
+    #[test]
+    fn append_history_keeps_the_newest_entries() {
+        let temp = tempdir().unwrap();
+        let store = AlertStore::new(temp.path().to_path_buf());
+
+        let events: Vec<AlertEvent> = (0..MAX_ALERT_HISTORY + 5).map(event).collect();
+        store.append_history(&events[..10]).unwrap();
+        let history = store.append_history(&events[10..]).unwrap();
+
+        assert_eq!(history.len(), MAX_ALERT_HISTORY);
+        assert_eq!(history[0].id, "event-5");
+        assert_eq!(store.load_history().unwrap(), history);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::domain::{CacheEntryInfo, CacheStoreStats, unix_secs_to_rfc3339};
+
+/// Builds the stats block for one cache from `(key, cached_at)` pairs.
+pub fn summarize_cache_entries<'a>(
+    entry_count: usize,
+    bytes_on_disk: u64,
+    entries: impl Iterator<Item = (&'a str, u64)>,
+) -> CacheStoreStats {
+    let mut oldest: Option<(&str, u64)> = None;
+    let mut newest: Option<(&str, u64)> = None;
+
+    for (key, cached_at) in entries {
+        if oldest.is_none_or(|(_, oldest_at)| cached_at < oldest_at) {
+            oldest = Some((key, cached_at));
+        }
+        if newest.is_none_or(|(_, newest_at)| cached_at > newest_at) {
+            newest = Some((key, cached_at));
+        }
+    }
+
+    CacheStoreStats {
+        entry_count,
+        bytes_on_disk,
+        oldest_entry: oldest.map(to_entry_info),
+        newest_entry: newest.map(to_entry_info),
+    }
+}
+
+fn to_entry_info((key, cached_at): (&str, u64)) -> CacheEntryInfo {
+    CacheEntryInfo {
+        key: key.to_string(),
+        cached_at: i64::try_from(cached_at)
+            .ok()
+            .and_then(unix_secs_to_rfc3339)
+            .unwrap_or_default(),
+    }
+}
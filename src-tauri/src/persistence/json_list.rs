@@ -0,0 +1,115 @@
+//! JSON array files shared by the alert and webhook stores. `store` prefixes
+//! the error codes, so a failed alerts read reports `alerts_read_failed`.
+
+use crate::domain::AppError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::Path;
+
+/// Reads the list at `path`; a missing or empty file is an empty list.
+pub(super) fn read_list<T: DeserializeOwned>(path: &Path, store: &str) -> Result<Vec<T>, AppError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path).map_err(|err| {
+        AppError::persistence(&format!("{store}_read_failed"))
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
+    })?;
+
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    serde_json::from_str::<Vec<T>>(&content).map_err(|err| {
+        AppError::persistence(&format!("{store}_parse_failed"))
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
+    })
+}
+
+pub(super) fn write_list<T: Serialize>(
+    path: &Path,
+    items: &[T],
+    store: &str,
+) -> Result<(), AppError> {
+    ensure_parent_dir(path, store)?;
+    let payload = serde_json::to_string_pretty(items).map_err(|err| {
+        AppError::persistence(&format!("{store}_serialize_failed"))
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
+    })?;
+
+    fs::write(path, payload).map_err(|err| {
+        AppError::persistence(&format!("{store}_write_failed"))
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
+    })
+}
+
+/// Appends `items` and drops the oldest entries beyond `cap`. Returns the
+/// saved list.
+pub(super) fn append_capped<T: Serialize + DeserializeOwned + Clone>(
+    path: &Path,
+    items: &[T],
+    cap: usize,
+    store: &str,
+) -> Result<Vec<T>, AppError> {
+    let mut list: Vec<T> = read_list(path, store)?;
+    list.extend_from_slice(items);
+    if list.len() > cap {
+        list.drain(..list.len() - cap);
+    }
+
+    write_list(path, &list, store)?;
+    Ok(list)
+}
+
+fn ensure_parent_dir(path: &Path, store: &str) -> Result<(), AppError> {
+    let parent = path.parent().ok_or_else(|| {
+        AppError::persistence(&format!("{store}_parent_dir_missing"))
+            .with_arg("file", path.display())
+    })?;
+
+    fs::create_dir_all(parent).map_err(|err| {
+        AppError::persistence(&format!("{store}_dir_create_failed"))
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn missing_and_empty_files_read_as_empty_lists() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join("items.json");
+        assert!(read_list::<u32>(&path, "items").unwrap().is_empty());
+
+        fs::write(&path, "  \n").unwrap();
+        assert!(read_list::<u32>(&path, "items").unwrap().is_empty());
+
+        fs::write(&path, "{").unwrap();
+        assert_eq!(
+            read_list::<u32>(&path, "items").unwrap_err().code,
+            "items_parse_failed"
+        );
+    }
+
+    #[test]
+    fn append_capped_keeps_the_newest_items() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join("nested").join("items.json");
+
+        append_capped(&path, &[1, 2, 3], 4, "items").unwrap();
+        let saved = append_capped(&path, &[4, 5], 4, "items").unwrap();
+
+        assert_eq!(saved, vec![2, 3, 4, 5]);
+        assert_eq!(read_list::<u32>(&path, "items").unwrap(), saved);
+    }
+}
@@ -0,0 +1,211 @@
+use super::LedgerRepository;
+use crate::domain::{AppError, Lot, Transaction, TransactionKind};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+const LEDGER_FILE_NAME: &str = "transactions.json";
+const LEGACY_PORTFOLIO_FILE_NAME: &str = "portfolio.json";
+
+/// Transaction ledger. Portfolios saved as plain lots before the ledger
+/// existed are read back as buy transactions until the first write.
+#[derive(Debug, Clone)]
+pub struct LedgerStore {
+    file_path: PathBuf,
+    legacy_portfolio_path: PathBuf,
+}
+
+impl LedgerStore {
+    pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
+        let base_dir = app.path().app_config_dir().map_err(|err| {
+            AppError::persistence("ledger_path_resolution_failed").with_arg("detail", err)
+        })?;
+
+        Ok(Self::new(base_dir))
+    }
+
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self {
+            file_path: base_dir.join(LEDGER_FILE_NAME),
+            legacy_portfolio_path: base_dir.join(LEGACY_PORTFOLIO_FILE_NAME),
+        }
+    }
+
+    pub fn load(&self) -> Result<Vec<Transaction>, AppError> {
+        if !self.file_path.exists() {
+            return self.load_legacy_portfolio();
+        }
+
+        let content = read_file(&self.file_path)?;
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        serde_json::from_str::<Vec<Transaction>>(&content).map_err(|err| {
+            AppError::persistence("ledger_parse_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
+        })
+    }
+
+    pub fn append(&self, transaction: Transaction) -> Result<Vec<Transaction>, AppError> {
+        let mut transactions = self.load()?;
+        transactions.push(transaction);
+        self.save(&transactions)
+    }
+
+    /// Replaces the whole ledger, for corrections. Callers replay it first.
+    pub fn save(&self, transactions: &[Transaction]) -> Result<Vec<Transaction>, AppError> {
+        ensure_parent_dir(&self.file_path)?;
+        let payload = serde_json::to_string_pretty(transactions).map_err(|err| {
+            AppError::persistence("ledger_serialize_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
+        })?;
+
+        fs::write(&self.file_path, payload).map_err(|err| {
+            AppError::persistence("ledger_write_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
+        })?;
+
+        Ok(transactions.to_vec())
+    }
+
+    fn load_legacy_portfolio(&self) -> Result<Vec<Transaction>, AppError> {
+        if !self.legacy_portfolio_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = read_file(&self.legacy_portfolio_path)?;
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let lots = serde_json::from_str::<Vec<Lot>>(&content).map_err(|err| {
+            AppError::persistence("ledger_parse_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.legacy_portfolio_path.display())
+        })?;
+
+        Ok(lots
+            .into_iter()
+            .map(|lot| Transaction {
+                recorded_at: format!("{}T00:00:00Z", lot.purchased_on),
+                id: lot.id,
+                symbol: lot.symbol,
+                kind: TransactionKind::Buy,
+                date: lot.purchased_on,
+                quantity: lot.quantity,
+                price: lot.price,
+                fees: lot.fees,
+            })
+            .collect())
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, AppError> {
+    fs::read_to_string(path).map_err(|err| {
+        AppError::persistence("ledger_read_failed")
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
+    })
+}
+
+fn ensure_parent_dir(path: &Path) -> Result<(), AppError> {
+    let parent = path.parent().ok_or_else(|| {
+        AppError::persistence("ledger_parent_dir_missing").with_arg("file", path.display())
+    })?;
+
+    fs::create_dir_all(parent).map_err(|err| {
+        AppError::persistence("ledger_dir_create_failed")
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
+    })
+}
+
+impl LedgerRepository for LedgerStore {
+    fn load(&self) -> Result<Vec<Transaction>, AppError> {
+        LedgerStore::load(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn buy(id: &str) -> Transaction {
+        Transaction {
+            id: id.to_string(),
+            symbol: "AAPL".to_string(),
+            kind: TransactionKind::Buy,
+            date: "2026-01-05".to_string(),
+            quantity: 10.0,
+            price: 150.0,
+            fees: 1.5,
+            recorded_at: "2026-01-05T15:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn load_returns_empty_when_file_is_missing() {
+        let temp = tempdir().unwrap();
+        let store = LedgerStore::new(temp.path().to_path_buf());
+
+        assert!(store.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn append_keeps_earlier_transactions() {
+        let temp = tempdir().unwrap();
+        let store = LedgerStore::new(temp.path().to_path_buf());
+
+        store.append(buy("tx-1")).unwrap();
+        let transactions = store.append(buy("tx-2")).unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(store.load().unwrap(), transactions);
+    }
+
+    #[test]
+    fn save_replaces_the_ledger() {
+        let temp = tempdir().unwrap();
+        let store = LedgerStore::new(temp.path().to_path_buf());
+        store.append(buy("tx-1")).unwrap();
+        store.append(buy("tx-2")).unwrap();
+
+        let saved = store.save(&[buy("tx-2")]).unwrap();
+
+        assert_eq!(saved, vec![buy("tx-2")]);
+        assert_eq!(store.load().unwrap(), saved);
+    }
+
+    #[test]
+    fn legacy_lots_are_read_as_buys() {
+        let temp = tempdir().unwrap();
+        let lots = vec![Lot {
+            id: "lot-1".to_string(),
+            symbol: "AAPL".to_string(),
+            quantity: 10.0,
+            price: 150.0,
+            purchased_on: "2026-01-05".to_string(),
+            fees: 1.5,
+        }];
+        fs::write(
+            temp.path().join(LEGACY_PORTFOLIO_FILE_NAME),
+            serde_json::to_string(&lots).unwrap(),
+        )
+        .unwrap();
+
+        let store = LedgerStore::new(temp.path().to_path_buf());
+        let transactions = store.load().unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].id, "lot-1");
+        assert_eq!(transactions[0].kind, TransactionKind::Buy);
+
+        let appended = store.append(buy("tx-2")).unwrap();
+        assert_eq!(appended.len(), 2);
+        assert_eq!(store.load().unwrap().len(), 2);
+    }
+}
@@ -1,14 +1,108 @@
+mod alert_store;
+mod cache_stats;
+mod json_list;
+mod ledger_store;
 mod quote_cache_store;
 mod settings_store;
 mod timeseries_cache_store;
 mod watchlist_store;
+mod webhook_store;
 
+pub use alert_store::AlertStore;
+pub use ledger_store::LedgerStore;
 pub use quote_cache_store::{
     CachedQuoteEntry, QuoteCacheMap, QuoteCacheStore, is_cache_fresh, to_cached_entry,
     to_stale_quote,
 };
 pub use settings_store::SettingsStore;
 pub use timeseries_cache_store::{
-    CachedTimeSeriesEntry, TimeSeriesCacheStore, is_timeseries_cache_fresh, to_stale_performance,
+    CacheLimits, CachedTimeSeriesEntry, TimeSeriesCacheFile, TimeSeriesCacheStore, covers_range,
+    is_timeseries_cache_fresh, merge_series, missing_bar_count, to_range_performance,
+    to_stale_performance,
 };
 pub use watchlist_store::WatchlistStore;
+pub use webhook_store::{DeliveryLogLock, WebhookStore};
+
+use crate::domain::{
+    AppError, AppSettings, AppSettingsInput, SeriesInterval, Transaction, WatchlistItem,
+};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// Bundle identifier from `tauri.conf.json`. The app config dir is named
+/// after it, which is how the CLI finds the desktop app's stores.
+pub const APP_IDENTIFIER: &str = "com.suman.tauri-appy";
+/// Overrides the config dir for the CLI, e.g. to point a cron job at a
+/// separate watchlist.
+pub const CONFIG_DIR_ENV: &str = "STOCKS_CONFIG_DIR";
+
+/// Directory all stores live in, as resolved by the running app.
+pub fn config_dir_from_app(app: &AppHandle) -> Result<PathBuf, AppError> {
+    app.path().app_config_dir().map_err(|err| {
+        AppError::persistence("config_path_resolution_failed").with_arg("detail", err)
+    })
+}
+
+/// The same directory without a running app: `CONFIG_DIR_ENV` if set,
+/// otherwise the platform config dir joined with `APP_IDENTIFIER`, matching
+/// what Tauri resolves for the desktop app.
+pub fn default_config_dir() -> Result<PathBuf, AppError> {
+    if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    dirs::config_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| {
+            AppError::persistence("config_path_resolution_failed")
+                .with_arg("detail", "no config directory on this platform")
+        })
+}
+
+/// Settings storage the services depend on, implemented by `SettingsStore`.
+pub trait SettingsRepository: Send + Sync {
+    fn load(&self) -> Result<AppSettings, AppError>;
+    fn save(&self, input: AppSettingsInput) -> Result<AppSettings, AppError>;
+}
+
+/// Watchlist storage the services depend on, implemented by `WatchlistStore`.
+pub trait WatchlistRepository: Send + Sync {
+    fn load(&self) -> Result<Vec<WatchlistItem>, AppError>;
+    fn save(&self, watchlist: &[WatchlistItem]) -> Result<Vec<WatchlistItem>, AppError>;
+}
+
+/// Read access to the transaction ledger, implemented by `LedgerStore`.
+pub trait LedgerRepository: Send + Sync {
+    fn load(&self) -> Result<Vec<Transaction>, AppError>;
+}
+
+/// Quote cache storage, implemented by `QuoteCacheStore`.
+pub trait QuoteCacheRepository: Send + Sync {
+    fn load(&self) -> Result<QuoteCacheMap, AppError>;
+    fn save(&self, cache: &QuoteCacheMap) -> Result<(), AppError>;
+    /// Returns the removed symbols.
+    fn remove_where(
+        &self,
+        should_remove: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<Vec<String>, AppError>;
+    fn clear(&self) -> Result<(), AppError>;
+}
+
+/// Timeseries cache storage, implemented by `TimeSeriesCacheStore`.
+pub trait TimeSeriesRepository: Send + Sync {
+    fn load(
+        &self,
+        symbol: &str,
+        interval: SeriesInterval,
+    ) -> Result<Option<CachedTimeSeriesEntry>, AppError>;
+    fn save(&self, entry: &CachedTimeSeriesEntry) -> Result<(), AppError>;
+    fn mark_accessed(&self, entry: &CachedTimeSeriesEntry, now: u64) -> Result<(), AppError>;
+    /// Returns the keys of the evicted entries.
+    fn evict(&self, limits: CacheLimits) -> Result<Vec<String>, AppError>;
+    /// Returns the keys of the removed entries.
+    fn remove_where(
+        &self,
+        should_remove: &mut dyn FnMut(&TimeSeriesCacheFile) -> bool,
+    ) -> Result<Vec<String>, AppError>;
+    fn clear(&self) -> Result<(), AppError>;
+}
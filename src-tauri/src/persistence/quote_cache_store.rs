@@ -1,4 +1,6 @@
-use crate::domain::{AppError, QuoteStatus, QuoteSummary, unix_timestamp_secs};
+use super::QuoteCacheRepository;
+use super::cache_stats::summarize_cache_entries;
+use crate::domain::{AppError, CacheStoreStats, QuoteStatus, QuoteSummary};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -25,10 +27,7 @@ pub struct QuoteCacheStore {
 impl QuoteCacheStore {
     pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
         let base_dir = app.path().app_config_dir().map_err(|err| {
-            AppError::persistence(
-                "quote_cache_path_resolution_failed",
-                format!("Unable to resolve quote cache directory: {err}"),
-            )
+            AppError::persistence("quote_cache_path_resolution_failed").with_arg("detail", err)
         })?;
 
         Ok(Self::new(base_dir))
@@ -46,10 +45,9 @@ impl QuoteCacheStore {
         }
 
         let content = fs::read_to_string(&self.file_path).map_err(|err| {
-            AppError::persistence(
-                "quote_cache_read_failed",
-                format!("Unable to read quote cache from disk: {err}"),
-            )
+            AppError::persistence("quote_cache_read_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })?;
 
         if content.trim().is_empty() {
@@ -57,10 +55,9 @@ impl QuoteCacheStore {
         }
 
         serde_json::from_str::<QuoteCacheMap>(&content).map_err(|err| {
-            AppError::persistence(
-                "quote_cache_parse_failed",
-                format!("Unable to parse quote cache file: {err}"),
-            )
+            AppError::persistence("quote_cache_parse_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })
     }
 
@@ -68,20 +65,70 @@ impl QuoteCacheStore {
         ensure_parent_dir(&self.file_path)?;
 
         let payload = serde_json::to_string_pretty(cache).map_err(|err| {
-            AppError::persistence(
-                "quote_cache_serialize_failed",
-                format!("Unable to serialize quote cache: {err}"),
-            )
+            AppError::persistence("quote_cache_serialize_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })?;
 
         fs::write(&self.file_path, payload).map_err(|err| {
-            AppError::persistence(
-                "quote_cache_write_failed",
-                format!("Unable to write quote cache to disk: {err}"),
-            )
+            AppError::persistence("quote_cache_write_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })
     }
 
+    /// Removes every cached quote whose symbol matches `should_remove` and
+    /// returns the removed symbols in sorted order.
+    pub fn remove_where(
+        &self,
+        mut should_remove: impl FnMut(&str) -> bool,
+    ) -> Result<Vec<String>, AppError> {
+        let mut cache = self.load()?;
+        let mut removed: Vec<String> = cache
+            .keys()
+            .filter(|symbol| should_remove(symbol))
+            .cloned()
+            .collect();
+
+        if removed.is_empty() {
+            return Ok(removed);
+        }
+
+        for symbol in &removed {
+            cache.remove(symbol);
+        }
+        self.save(&cache)?;
+        removed.sort();
+        Ok(removed)
+    }
+
+    /// Deletes the cache file, even when it no longer parses.
+    pub fn clear(&self) -> Result<(), AppError> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        fs::remove_file(&self.file_path).map_err(|err| {
+            AppError::persistence("cache_clear_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
+        })
+    }
+
+    pub fn stats(&self) -> Result<CacheStoreStats, AppError> {
+        let cache = self.load()?;
+        let bytes_on_disk = fs::metadata(&self.file_path)
+            .map(|meta| meta.len())
+            .unwrap_or(0);
+        Ok(summarize_cache_entries(
+            cache.len(),
+            bytes_on_disk,
+            cache
+                .iter()
+                .map(|(symbol, entry)| (symbol.as_str(), entry.cached_at)),
+        ))
+    }
+
     pub fn ttl_seconds() -> u64 {
         QUOTE_CACHE_TTL_SECONDS
     }
@@ -99,29 +146,46 @@ pub fn to_stale_quote(entry: &CachedQuoteEntry, error: &AppError) -> QuoteSummar
     quote
 }
 
-pub fn to_cached_entry(quote: QuoteSummary) -> CachedQuoteEntry {
+pub fn to_cached_entry(quote: QuoteSummary, now: u64) -> CachedQuoteEntry {
     CachedQuoteEntry {
         quote,
-        cached_at: unix_timestamp_secs(),
+        cached_at: now,
     }
 }
 
 fn ensure_parent_dir(path: &Path) -> Result<(), AppError> {
     let parent = path.parent().ok_or_else(|| {
-        AppError::persistence(
-            "quote_cache_parent_dir_missing",
-            "Quote cache path has no parent directory.",
-        )
+        AppError::persistence("quote_cache_parent_dir_missing").with_arg("file", path.display())
     })?;
 
     fs::create_dir_all(parent).map_err(|err| {
-        AppError::persistence(
-            "quote_cache_dir_create_failed",
-            format!("Unable to create quote cache directory: {err}"),
-        )
+        AppError::persistence("quote_cache_dir_create_failed")
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
     })
 }
 
+impl QuoteCacheRepository for QuoteCacheStore {
+    fn load(&self) -> Result<QuoteCacheMap, AppError> {
+        QuoteCacheStore::load(self)
+    }
+
+    fn save(&self, cache: &QuoteCacheMap) -> Result<(), AppError> {
+        QuoteCacheStore::save(self, cache)
+    }
+
+    fn remove_where(
+        &self,
+        should_remove: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<Vec<String>, AppError> {
+        QuoteCacheStore::remove_where(self, should_remove)
+    }
+
+    fn clear(&self) -> Result<(), AppError> {
+        QuoteCacheStore::clear(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,7 +198,8 @@ mod tests {
             change_abs: Some(1.0),
             change_pct: Some(0.8),
             currency: Some("USD".to_string()),
-            last_updated_at: "1730000000".to_string(),
+            last_updated_at: "2024-10-27T03:33:20Z".to_string(),
+            exchange_timezone: None,
             status: QuoteStatus::Fresh,
             error_code: None,
             error_message: None,
@@ -160,6 +225,54 @@ mod tests {
         assert!(loaded.contains_key("AAPL"));
     }
 
+    #[test]
+    fn stats_report_oldest_and_newest_quotes() {
+        let temp = tempdir().unwrap();
+        let store = QuoteCacheStore::new(temp.path().to_path_buf());
+        let mut cache = QuoteCacheMap::new();
+        for (symbol, cached_at) in [("AAPL", 1_771_754_400), ("MSFT", 1_771_750_800)] {
+            cache.insert(
+                symbol.to_string(),
+                CachedQuoteEntry {
+                    quote: sample_quote(),
+                    cached_at,
+                },
+            );
+        }
+        store.save(&cache).unwrap();
+
+        let stats = store.stats().unwrap();
+        assert_eq!(stats.entry_count, 2);
+        assert!(stats.bytes_on_disk > 0);
+        assert_eq!(stats.oldest_entry.unwrap().key, "MSFT");
+        assert_eq!(
+            stats.newest_entry.unwrap().cached_at,
+            "2026-02-22T10:00:00Z"
+        );
+    }
+
+    #[test]
+    fn remove_where_drops_matching_symbols() {
+        let temp = tempdir().unwrap();
+        let store = QuoteCacheStore::new(temp.path().to_path_buf());
+        let mut cache = QuoteCacheMap::new();
+        for symbol in ["AAPL", "MSFT", "NVDA"] {
+            cache.insert(
+                symbol.to_string(),
+                CachedQuoteEntry {
+                    quote: sample_quote(),
+                    cached_at: 100,
+                },
+            );
+        }
+        store.save(&cache).unwrap();
+
+        let removed = store.remove_where(|symbol| symbol != "MSFT").unwrap();
+        assert_eq!(removed, vec!["AAPL".to_string(), "NVDA".to_string()]);
+        assert_eq!(store.load().unwrap().len(), 1);
+        assert!(store.remove_where(|_| false).unwrap().is_empty());
+    }
+
     #[test]
     fn ttl_logic_marks_only_recent_entries_as_fresh() {
         assert!(is_cache_fresh(100, 130));
@@ -1,4 +1,5 @@
-use crate::domain::{AppError, AppSettings, AppSettingsInput, validate_settings};
+use super::SettingsRepository;
+use crate::domain::{AppError, AppSettings, AppSettingsInput, register_secret, validate_settings};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager};
@@ -13,10 +14,7 @@ pub struct SettingsStore {
 impl SettingsStore {
     pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
         let base_dir = app.path().app_config_dir().map_err(|err| {
-            AppError::persistence(
-                "settings_path_resolution_failed",
-                format!("Unable to resolve settings directory: {err}"),
-            )
+            AppError::persistence("settings_path_resolution_failed").with_arg("detail", err)
         })?;
 
         Ok(Self::new(base_dir))
@@ -34,22 +32,22 @@ impl SettingsStore {
         }
 
         let content = fs::read_to_string(&self.file_path).map_err(|err| {
-            AppError::persistence(
-                "settings_read_failed",
-                format!("Unable to read settings from disk: {err}"),
-            )
+            AppError::persistence("settings_read_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })?;
 
         if content.trim().is_empty() {
             return Ok(AppSettings::default());
         }
 
-        serde_json::from_str::<AppSettings>(&content).map_err(|err| {
-            AppError::persistence(
-                "settings_parse_failed",
-                format!("Unable to parse settings file: {err}"),
-            )
-        })
+        let settings = serde_json::from_str::<AppSettings>(&content).map_err(|err| {
+            AppError::persistence("settings_parse_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
+        })?;
+        register_secret(&settings.api_key);
+        Ok(settings)
     }
 
     pub fn save(&self, input: AppSettingsInput) -> Result<AppSettings, AppError> {
@@ -58,44 +56,59 @@ impl SettingsStore {
         Ok(validated_settings)
     }
 
+    pub fn set_offline_mode(&self, enabled: bool) -> Result<AppSettings, AppError> {
+        let mut settings = self.load()?;
+        settings.offline_mode = enabled;
+        self.persist(&settings)?;
+        Ok(settings)
+    }
+
     fn persist(&self, settings: &AppSettings) -> Result<(), AppError> {
+        register_secret(&settings.api_key);
         ensure_parent_dir(&self.file_path)?;
         let payload = serde_json::to_string_pretty(settings).map_err(|err| {
-            AppError::persistence(
-                "settings_serialize_failed",
-                format!("Unable to serialize settings: {err}"),
-            )
+            AppError::persistence("settings_serialize_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })?;
 
         fs::write(&self.file_path, payload).map_err(|err| {
-            AppError::persistence(
-                "settings_write_failed",
-                format!("Unable to write settings to disk: {err}"),
-            )
+            AppError::persistence("settings_write_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })
     }
 }
 
 fn ensure_parent_dir(path: &Path) -> Result<(), AppError> {
     let parent = path.parent().ok_or_else(|| {
-        AppError::persistence(
-            "settings_parent_dir_missing",
-            "Settings path has no parent directory.",
-        )
+        AppError::persistence("settings_parent_dir_missing").with_arg("file", path.display())
     })?;
 
     fs::create_dir_all(parent).map_err(|err| {
-        AppError::persistence(
-            "settings_dir_create_failed",
-            format!("Unable to create settings directory: {err}"),
-        )
+        AppError::persistence("settings_dir_create_failed")
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
     })
 }
 
+impl SettingsRepository for SettingsStore {
+    fn load(&self) -> Result<AppSettings, AppError> {
+        SettingsStore::load(self)
+    }
+
+    fn save(&self, input: AppSettingsInput) -> Result<AppSettings, AppError> {
+        SettingsStore::save(self, input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{AppProvider, TimeRange};
+    use crate::domain::{
+        AppProvider, DEFAULT_CACHE_MAX_ENTRIES, DEFAULT_CACHE_MAX_MEGABYTES, Locale, LogLevel,
+        LotMatching, TimeRange,
+    };
     use tempfile::tempdir;
 
     fn sample_input(api_key: &str, refresh_seconds: u32) -> AppSettingsInput {
@@ -105,6 +118,14 @@ mod tests {
             default_range: TimeRange::OneWeek,
             auto_refresh_seconds: refresh_seconds,
             notifications_enabled: true,
+            cache_max_entries: DEFAULT_CACHE_MAX_ENTRIES,
+            cache_max_megabytes: DEFAULT_CACHE_MAX_MEGABYTES,
+            offline_mode: false,
+            lot_matching: LotMatching::Fifo,
+            risk_free_rate_pct: 0.0,
+            quiet_hours: None,
+            log_level: LogLevel::Info,
+            locale: Locale::En,
         }
     }
 
@@ -129,6 +150,31 @@ mod tests {
         assert_eq!(loaded.api_key, "valid-key-123");
     }
 
+    #[test]
+    fn load_fills_cache_limits_missing_from_older_settings_files() {
+        let temp = tempdir().unwrap();
+        let store = SettingsStore::new(temp.path().to_path_buf());
+        fs::write(
+            temp.path().join(SETTINGS_FILE_NAME),
+            r#"{"provider":"twelvedata","apiKey":"valid-key-123","defaultRange":"1M","autoRefreshSeconds":60,"notificationsEnabled":false}"#,
+        )
+        .unwrap();
+
+        let settings = store.load().unwrap();
+        assert_eq!(settings.cache_max_entries, DEFAULT_CACHE_MAX_ENTRIES);
+        assert_eq!(settings.cache_max_megabytes, DEFAULT_CACHE_MAX_MEGABYTES);
+    }
+
+    #[test]
+    fn set_offline_mode_persists_without_an_api_key() {
+        let temp = tempdir().unwrap();
+        let store = SettingsStore::new(temp.path().to_path_buf());
+
+        let updated = store.set_offline_mode(true).unwrap();
+        assert!(updated.offline_mode);
+        assert!(store.load().unwrap().offline_mode);
+    }
+
     #[test]
     fn save_rejects_invalid_input() {
         let temp = tempdir().unwrap();
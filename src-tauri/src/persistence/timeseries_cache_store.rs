@@ -1,17 +1,66 @@
-use crate::domain::{AppError, QuoteStatus, SymbolPerformance, TimeRange, unix_timestamp_secs};
+use super::TimeSeriesRepository;
+use super::cache_stats::summarize_cache_entries;
+use crate::domain::{
+    AppError, AppSettings, CacheStoreStats, PricePoint, PriceSeries, QuoteStatus, SeriesInterval,
+    SymbolPerformance, TimeRange, rfc3339_to_unix_secs,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 
 const CACHE_DIR_NAME: &str = "cache";
 const TIMESERIES_DIR_NAME: &str = "timeseries";
 const TIMESERIES_CACHE_TTL_SECONDS: u64 = 300;
+const BYTES_PER_MEGABYTE: u64 = 1024 * 1024;
 
+/// Cache dirs already cleared of files from the range-keyed layout in this
+/// process, so the scan runs once per dir.
+static LEGACY_CLEARED_DIRS: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// Canonical bar series for one symbol and interval. Every `TimeRange` backed
+/// by the same interval is served as a slice of these points.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CachedTimeSeriesEntry {
-    pub performance: SymbolPerformance,
+    pub symbol: String,
+    pub interval: SeriesInterval,
+    pub points: Vec<PricePoint>,
+    pub exchange_timezone: Option<String>,
     pub cached_at: u64,
+    #[serde(default)]
+    pub last_accessed_at: u64,
+}
+
+/// One file in the timeseries cache directory. Files that cannot be parsed
+/// (for example from an older cache layout) carry no timestamps, and files
+/// with an unknown suffix carry no interval.
+#[derive(Debug, Clone)]
+pub struct TimeSeriesCacheFile {
+    pub key: String,
+    pub symbol: String,
+    pub interval: Option<SeriesInterval>,
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub cached_at: Option<u64>,
+    pub last_accessed_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheLimits {
+    pub max_entries: usize,
+    pub max_bytes: u64,
+}
+
+impl CacheLimits {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            max_entries: settings.cache_max_entries as usize,
+            max_bytes: u64::from(settings.cache_max_megabytes) * BYTES_PER_MEGABYTE,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -22,10 +71,7 @@ pub struct TimeSeriesCacheStore {
 impl TimeSeriesCacheStore {
     pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
         let base_dir = app.path().app_config_dir().map_err(|err| {
-            AppError::persistence(
-                "timeseries_cache_path_resolution_failed",
-                format!("Unable to resolve timeseries cache directory: {err}"),
-            )
+            AppError::persistence("timeseries_cache_path_resolution_failed").with_arg("detail", err)
         })?;
 
         Ok(Self::new(base_dir))
@@ -38,18 +84,18 @@ impl TimeSeriesCacheStore {
     pub fn load(
         &self,
         symbol: &str,
-        range: TimeRange,
+        interval: SeriesInterval,
     ) -> Result<Option<CachedTimeSeriesEntry>, AppError> {
-        let file_path = self.file_path(symbol, range);
+        self.remove_legacy_files();
+        let file_path = self.file_path(symbol, interval);
         if !file_path.exists() {
             return Ok(None);
         }
 
         let content = fs::read_to_string(&file_path).map_err(|err| {
-            AppError::persistence(
-                "timeseries_cache_read_failed",
-                format!("Unable to read timeseries cache: {err}"),
-            )
+            AppError::persistence("timeseries_cache_read_failed")
+                .with_arg("detail", err)
+                .with_arg("file", file_path.display())
         })?;
 
         if content.trim().is_empty() {
@@ -57,49 +103,199 @@ impl TimeSeriesCacheStore {
         }
 
         let entry = serde_json::from_str::<CachedTimeSeriesEntry>(&content).map_err(|err| {
-            AppError::persistence(
-                "timeseries_cache_parse_failed",
-                format!("Unable to parse timeseries cache file: {err}"),
-            )
+            AppError::persistence("timeseries_cache_parse_failed")
+                .with_arg("detail", err)
+                .with_arg("file", file_path.display())
         })?;
 
         Ok(Some(entry))
     }
 
-    pub fn save(
-        &self,
-        symbol: &str,
-        range: TimeRange,
-        performance: SymbolPerformance,
-    ) -> Result<(), AppError> {
-        let file_path = self.file_path(symbol, range);
+    pub fn save(&self, entry: &CachedTimeSeriesEntry) -> Result<(), AppError> {
+        let file_path = self.file_path(&entry.symbol, entry.interval);
         ensure_parent_dir(&file_path)?;
 
-        let entry = CachedTimeSeriesEntry {
-            performance,
-            cached_at: unix_timestamp_secs(),
-        };
+        let payload = serde_json::to_string_pretty(entry).map_err(|err| {
+            AppError::persistence("timeseries_cache_serialize_failed")
+                .with_arg("detail", err)
+                .with_arg("file", file_path.display())
+        })?;
 
-        let payload = serde_json::to_string_pretty(&entry).map_err(|err| {
-            AppError::persistence(
-                "timeseries_cache_serialize_failed",
-                format!("Unable to serialize timeseries cache: {err}"),
-            )
+        fs::write(&file_path, payload).map_err(|err| {
+            AppError::persistence("timeseries_cache_write_failed")
+                .with_arg("detail", err)
+                .with_arg("file", file_path.display())
+        })
+    }
+
+    pub fn mark_accessed(&self, entry: &CachedTimeSeriesEntry, now: u64) -> Result<(), AppError> {
+        let mut touched = entry.clone();
+        touched.last_accessed_at = now;
+        self.save(&touched)
+    }
+
+    pub fn list_files(&self) -> Result<Vec<TimeSeriesCacheFile>, AppError> {
+        self.remove_legacy_files();
+        let dir = self.dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let read_dir = fs::read_dir(&dir).map_err(|err| {
+            AppError::persistence("timeseries_cache_read_failed")
+                .with_arg("detail", err)
+                .with_arg("file", dir.display())
         })?;
 
-        fs::write(file_path, payload).map_err(|err| {
-            AppError::persistence(
-                "timeseries_cache_write_failed",
-                format!("Unable to write timeseries cache: {err}"),
-            )
+        let mut files: Vec<TimeSeriesCacheFile> = read_dir
+            .flatten()
+            .filter_map(|dir_entry| {
+                let path = dir_entry.path();
+                if path.extension().and_then(OsStr::to_str) != Some("json") {
+                    return None;
+                }
+
+                let key = path.file_stem()?.to_str()?.to_string();
+                let (symbol, interval) = match key.rsplit_once('-') {
+                    Some((symbol, suffix)) => {
+                        (symbol.to_string(), SeriesInterval::from_key(suffix))
+                    }
+                    None => (key.clone(), None),
+                };
+                let bytes = dir_entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+                let entry = fs::read_to_string(&path).ok().and_then(|content| {
+                    serde_json::from_str::<CachedTimeSeriesEntry>(&content).ok()
+                });
+
+                Some(TimeSeriesCacheFile {
+                    key,
+                    symbol,
+                    interval,
+                    bytes,
+                    cached_at: entry.as_ref().map(|entry| entry.cached_at),
+                    last_accessed_at: entry
+                        .as_ref()
+                        .map(|entry| entry.last_accessed_at.max(entry.cached_at)),
+                    path,
+                })
+            })
+            .collect();
+
+        files.sort_by(|left, right| left.key.cmp(&right.key));
+        Ok(files)
+    }
+
+    /// Removes least recently accessed files until the cache fits `limits`.
+    /// Returns the keys of the evicted files.
+    pub fn evict(&self, limits: CacheLimits) -> Result<Vec<String>, AppError> {
+        let mut files = self.list_files()?;
+        files.sort_by_key(|file| file.last_accessed_at);
+
+        let mut remaining_entries = files.len();
+        let mut remaining_bytes: u64 = files.iter().map(|file| file.bytes).sum();
+        let mut evicted = Vec::new();
+
+        for file in files {
+            if remaining_entries <= limits.max_entries && remaining_bytes <= limits.max_bytes {
+                break;
+            }
+
+            fs::remove_file(&file.path).map_err(|err| {
+                AppError::persistence("timeseries_cache_evict_failed").with_arg("detail", err)
+            })?;
+            remaining_entries -= 1;
+            remaining_bytes = remaining_bytes.saturating_sub(file.bytes);
+            evicted.push(file.key);
+        }
+
+        Ok(evicted)
+    }
+
+    /// Deletes every cache file matching `should_remove` and returns the
+    /// removed keys.
+    pub fn remove_where(
+        &self,
+        mut should_remove: impl FnMut(&TimeSeriesCacheFile) -> bool,
+    ) -> Result<Vec<String>, AppError> {
+        let mut removed = Vec::new();
+        for file in self.list_files()? {
+            if !should_remove(&file) {
+                continue;
+            }
+
+            fs::remove_file(&file.path).map_err(|err| {
+                AppError::persistence("timeseries_cache_remove_failed").with_arg("detail", err)
+            })?;
+            removed.push(file.key);
+        }
+
+        Ok(removed)
+    }
+
+    /// Deletes the whole cache directory, including files that no longer
+    /// parse.
+    pub fn clear(&self) -> Result<(), AppError> {
+        let dir = self.dir();
+        if !dir.exists() {
+            return Ok(());
+        }
+
+        fs::remove_dir_all(&dir).map_err(|err| {
+            AppError::persistence("cache_clear_failed")
+                .with_arg("detail", err)
+                .with_arg("file", dir.display())
         })
     }
 
-    fn file_path(&self, symbol: &str, range: TimeRange) -> PathBuf {
-        self.base_dir
-            .join(CACHE_DIR_NAME)
-            .join(TIMESERIES_DIR_NAME)
-            .join(format!("{}-{}.json", symbol, range.as_key()))
+    pub fn stats(&self) -> Result<CacheStoreStats, AppError> {
+        let files = self.list_files()?;
+        let bytes_on_disk = files.iter().map(|file| file.bytes).sum();
+        Ok(summarize_cache_entries(
+            files.len(),
+            bytes_on_disk,
+            files
+                .iter()
+                .filter_map(|file| Some((file.key.as_str(), file.cached_at?))),
+        ))
+    }
+
+    /// Deletes files from the old `{symbol}-{range}.json` layout. They can
+    /// never be read again and would otherwise sit outside eviction and
+    /// invalidation forever. Failures are retried on the next call.
+    fn remove_legacy_files(&self) {
+        let dir = self.dir();
+        let Ok(mut cleared) = LEGACY_CLEARED_DIRS.lock() else {
+            return;
+        };
+        if cleared.contains(&dir) {
+            return;
+        }
+
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            if !dir.exists() {
+                cleared.insert(dir);
+            }
+            return;
+        };
+
+        let mut all_removed = true;
+        for path in read_dir.flatten().map(|dir_entry| dir_entry.path()) {
+            if is_legacy_file(&path) && fs::remove_file(&path).is_err() {
+                all_removed = false;
+            }
+        }
+        if all_removed {
+            cleared.insert(dir);
+        }
+    }
+
+    fn dir(&self) -> PathBuf {
+        self.base_dir.join(CACHE_DIR_NAME).join(TIMESERIES_DIR_NAME)
+    }
+
+    fn file_path(&self, symbol: &str, interval: SeriesInterval) -> PathBuf {
+        self.dir()
+            .join(format!("{}-{}.json", symbol, interval.as_key()))
     }
 
     pub fn ttl_seconds() -> u64 {
@@ -111,57 +307,224 @@ pub fn is_timeseries_cache_fresh(cached_at: u64, now: u64) -> bool {
     now.saturating_sub(cached_at) <= TIMESERIES_CACHE_TTL_SECONDS
 }
 
-pub fn to_stale_performance(entry: &CachedTimeSeriesEntry) -> SymbolPerformance {
-    let mut performance = entry.performance.clone();
-    performance.status = QuoteStatus::Stale;
-    performance
+/// Merges freshly fetched bars into the canonical series. Bars with the same
+/// timestamp are replaced so a still-forming bar picks up its latest values.
+pub fn merge_series(
+    existing: Option<&CachedTimeSeriesEntry>,
+    series: PriceSeries,
+    now: u64,
+) -> CachedTimeSeriesEntry {
+    let mut bars: BTreeMap<String, PricePoint> = existing
+        .map(|entry| {
+            entry
+                .points
+                .iter()
+                .map(|point| (point.ts.clone(), point.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for point in series.points {
+        bars.insert(point.ts.clone(), point);
+    }
+
+    let overflow = bars.len().saturating_sub(series.interval.retained_bars());
+    let points = bars
+        .into_iter()
+        .skip(overflow)
+        .map(|(_, point)| point)
+        .collect();
+
+    CachedTimeSeriesEntry {
+        symbol: series.symbol,
+        interval: series.interval,
+        points,
+        exchange_timezone: series
+            .exchange_timezone
+            .or_else(|| existing.and_then(|entry| entry.exchange_timezone.clone())),
+        cached_at: now,
+        last_accessed_at: now,
+    }
+}
+
+/// Number of bars to request so the canonical series covers `range` up to
+/// `now`. When the cache already covers the range only the tail since the last
+/// cached bar is requested, including that bar so it can be updated.
+pub fn missing_bar_count(
+    entry: Option<&CachedTimeSeriesEntry>,
+    range: TimeRange,
+    now: u64,
+) -> usize {
+    let needed = range.bar_count();
+    let Some(entry) = entry.filter(|entry| entry.points.len() >= needed) else {
+        return needed;
+    };
+
+    let Some(last_bar_at) = entry
+        .points
+        .last()
+        .and_then(|point| rfc3339_to_unix_secs(&point.ts))
+    else {
+        return needed;
+    };
+
+    let elapsed = now.saturating_sub(u64::try_from(last_bar_at).unwrap_or(0));
+    let missing = elapsed.div_ceil(range.interval().seconds()) as usize + 1;
+    missing.min(needed)
+}
+
+pub fn covers_range(entry: &CachedTimeSeriesEntry, range: TimeRange) -> bool {
+    entry.interval == range.interval() && entry.points.len() >= range.bar_count()
+}
+
+pub fn to_range_performance(
+    entry: &CachedTimeSeriesEntry,
+    range: TimeRange,
+    status: QuoteStatus,
+) -> Option<SymbolPerformance> {
+    let skip = entry.points.len().saturating_sub(range.bar_count());
+    SymbolPerformance::from_points(
+        &entry.symbol,
+        range,
+        entry.points[skip..].to_vec(),
+        entry.exchange_timezone.clone(),
+        status,
+    )
+}
+
+/// Cached bars marked stale with the error that kept them from being
+/// refreshed, in the same shape as `to_stale_quote`.
+pub fn to_stale_performance(
+    entry: &CachedTimeSeriesEntry,
+    range: TimeRange,
+    error: &AppError,
+) -> Option<SymbolPerformance> {
+    let mut performance = to_range_performance(entry, range, QuoteStatus::Stale)?;
+    performance.error_code = Some(error.code.clone());
+    performance.error_message = Some(error.message.clone());
+    Some(performance)
+}
+
+fn is_legacy_file(path: &Path) -> bool {
+    path.extension().and_then(OsStr::to_str) == Some("json")
+        && path
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .and_then(|key| key.rsplit_once('-'))
+            .is_some_and(|(_, suffix)| TimeRange::ALL.iter().any(|range| range.as_key() == suffix))
 }
 
 fn ensure_parent_dir(path: &Path) -> Result<(), AppError> {
     let parent = path.parent().ok_or_else(|| {
-        AppError::persistence(
-            "timeseries_cache_parent_dir_missing",
-            "Timeseries cache path has no parent directory.",
-        )
+        AppError::persistence("timeseries_cache_parent_dir_missing")
+            .with_arg("file", path.display())
     })?;
 
     fs::create_dir_all(parent).map_err(|err| {
-        AppError::persistence(
-            "timeseries_cache_dir_create_failed",
-            format!("Unable to create timeseries cache directory: {err}"),
-        )
+        AppError::persistence("timeseries_cache_dir_create_failed")
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
     })
 }
 
+impl TimeSeriesRepository for TimeSeriesCacheStore {
+    fn load(
+        &self,
+        symbol: &str,
+        interval: SeriesInterval,
+    ) -> Result<Option<CachedTimeSeriesEntry>, AppError> {
+        TimeSeriesCacheStore::load(self, symbol, interval)
+    }
+
+    fn save(&self, entry: &CachedTimeSeriesEntry) -> Result<(), AppError> {
+        TimeSeriesCacheStore::save(self, entry)
+    }
+
+    fn mark_accessed(&self, entry: &CachedTimeSeriesEntry, now: u64) -> Result<(), AppError> {
+        TimeSeriesCacheStore::mark_accessed(self, entry, now)
+    }
+
+    fn evict(&self, limits: CacheLimits) -> Result<Vec<String>, AppError> {
+        TimeSeriesCacheStore::evict(self, limits)
+    }
+
+    fn remove_where(
+        &self,
+        should_remove: &mut dyn FnMut(&TimeSeriesCacheFile) -> bool,
+    ) -> Result<Vec<String>, AppError> {
+        TimeSeriesCacheStore::remove_where(self, should_remove)
+    }
+
+    fn clear(&self) -> Result<(), AppError> {
+        TimeSeriesCacheStore::clear(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::tempdir;
 
-    fn sample_performance() -> SymbolPerformance {
-        SymbolPerformance {
+    fn daily_points(days: std::ops::Range<u32>) -> Vec<PricePoint> {
+        days.map(|day| {
+            PricePoint::new(
+                format!("2026-01-{day:02}T21:00:00Z"),
+                100.0 + f64::from(day),
+            )
+        })
+        .collect()
+    }
+
+    fn sample_series(points: Vec<PricePoint>) -> PriceSeries {
+        PriceSeries {
             symbol: "AAPL".to_string(),
-            range: TimeRange::OneWeek,
-            points: vec![],
-            min: 180.0,
-            max: 200.0,
-            start: 181.0,
-            end: 198.0,
-            last_updated_at: "2026-02-22 10:00:00".to_string(),
-            status: QuoteStatus::Fresh,
+            interval: SeriesInterval::OneDay,
+            points,
+            exchange_timezone: Some("America/New_York".to_string()),
         }
     }
 
     #[test]
-    fn timeseries_cache_roundtrip() {
+    fn legacy_range_keyed_files_are_removed_on_first_load() {
         let temp = tempdir().unwrap();
         let store = TimeSeriesCacheStore::new(temp.path().to_path_buf());
-
         store
-            .save("AAPL", TimeRange::OneWeek, sample_performance())
+            .save(&merge_series(None, sample_series(daily_points(1..8)), 100))
             .unwrap();
-        let loaded = store.load("AAPL", TimeRange::OneWeek).unwrap();
-        assert!(loaded.is_some());
+        let legacy = store.dir().join("AAPL-1M.json");
+        fs::write(&legacy, "{}").unwrap();
+
+        assert!(
+            store
+                .load("AAPL", SeriesInterval::OneDay)
+                .unwrap()
+                .is_some()
+        );
+        assert!(!legacy.exists());
+        let keys: Vec<String> = store
+            .list_files()
+            .unwrap()
+            .into_iter()
+            .map(|file| file.key)
+            .collect();
+        assert_eq!(keys, ["AAPL-1day"]);
+    }
+
+    #[test]
+    fn timeseries_cache_roundtrip() {
+        let temp = tempdir().unwrap();
+        let store = TimeSeriesCacheStore::new(temp.path().to_path_buf());
+
+        let entry = merge_series(None, sample_series(daily_points(1..8)), 100);
+        store.save(&entry).unwrap();
+        let loaded = store.load("AAPL", SeriesInterval::OneDay).unwrap();
+        assert_eq!(loaded.unwrap().points.len(), 7);
+        assert!(
+            store
+                .load("AAPL", SeriesInterval::OneHour)
+                .unwrap()
+                .is_none()
+        );
     }
 
     #[test]
@@ -169,4 +532,180 @@ mod tests {
         assert!(is_timeseries_cache_fresh(100, 350));
         assert!(!is_timeseries_cache_fresh(100, 401));
     }
+
+    fn save_daily_entry(store: &TimeSeriesCacheStore, symbol: &str, accessed_at: u64) {
+        let mut series = sample_series(daily_points(1..8));
+        series.symbol = symbol.to_string();
+        store
+            .save(&merge_series(None, series, accessed_at))
+            .unwrap();
+    }
+
+    #[test]
+    fn evict_removes_least_recently_accessed_entries_first() {
+        let temp = tempdir().unwrap();
+        let store = TimeSeriesCacheStore::new(temp.path().to_path_buf());
+        save_daily_entry(&store, "AAPL", 300);
+        save_daily_entry(&store, "MSFT", 100);
+        save_daily_entry(&store, "NVDA", 200);
+
+        let msft = store.load("MSFT", SeriesInterval::OneDay).unwrap().unwrap();
+        store.mark_accessed(&msft, 400).unwrap();
+
+        let evicted = store
+            .evict(CacheLimits {
+                max_entries: 2,
+                max_bytes: u64::MAX,
+            })
+            .unwrap();
+        assert_eq!(evicted, vec!["NVDA-1day".to_string()]);
+        assert!(
+            store
+                .load("MSFT", SeriesInterval::OneDay)
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn evict_enforces_byte_limit_and_drops_unreadable_files_first() {
+        let temp = tempdir().unwrap();
+        let store = TimeSeriesCacheStore::new(temp.path().to_path_buf());
+        save_daily_entry(&store, "AAPL", 100);
+        save_daily_entry(&store, "MSFT", 200);
+        fs::write(store.dir().join("GOOG-1day.json"), "{}").unwrap();
+
+        let files = store.list_files().unwrap();
+        let newest_bytes = files
+            .iter()
+            .find(|file| file.key == "MSFT-1day")
+            .unwrap()
+            .bytes;
+
+        let evicted = store
+            .evict(CacheLimits {
+                max_entries: 10,
+                max_bytes: newest_bytes,
+            })
+            .unwrap();
+        assert_eq!(
+            evicted,
+            vec!["GOOG-1day".to_string(), "AAPL-1day".to_string()]
+        );
+    }
+
+    #[test]
+    fn remove_where_matches_on_symbol_and_interval() {
+        let temp = tempdir().unwrap();
+        let store = TimeSeriesCacheStore::new(temp.path().to_path_buf());
+        save_daily_entry(&store, "BRK-B", 100);
+        save_daily_entry(&store, "MSFT", 100);
+        fs::write(store.dir().join("BRK-B-1week.json"), "{}").unwrap();
+
+        let removed = store.remove_where(|file| file.symbol == "BRK-B").unwrap();
+        assert_eq!(
+            removed,
+            vec!["BRK-B-1day".to_string(), "BRK-B-1week".to_string()]
+        );
+
+        let removed = store
+            .remove_where(|file| file.interval == Some(SeriesInterval::OneHour))
+            .unwrap();
+        assert!(removed.is_empty());
+        assert_eq!(store.list_files().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn stats_report_entries_and_age_bounds() {
+        let temp = tempdir().unwrap();
+        let store = TimeSeriesCacheStore::new(temp.path().to_path_buf());
+        assert_eq!(store.stats().unwrap(), CacheStoreStats::default());
+
+        save_daily_entry(&store, "AAPL", 1_771_754_400);
+        save_daily_entry(&store, "MSFT", 1_771_758_000);
+
+        let stats = store.stats().unwrap();
+        assert_eq!(stats.entry_count, 2);
+        assert!(stats.bytes_on_disk > 0);
+        assert_eq!(stats.oldest_entry.unwrap().key, "AAPL-1day");
+        let newest = stats.newest_entry.unwrap();
+        assert_eq!(newest.key, "MSFT-1day");
+        assert_eq!(newest.cached_at, "2026-02-22T11:00:00Z");
+    }
+
+    #[test]
+    fn merge_series_replaces_overlapping_bars_and_appends_new_ones() {
+        let existing = merge_series(None, sample_series(daily_points(1..5)), 100);
+        let mut tail = daily_points(4..7);
+        tail[0].close = 999.0;
+
+        let merged = merge_series(Some(&existing), sample_series(tail), 200);
+        assert_eq!(merged.points.len(), 6);
+        assert_eq!(merged.points[3].close, 999.0);
+        assert_eq!(merged.points.last().unwrap().ts, "2026-01-06T21:00:00Z");
+        assert_eq!(merged.cached_at, 200);
+    }
+
+    #[test]
+    fn merge_series_keeps_only_retained_bars() {
+        let points: Vec<PricePoint> = (0..120)
+            .map(|index| {
+                PricePoint::new(
+                    format!("2026-01-01T00:{:02}:{:02}Z", index / 60, index % 60),
+                    f64::from(index),
+                )
+            })
+            .collect();
+
+        let merged = merge_series(None, sample_series(points), 100);
+        assert_eq!(merged.points.len(), SeriesInterval::OneDay.retained_bars());
+        assert_eq!(merged.points[0].close, 30.0);
+    }
+
+    #[test]
+    fn missing_bar_count_requests_full_range_when_cache_is_short() {
+        let entry = merge_series(None, sample_series(daily_points(1..8)), 100);
+        assert_eq!(
+            missing_bar_count(Some(&entry), TimeRange::OneMonth, 0),
+            TimeRange::OneMonth.bar_count()
+        );
+        assert_eq!(
+            missing_bar_count(None, TimeRange::OneWeek, 0),
+            TimeRange::OneWeek.bar_count()
+        );
+    }
+
+    #[test]
+    fn missing_bar_count_requests_only_the_tail() {
+        let entry = merge_series(None, sample_series(daily_points(1..8)), 100);
+        let last_bar_at = rfc3339_to_unix_secs("2026-01-07T21:00:00Z").unwrap() as u64;
+
+        assert_eq!(
+            missing_bar_count(Some(&entry), TimeRange::OneWeek, last_bar_at + 3_600),
+            2
+        );
+        assert_eq!(
+            missing_bar_count(Some(&entry), TimeRange::OneWeek, last_bar_at + 2 * 86_400),
+            3
+        );
+    }
+
+    #[test]
+    fn ranges_are_served_as_slices_of_the_canonical_series() {
+        let entry = merge_series(None, sample_series(daily_points(1..31)), 100);
+
+        let week = to_range_performance(&entry, TimeRange::OneWeek, QuoteStatus::Fresh).unwrap();
+        assert_eq!(week.points.len(), 7);
+        assert_eq!(week.start, 124.0);
+        assert_eq!(week.end, 130.0);
+        assert!(covers_range(&entry, TimeRange::OneMonth));
+        assert!(!covers_range(&entry, TimeRange::ThreeMonths));
+
+        let offline = AppError::provider("offline").with_reason("cached_chart");
+        let stale = to_stale_performance(&entry, TimeRange::OneMonth, &offline).unwrap();
+        assert_eq!(stale.points.len(), 30);
+        assert_eq!(stale.status, QuoteStatus::Stale);
+        assert_eq!(stale.error_code.as_deref(), Some("offline"));
+        assert_eq!(stale.error_message, Some(offline.message));
+    }
 }
@@ -1,3 +1,4 @@
+use super::WatchlistRepository;
 use crate::domain::{AppError, WatchlistItem};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -13,10 +14,7 @@ pub struct WatchlistStore {
 impl WatchlistStore {
     pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
         let base_dir = app.path().app_config_dir().map_err(|err| {
-            AppError::persistence(
-                "watchlist_path_resolution_failed",
-                format!("Unable to resolve watchlist directory: {err}"),
-            )
+            AppError::persistence("watchlist_path_resolution_failed").with_arg("detail", err)
         })?;
 
         Ok(Self::new(base_dir))
@@ -34,10 +32,9 @@ impl WatchlistStore {
         }
 
         let content = fs::read_to_string(&self.file_path).map_err(|err| {
-            AppError::persistence(
-                "watchlist_read_failed",
-                format!("Unable to read watchlist from disk: {err}"),
-            )
+            AppError::persistence("watchlist_read_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })?;
 
         if content.trim().is_empty() {
@@ -45,27 +42,24 @@ impl WatchlistStore {
         }
 
         serde_json::from_str::<Vec<WatchlistItem>>(&content).map_err(|err| {
-            AppError::persistence(
-                "watchlist_parse_failed",
-                format!("Unable to parse watchlist file: {err}"),
-            )
+            AppError::persistence("watchlist_parse_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })
     }
 
     pub fn save(&self, watchlist: &[WatchlistItem]) -> Result<Vec<WatchlistItem>, AppError> {
         ensure_parent_dir(&self.file_path)?;
         let payload = serde_json::to_string_pretty(watchlist).map_err(|err| {
-            AppError::persistence(
-                "watchlist_serialize_failed",
-                format!("Unable to serialize watchlist: {err}"),
-            )
+            AppError::persistence("watchlist_serialize_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })?;
 
         fs::write(&self.file_path, payload).map_err(|err| {
-            AppError::persistence(
-                "watchlist_write_failed",
-                format!("Unable to write watchlist to disk: {err}"),
-            )
+            AppError::persistence("watchlist_write_failed")
+                .with_arg("detail", err)
+                .with_arg("file", self.file_path.display())
         })?;
 
         Ok(watchlist.to_vec())
@@ -74,20 +68,26 @@ impl WatchlistStore {
 
 fn ensure_parent_dir(path: &Path) -> Result<(), AppError> {
     let parent = path.parent().ok_or_else(|| {
-        AppError::persistence(
-            "watchlist_parent_dir_missing",
-            "Watchlist path has no parent directory.",
-        )
+        AppError::persistence("watchlist_parent_dir_missing").with_arg("file", path.display())
     })?;
 
     fs::create_dir_all(parent).map_err(|err| {
-        AppError::persistence(
-            "watchlist_dir_create_failed",
-            format!("Unable to create watchlist directory: {err}"),
-        )
+        AppError::persistence("watchlist_dir_create_failed")
+            .with_arg("detail", err)
+            .with_arg("file", path.display())
     })
 }
 
+impl WatchlistRepository for WatchlistStore {
+    fn load(&self) -> Result<Vec<WatchlistItem>, AppError> {
+        WatchlistStore::load(self)
+    }
+
+    fn save(&self, watchlist: &[WatchlistItem]) -> Result<Vec<WatchlistItem>, AppError> {
+        WatchlistStore::save(self, watchlist)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,165 @@
+use super::json_list::{append_capped, read_list, write_list};
+use crate::domain::{AppError, Webhook, WebhookDelivery, register_secret};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use tauri::{AppHandle, Manager};
+
+const WEBHOOKS_FILE_NAME: &str = "webhooks.json";
+const DELIVERY_LOG_FILE_NAME: &str = "webhook_deliveries.json";
+const MAX_DELIVERY_LOG: usize = 200;
+const STORE: &str = "webhooks";
+const PUBLIC_HEADERS: [&str; 3] = ["accept", "content-type", "user-agent"];
+
+/// Serializes delivery log appends. Dispatches run in spawned tasks, and two
+/// appends racing through the read-modify-write would drop one side's
+/// entries. The app keeps one in managed state.
+#[derive(Debug, Default)]
+pub struct DeliveryLogLock(Mutex<()>);
+
+impl DeliveryLogLock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Configured webhooks plus a capped delivery log, newest last.
+#[derive(Debug, Clone)]
+pub struct WebhookStore {
+    webhooks_path: PathBuf,
+    deliveries_path: PathBuf,
+}
+
+impl WebhookStore {
+    pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
+        let base_dir = app.path().app_config_dir().map_err(|err| {
+            AppError::persistence("webhooks_path_resolution_failed").with_arg("detail", err)
+        })?;
+
+        Ok(Self::new(base_dir))
+    }
+
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self {
+            webhooks_path: base_dir.join(WEBHOOKS_FILE_NAME),
+            deliveries_path: base_dir.join(DELIVERY_LOG_FILE_NAME),
+        }
+    }
+
+    pub fn load_webhooks(&self) -> Result<Vec<Webhook>, AppError> {
+        let webhooks: Vec<Webhook> = read_list(&self.webhooks_path, STORE)?;
+        webhooks.iter().for_each(register_webhook_secrets);
+        Ok(webhooks)
+    }
+
+    pub fn save_webhooks(&self, webhooks: &[Webhook]) -> Result<Vec<Webhook>, AppError> {
+        webhooks.iter().for_each(register_webhook_secrets);
+        write_list(&self.webhooks_path, webhooks, STORE)?;
+        Ok(webhooks.to_vec())
+    }
+
+    pub fn load_deliveries(&self) -> Result<Vec<WebhookDelivery>, AppError> {
+        read_list(&self.deliveries_path, STORE)
+    }
+
+    /// Appends `deliveries` under `log_lock` and drops the oldest entries
+    /// beyond the log cap.
+    pub fn append_deliveries(
+        &self,
+        log_lock: &DeliveryLogLock,
+        deliveries: &[WebhookDelivery],
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        let _guard = log_lock.0.lock().unwrap_or_else(PoisonError::into_inner);
+        append_capped(&self.deliveries_path, deliveries, MAX_DELIVERY_LOG, STORE)
+    }
+}
+
+/// Webhook URLs often embed their token in the path, and custom header values
+/// are usually credentials, so both are kept out of logs and error messages.
+fn register_webhook_secrets(webhook: &Webhook) {
+    register_secret(&webhook.url);
+    webhook
+        .headers
+        .iter()
+        .filter(|(name, _)| !PUBLIC_HEADERS.contains(&name.to_ascii_lowercase().as_str()))
+        .for_each(|(_, value)| register_secret(value));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use tempfile::tempdir;
+
+    fn delivery(index: usize) -> WebhookDelivery {
+        WebhookDelivery {
+            id: format!("delivery-{index}"),
+            webhook_id: "hook-1".to_string(),
+            event: "alert".to_string(),
+            attempts: 1,
+            status_code: Some(200),
+            success: true,
+            error: None,
+            started_at: "2026-02-22T15:00:00Z".to_string(),
+            finished_at: "2026-02-22T15:00:01Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn save_and_load_roundtrip_webhooks() {
+        let temp = tempdir().unwrap();
+        let store = WebhookStore::new(temp.path().to_path_buf());
+        assert!(store.load_webhooks().unwrap().is_empty());
+
+        let webhooks = vec![Webhook {
+            id: "hook-1".to_string(),
+            name: "Chat".to_string(),
+            url: "https://example.com/hook".to_string(),
+            headers: BTreeMap::from([("X-Token".to_string(), "secret".to_string())]),
+            body_template: Some("{{payload}}".to_string()),
+            on_alert: true,
+            on_refresh: false,
+            enabled: true,
+            created_at: "2026-02-22T12:00:00Z".to_string(),
+        }];
+        store.save_webhooks(&webhooks).unwrap();
+
+        assert_eq!(store.load_webhooks().unwrap(), webhooks);
+    }
+
+    #[test]
+    fn append_deliveries_keeps_the_newest_entries() {
+        let temp = tempdir().unwrap();
+        let store = WebhookStore::new(temp.path().to_path_buf());
+
+        let deliveries: Vec<WebhookDelivery> = (0..MAX_DELIVERY_LOG + 3).map(delivery).collect();
+        store
+            .append_deliveries(&DeliveryLogLock::new(), &deliveries)
+            .unwrap();
+
+        let log = store.load_deliveries().unwrap();
+        assert_eq!(log.len(), MAX_DELIVERY_LOG);
+        assert_eq!(log[0].id, "delivery-3");
+    }
+
+    #[test]
+    fn concurrent_appends_keep_every_entry() {
+        let temp = tempdir().unwrap();
+        let store = WebhookStore::new(temp.path().to_path_buf());
+        let log_lock = DeliveryLogLock::new();
+
+        std::thread::scope(|scope| {
+            for writer in 0..8 {
+                let (store, log_lock) = (&store, &log_lock);
+                scope.spawn(move || {
+                    for index in 0..5 {
+                        store
+                            .append_deliveries(log_lock, &[delivery(writer * 5 + index)])
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        assert_eq!(store.load_deliveries().unwrap().len(), 40);
+    }
+}
@@ -2,17 +2,29 @@ mod twelvedata;
 
 pub use twelvedata::TwelveDataAdapter;
 
-use crate::domain::{AppError, ProviderTestResult, QuoteSummary, SymbolPerformance, TimeRange};
+use crate::domain::{
+    ApiCredits, AppError, PriceSeries, ProviderEndpoint, ProviderTestResult, QuoteSummary,
+    SeriesInterval,
+};
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait MarketDataProvider: Send + Sync {
     async fn test_connection(&self, api_key: &str) -> Result<ProviderTestResult, AppError>;
     async fn fetch_quote(&self, symbol: &str, api_key: &str) -> Result<QuoteSummary, AppError>;
-    async fn fetch_symbol_performance(
+    /// Fetches the most recent `outputsize` bars, returned oldest first.
+    async fn fetch_time_series(
         &self,
         symbol: &str,
-        range: TimeRange,
+        interval: SeriesInterval,
+        outputsize: usize,
         api_key: &str,
-    ) -> Result<SymbolPerformance, AppError>;
+    ) -> Result<PriceSeries, AppError>;
+    /// Makes the cheapest possible request to `endpoint` as a health check and
+    /// returns the credit usage the provider reported with it.
+    async fn probe_endpoint(
+        &self,
+        endpoint: ProviderEndpoint,
+        api_key: &str,
+    ) -> Result<ApiCredits, AppError>;
 }
@@ -1,16 +1,22 @@
 use super::MarketDataProvider;
 use crate::domain::{
-    AppError, AppProvider, PricePoint, ProviderTestResult, QuoteStatus, QuoteSummary,
-    SymbolPerformance, TimeRange, unix_timestamp_secs,
+    ApiCredits, AppError, AppProvider, PricePoint, PriceSeries, ProviderEndpoint,
+    ProviderTestResult, QuoteStatus, QuoteSummary, SeriesInterval, now_rfc3339,
+    provider_datetime_to_rfc3339, unix_secs_to_rfc3339,
 };
+use crate::observability::metrics;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
 use serde_json::Value;
 
 const DEFAULT_BASE_URL: &str = "https://api.twelvedata.com";
 const TEST_SYMBOL: &str = "AAPL";
 const TEST_INTERVAL: &str = "1day";
 const TEST_OUTPUT_SIZE: &str = "1";
+const CREDITS_USED_HEADER: &str = "api-credits-used";
+const CREDITS_LEFT_HEADER: &str = "api-credits-left";
 
 #[derive(Debug, Clone)]
 pub struct TwelveDataAdapter {
@@ -25,44 +31,73 @@ impl TwelveDataAdapter {
             base_url: DEFAULT_BASE_URL.to_string(),
         }
     }
-}
 
-#[async_trait]
-impl MarketDataProvider for TwelveDataAdapter {
-    async fn test_connection(&self, api_key: &str) -> Result<ProviderTestResult, AppError> {
-        let sanitized_key = api_key.trim();
-        if sanitized_key.is_empty() {
-            return Err(AppError::validation(
-                "invalid_settings",
-                "Save a valid API key before testing connection.",
-            ));
-        }
+    /// Fetches `endpoint` and returns its JSON payload, turning transport
+    /// failures and provider error payloads into `AppError`s. Response sizes
+    /// are added to the bytes fetched metric.
+    async fn get_payload(&self, endpoint: &str, query: &[(&str, &str)]) -> Result<Value, AppError> {
+        self.get_response(endpoint, query)
+            .await
+            .map(|(payload, _)| payload)
+    }
 
+    /// Same as `get_payload`, also returning the credit headers.
+    async fn get_response(
+        &self,
+        endpoint: &str,
+        query: &[(&str, &str)],
+    ) -> Result<(Value, ApiCredits), AppError> {
         let response = self
             .client
-            .get(format!("{}/time_series", self.base_url))
-            .query(&[
-                ("symbol", TEST_SYMBOL),
-                ("interval", TEST_INTERVAL),
-                ("outputsize", TEST_OUTPUT_SIZE),
-                ("apikey", sanitized_key),
-            ])
+            .get(format!("{}/{endpoint}", self.base_url))
+            .query(query)
             .send()
             .await
             .map_err(map_transport_error)?;
 
         let status = response.status();
-        let payload = response.json::<Value>().await.map_err(|err| {
-            AppError::provider(
-                "provider_payload_parse_failed",
-                format!("Unable to parse provider response: {err}"),
-            )
+        let credits = read_api_credits(response.headers());
+        let retry_after = read_retry_after(response.headers(), Utc::now());
+        let body = response.bytes().await.map_err(map_transport_error)?;
+        metrics().record_bytes_fetched(body.len() as u64);
+        let payload = serde_json::from_slice::<Value>(&body).map_err(|err| {
+            AppError::provider("provider_payload_parse_failed").with_arg("detail", err)
         })?;
 
         if !status.is_success() || payload_has_error_status(&payload) {
-            return Err(map_provider_error(status, &payload));
+            let err = map_provider_error(status, &payload);
+            return Err(match retry_after {
+                Some(seconds) if err.retryable => err.with_retry_after(seconds),
+                _ => err,
+            });
+        }
+
+        Ok((payload, credits))
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for TwelveDataAdapter {
+    async fn test_connection(&self, api_key: &str) -> Result<ProviderTestResult, AppError> {
+        let sanitized_key = api_key.trim();
+        if sanitized_key.is_empty() {
+            return Err(AppError::validation("invalid_settings").with_reason("api_key_missing"));
         }
 
+        let result = self
+            .get_payload(
+                "time_series",
+                &[
+                    ("symbol", TEST_SYMBOL),
+                    ("interval", TEST_INTERVAL),
+                    ("outputsize", TEST_OUTPUT_SIZE),
+                    ("apikey", sanitized_key),
+                ],
+            )
+            .await;
+        metrics().record_provider_call("time_series", &result);
+        result?;
+
         Ok(ProviderTestResult {
             ok: true,
             provider: AppProvider::Twelvedata,
@@ -73,77 +108,101 @@ impl MarketDataProvider for TwelveDataAdapter {
     async fn fetch_quote(&self, symbol: &str, api_key: &str) -> Result<QuoteSummary, AppError> {
         let sanitized_key = api_key.trim();
         if sanitized_key.is_empty() {
-            return Err(AppError::validation(
-                "invalid_settings",
-                "Save a valid API key before refreshing quotes.",
-            ));
+            return Err(AppError::validation("invalid_settings").with_reason("api_key_missing"));
         }
 
-        let response = self
-            .client
-            .get(format!("{}/quote", self.base_url))
-            .query(&[("symbol", symbol), ("apikey", sanitized_key)])
-            .send()
+        let result = self
+            .get_payload("quote", &[("symbol", symbol), ("apikey", sanitized_key)])
             .await
-            .map_err(map_transport_error)?;
-
-        let status = response.status();
-        let payload = response.json::<Value>().await.map_err(|err| {
-            AppError::provider(
-                "provider_payload_parse_failed",
-                format!("Unable to parse provider response: {err}"),
-            )
-        })?;
+            .and_then(|payload| parse_quote_payload(symbol, payload));
+        metrics().record_provider_call("quote", &result);
+        result
+    }
 
-        if !status.is_success() || payload_has_error_status(&payload) {
-            return Err(map_provider_error(status, &payload));
+    async fn fetch_time_series(
+        &self,
+        symbol: &str,
+        interval: SeriesInterval,
+        outputsize: usize,
+        api_key: &str,
+    ) -> Result<PriceSeries, AppError> {
+        let sanitized_key = api_key.trim();
+        if sanitized_key.is_empty() {
+            return Err(AppError::validation("invalid_settings").with_reason("api_key_missing"));
         }
 
-        parse_quote_payload(symbol, payload)
+        let outputsize = outputsize.max(1).to_string();
+        let result = self
+            .get_payload(
+                "time_series",
+                &[
+                    ("symbol", symbol),
+                    ("interval", interval.as_key()),
+                    ("outputsize", outputsize.as_str()),
+                    ("apikey", sanitized_key),
+                ],
+            )
+            .await
+            .and_then(|payload| parse_time_series_payload(symbol, interval, payload));
+        metrics().record_provider_call("time_series", &result);
+        result
     }
 
-    async fn fetch_symbol_performance(
+    async fn probe_endpoint(
         &self,
-        symbol: &str,
-        range: TimeRange,
+        endpoint: ProviderEndpoint,
         api_key: &str,
-    ) -> Result<SymbolPerformance, AppError> {
+    ) -> Result<ApiCredits, AppError> {
         let sanitized_key = api_key.trim();
         if sanitized_key.is_empty() {
-            return Err(AppError::validation(
-                "invalid_settings",
-                "Save a valid API key before loading chart data.",
-            ));
+            return Err(AppError::validation("invalid_settings").with_reason("api_key_missing"));
         }
 
-        let (interval, outputsize) = range_request_config(range);
-        let response = self
-            .client
-            .get(format!("{}/time_series", self.base_url))
-            .query(&[
-                ("symbol", symbol),
-                ("interval", interval),
-                ("outputsize", outputsize),
-                ("apikey", sanitized_key),
-            ])
-            .send()
+        let mut query = match endpoint {
+            ProviderEndpoint::Quote => vec![("symbol", TEST_SYMBOL)],
+            ProviderEndpoint::TimeSeries => vec![
+                ("symbol", TEST_SYMBOL),
+                ("interval", TEST_INTERVAL),
+                ("outputsize", TEST_OUTPUT_SIZE),
+            ],
+            ProviderEndpoint::SymbolSearch => {
+                vec![("symbol", TEST_SYMBOL), ("outputsize", TEST_OUTPUT_SIZE)]
+            }
+        };
+        query.push(("apikey", sanitized_key));
+
+        let result = self
+            .get_response(endpoint.as_str(), &query)
             .await
-            .map_err(map_transport_error)?;
-
-        let status = response.status();
-        let payload = response.json::<Value>().await.map_err(|err| {
-            AppError::provider(
-                "provider_payload_parse_failed",
-                format!("Unable to parse provider response: {err}"),
-            )
-        })?;
+            .map(|(_, credits)| credits);
+        metrics().record_provider_call(endpoint.as_str(), &result);
+        result
+    }
+}
 
-        if !status.is_success() || payload_has_error_status(&payload) {
-            return Err(map_provider_error(status, &payload));
-        }
+fn read_api_credits(headers: &HeaderMap) -> ApiCredits {
+    let read = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+    };
+    ApiCredits {
+        used: read(CREDITS_USED_HEADER),
+        left: read(CREDITS_LEFT_HEADER),
+    }
+}
 
-        parse_symbol_performance_payload(symbol, range, payload)
+/// Reads `Retry-After` as either a number of seconds or an HTTP date.
+fn read_retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<u64> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds);
     }
+
+    let retry_at = DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = retry_at.with_timezone(&Utc) - now;
+    Some(u64::try_from(wait.num_seconds()).unwrap_or(0))
 }
 
 fn payload_has_error_status(payload: &Value) -> bool {
@@ -155,20 +214,14 @@ fn payload_has_error_status(payload: &Value) -> bool {
 
 fn map_transport_error(err: reqwest::Error) -> AppError {
     if err.is_timeout() {
-        return AppError::provider(
-            "network_timeout",
-            "Connection test timed out while contacting provider.",
-        );
+        return AppError::provider("network_timeout");
     }
 
     if err.is_connect() {
-        return AppError::provider(
-            "network_connect_error",
-            "Unable to connect to provider. Check your network connection.",
-        );
+        return AppError::provider("network_connect_error");
     }
 
-    AppError::provider("network_error", format!("Provider request failed: {err}"))
+    AppError::provider("network_error").with_arg("detail", err.without_url())
 }
 
 fn map_provider_error(status: StatusCode, payload: &Value) -> AppError {
@@ -196,7 +249,7 @@ fn map_provider_error(status: StatusCode, payload: &Value) -> AppError {
         "provider_error"
     };
 
-    AppError::provider(code, message)
+    AppError::provider(code).with_arg("detail", message)
 }
 
 fn parse_quote_payload(symbol: &str, payload: Value) -> Result<QuoteSummary, AppError> {
@@ -207,7 +260,7 @@ fn parse_quote_payload(symbol: &str, payload: Value) -> Result<QuoteSummary, App
         .unwrap_or_else(|| symbol.to_string());
 
     let price = parse_number_field(&payload, &["close", "price", "last"]).ok_or_else(|| {
-        AppError::provider("provider_payload_invalid", "Quote payload missing price.")
+        AppError::provider("provider_payload_invalid").with_reason("missing_price")
     })?;
 
     let change_abs = parse_number_field(&payload, &["change"]);
@@ -216,11 +269,21 @@ fn parse_quote_payload(symbol: &str, payload: Value) -> Result<QuoteSummary, App
         .get("currency")
         .and_then(Value::as_str)
         .map(str::to_string);
-    let last_updated_at = payload
-        .get("datetime")
-        .and_then(Value::as_str)
-        .map(str::to_string)
-        .unwrap_or_else(|| unix_timestamp_secs().to_string());
+    let exchange_timezone = ["exchange_timezone", "timezone"]
+        .iter()
+        .find_map(|key| payload.get(*key).and_then(Value::as_str))
+        .map(str::to_string);
+    let last_updated_at = parse_number_field(&payload, &["timestamp", "last_quote_at"])
+        .and_then(|secs| unix_secs_to_rfc3339(secs as i64))
+        .or_else(|| {
+            payload
+                .get("datetime")
+                .and_then(Value::as_str)
+                .and_then(|datetime| {
+                    provider_datetime_to_rfc3339(datetime, exchange_timezone.as_deref())
+                })
+        })
+        .unwrap_or_else(now_rfc3339);
 
     Ok(QuoteSummary {
         symbol: symbol_value,
@@ -229,6 +292,7 @@ fn parse_quote_payload(symbol: &str, payload: Value) -> Result<QuoteSummary, App
         change_pct,
         currency,
         last_updated_at,
+        exchange_timezone,
         status: QuoteStatus::Fresh,
         error_code: None,
         error_message: None,
@@ -247,88 +311,56 @@ fn parse_number_field(payload: &Value, keys: &[&str]) -> Option<f64> {
     })
 }
 
-fn range_request_config(range: TimeRange) -> (&'static str, &'static str) {
-    match range {
-        TimeRange::OneDay => ("1h", "24"),
-        TimeRange::OneWeek => ("1day", "7"),
-        TimeRange::OneMonth => ("1day", "30"),
-        TimeRange::ThreeMonths => ("1day", "90"),
-        TimeRange::OneYear => ("1week", "52"),
-    }
-}
-
-fn parse_symbol_performance_payload(
+fn parse_time_series_payload(
     symbol: &str,
-    range: TimeRange,
+    interval: SeriesInterval,
     payload: Value,
-) -> Result<SymbolPerformance, AppError> {
+) -> Result<PriceSeries, AppError> {
     let values = payload
         .get("values")
         .and_then(Value::as_array)
         .ok_or_else(|| {
-            AppError::provider(
-                "provider_payload_invalid",
-                "Time series payload missing values.",
-            )
+            AppError::provider("provider_payload_invalid").with_reason("missing_values")
         })?;
 
     if values.is_empty() {
-        return Err(AppError::provider(
-            "provider_payload_invalid",
-            "Time series payload contains no values.",
-        ));
+        return Err(AppError::provider("provider_payload_invalid").with_reason("no_values"));
     }
 
+    let exchange_timezone = payload
+        .get("meta")
+        .and_then(|meta| meta.get("exchange_timezone"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
     let mut points: Vec<PricePoint> = values
         .iter()
         .filter_map(|entry| {
-            let ts = entry.get("datetime")?.as_str()?.to_string();
+            let datetime = entry.get("datetime")?.as_str()?;
+            let ts = provider_datetime_to_rfc3339(datetime, exchange_timezone.as_deref())?;
             let close = parse_number_field(entry, &["close"])?;
-            Some(PricePoint { ts, close })
+            Some(PricePoint {
+                ts,
+                close,
+                open: parse_number_field(entry, &["open"]),
+                high: parse_number_field(entry, &["high"]),
+                low: parse_number_field(entry, &["low"]),
+                volume: parse_number_field(entry, &["volume"]),
+            })
         })
         .collect();
 
     if points.is_empty() {
-        return Err(AppError::provider(
-            "provider_payload_invalid",
-            "Time series payload contains no valid close values.",
-        ));
+        return Err(AppError::provider("provider_payload_invalid").with_reason("no_valid_closes"));
     }
 
     points.reverse();
-    let start = points.first().map(|point| point.close).ok_or_else(|| {
-        AppError::provider(
-            "provider_payload_invalid",
-            "Time series payload missing start point.",
-        )
-    })?;
-    let end = points.last().map(|point| point.close).ok_or_else(|| {
-        AppError::provider(
-            "provider_payload_invalid",
-            "Time series payload missing end point.",
-        )
-    })?;
-    let min = points
-        .iter()
-        .fold(f64::INFINITY, |acc, point| acc.min(point.close));
-    let max = points
-        .iter()
-        .fold(f64::NEG_INFINITY, |acc, point| acc.max(point.close));
-    let last_updated_at = points
-        .last()
-        .map(|point| point.ts.clone())
-        .unwrap_or_else(|| unix_timestamp_secs().to_string());
 
-    Ok(SymbolPerformance {
+    Ok(PriceSeries {
         symbol: symbol.to_string(),
-        range,
+        interval,
         points,
-        min,
-        max,
-        start,
-        end,
-        last_updated_at,
-        status: QuoteStatus::Fresh,
+        exchange_timezone,
     })
 }
 
@@ -337,6 +369,46 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn read_api_credits_parses_the_credit_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CREDITS_USED_HEADER, "3".parse().unwrap());
+        headers.insert(CREDITS_LEFT_HEADER, " 797 ".parse().unwrap());
+
+        assert_eq!(
+            read_api_credits(&headers),
+            ApiCredits {
+                used: Some(3),
+                left: Some(797),
+            }
+        );
+        assert_eq!(read_api_credits(&HeaderMap::new()), ApiCredits::default());
+    }
+
+    #[test]
+    fn read_retry_after_accepts_seconds_and_dates() {
+        let now = DateTime::parse_from_rfc3339("2026-02-22T15:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(RETRY_AFTER, value.parse().unwrap());
+            headers
+        };
+
+        assert_eq!(read_retry_after(&headers("45"), now), Some(45));
+        assert_eq!(
+            read_retry_after(&headers("Sun, 22 Feb 2026 15:01:30 GMT"), now),
+            Some(90)
+        );
+        assert_eq!(
+            read_retry_after(&headers("Sun, 22 Feb 2026 14:00:00 GMT"), now),
+            Some(0)
+        );
+        assert_eq!(read_retry_after(&headers("soon"), now), None);
+        assert_eq!(read_retry_after(&HeaderMap::new(), now), None);
+    }
+
     #[test]
     fn map_provider_error_detects_invalid_key() {
         let payload = json!({ "status": "error", "message": "API key is incorrect." });
@@ -377,6 +449,26 @@ mod tests {
         assert_eq!(quote.status, QuoteStatus::Fresh);
     }
 
+    #[test]
+    fn parse_quote_payload_prefers_timestamp_then_exchange_datetime() {
+        let with_timestamp = json!({
+            "close": "191.25",
+            "datetime": "2026-02-22",
+            "timestamp": 1771754400
+        });
+        let quote = parse_quote_payload("AAPL", with_timestamp).unwrap();
+        assert_eq!(quote.last_updated_at, "2026-02-22T10:00:00Z");
+
+        let datetime_only = json!({
+            "close": "191.25",
+            "datetime": "2026-02-20 10:00:00",
+            "exchange_timezone": "America/New_York"
+        });
+        let quote = parse_quote_payload("AAPL", datetime_only).unwrap();
+        assert_eq!(quote.last_updated_at, "2026-02-20T15:00:00Z");
+        assert_eq!(quote.exchange_timezone.as_deref(), Some("America/New_York"));
+    }
+
     #[test]
     fn parse_quote_payload_requires_price() {
         let payload = json!({
@@ -389,7 +481,7 @@ mod tests {
     }
 
     #[test]
-    fn parse_symbol_performance_payload_maps_points_and_metrics() {
+    fn parse_time_series_payload_orders_points_oldest_first() {
         let payload = json!({
             "meta": { "exchange_timezone": "UTC" },
             "values": [
@@ -399,20 +491,60 @@ mod tests {
             ]
         });
 
-        let performance =
-            parse_symbol_performance_payload("AAPL", TimeRange::OneWeek, payload).unwrap();
-        assert_eq!(performance.points.len(), 3);
-        assert_eq!(performance.start, 200.0);
-        assert_eq!(performance.end, 201.0);
-        assert_eq!(performance.min, 199.0);
-        assert_eq!(performance.max, 201.0);
-        assert_eq!(performance.status, QuoteStatus::Fresh);
+        let series = parse_time_series_payload("AAPL", SeriesInterval::OneDay, payload).unwrap();
+        assert_eq!(series.points.len(), 3);
+        assert_eq!(series.points[0].close, 200.0);
+        assert_eq!(series.points[2].close, 201.0);
+        assert_eq!(series.interval, SeriesInterval::OneDay);
+    }
+
+    #[test]
+    fn parse_time_series_payload_normalizes_timestamps_to_utc() {
+        let payload = json!({
+            "meta": { "exchange_timezone": "America/New_York" },
+            "values": [
+                { "datetime": "2026-02-20 10:00:00", "close": "201.0" },
+                { "datetime": "2026-02-19 10:00:00", "close": "200.0" }
+            ]
+        });
+
+        let series = parse_time_series_payload("AAPL", SeriesInterval::OneHour, payload).unwrap();
+        assert_eq!(series.points[0].ts, "2026-02-19T15:00:00Z");
+        assert_eq!(series.points[1].ts, "2026-02-20T15:00:00Z");
+        assert_eq!(
+            series.exchange_timezone.as_deref(),
+            Some("America/New_York")
+        );
+    }
+
+    #[test]
+    fn parse_time_series_payload_keeps_ohlcv_when_present() {
+        let payload = json!({
+            "values": [
+                {
+                    "datetime": "2026-02-20",
+                    "open": "198.5",
+                    "high": "202.0",
+                    "low": "197.25",
+                    "close": "201.0",
+                    "volume": "5400000"
+                },
+                { "datetime": "2026-02-19", "close": "200.0" }
+            ]
+        });
+
+        let series = parse_time_series_payload("AAPL", SeriesInterval::OneDay, payload).unwrap();
+        assert_eq!(series.points[0].high, None);
+        assert_eq!(series.points[1].open, Some(198.5));
+        assert_eq!(series.points[1].high, Some(202.0));
+        assert_eq!(series.points[1].low, Some(197.25));
+        assert_eq!(series.points[1].volume, Some(5_400_000.0));
     }
 
     #[test]
-    fn parse_symbol_performance_payload_rejects_missing_values() {
+    fn parse_time_series_payload_rejects_missing_values() {
         let payload = json!({});
-        let result = parse_symbol_performance_payload("AAPL", TimeRange::OneWeek, payload);
+        let result = parse_time_series_payload("AAPL", SeriesInterval::OneDay, payload);
         assert!(result.is_err());
     }
 }
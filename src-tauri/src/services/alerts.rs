@@ -0,0 +1,150 @@
+use super::{Clock, MarketDataService, SystemClock};
+use crate::connectivity::ConnectivityMonitor;
+use crate::domain::{
+    AlertEvent, AlertRule, AppError, AppSettings, MovingAverages, QuoteSummary, TimeRange,
+    evaluate_alerts,
+};
+use crate::indicators::sma;
+use crate::persistence::AlertStore;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+/// Range whose daily closes back the moving averages; its bars cover the
+/// longest period a rule accepts.
+const MOVING_AVERAGE_RANGE: TimeRange = TimeRange::ThreeMonths;
+
+/// Evaluates the saved alert rules against a quote refresh. Emitting events
+/// and showing notifications is left to the caller.
+pub struct AlertService<'a> {
+    store: AlertStore,
+    market_data: MarketDataService<'a>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<'a> AlertService<'a> {
+    pub fn new(
+        store: AlertStore,
+        market_data: MarketDataService<'a>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            store,
+            market_data,
+            clock,
+        }
+    }
+
+    pub fn from_app(app: &'a AppHandle) -> Result<Self, AppError> {
+        Ok(Self::new(
+            AlertStore::from_app(app)?,
+            MarketDataService::from_app(app)?,
+            Arc::new(SystemClock),
+        ))
+    }
+
+    pub fn from_config_dir(
+        config_dir: &Path,
+        monitor: &'a ConnectivityMonitor,
+    ) -> Result<Self, AppError> {
+        Ok(Self::new(
+            AlertStore::new(config_dir.to_path_buf()),
+            MarketDataService::from_config_dir(config_dir, monitor)?,
+            Arc::new(SystemClock),
+        ))
+    }
+
+    /// Evaluates every enabled rule, saves the rules' trigger state and
+    /// records triggered alerts in the history. Rule symbols missing from
+    /// `quotes` are quoted here, and moving averages come from each symbol's
+    /// daily series, fetched when the cache is short or expired. Returns the
+    /// rules alongside the triggered alerts.
+    pub async fn evaluate(
+        &self,
+        settings: &AppSettings,
+        quotes: &[QuoteSummary],
+    ) -> Result<(Vec<AlertRule>, Vec<AlertEvent>), AppError> {
+        let mut rules = self.store.load_rules()?;
+        if rules.is_empty() {
+            return Ok((rules, Vec::new()));
+        }
+
+        let quoted: HashSet<&str> = quotes.iter().map(|quote| quote.symbol.as_str()).collect();
+        let mut missing: Vec<String> = Vec::new();
+        for rule in rules.iter().filter(|rule| rule.enabled) {
+            if !quoted.contains(rule.symbol.as_str()) && !missing.contains(&rule.symbol) {
+                missing.push(rule.symbol.clone());
+            }
+        }
+        let mut all_quotes = quotes.to_vec();
+        if !missing.is_empty() {
+            all_quotes.extend(
+                self.market_data
+                    .quotes_for_symbols(settings, &missing)
+                    .await?,
+            );
+        }
+
+        let moving_averages = self.load_moving_averages(settings, &rules).await?;
+        let before = rules.clone();
+        let events = evaluate_alerts(
+            &mut rules,
+            &all_quotes,
+            &moving_averages,
+            self.clock.now() as i64,
+        );
+        if rules != before {
+            self.store.save_rules(&rules)?;
+        }
+        if !events.is_empty() {
+            self.store.append_history(&events)?;
+        }
+
+        Ok((rules, events))
+    }
+
+    /// A symbol without bars leaves its crossing rules unevaluated; any other
+    /// failure fails the evaluation.
+    async fn load_moving_averages(
+        &self,
+        settings: &AppSettings,
+        rules: &[AlertRule],
+    ) -> Result<MovingAverages, AppError> {
+        let mut moving_averages = MovingAverages::new();
+        let mut closes_by_symbol: HashMap<String, Option<Vec<f64>>> = HashMap::new();
+
+        for rule in rules.iter().filter(|rule| rule.enabled) {
+            let Some(period) = rule.condition.moving_average_period() else {
+                continue;
+            };
+            let key = (rule.symbol.clone(), period);
+            if moving_averages.contains_key(&key) {
+                continue;
+            }
+
+            if !closes_by_symbol.contains_key(&rule.symbol) {
+                let closes = match self
+                    .market_data
+                    .load_symbol_performance(settings, &rule.symbol, MOVING_AVERAGE_RANGE, false)
+                    .await
+                {
+                    Ok(performance) => {
+                        Some(performance.points.iter().map(|point| point.close).collect())
+                    }
+                    Err(err) if err.is_symbol_scoped() => None,
+                    Err(err) => return Err(err),
+                };
+                closes_by_symbol.insert(rule.symbol.clone(), closes);
+            }
+
+            if let Some(Some(closes)) = closes_by_symbol.get(&rule.symbol)
+                && let Some(Some(average)) = sma(closes, period).last()
+            {
+                moving_averages.insert(key, *average);
+            }
+        }
+
+        Ok(moving_averages)
+    }
+}
@@ -0,0 +1,50 @@
+use super::{MarketDataStores, tracked_symbols};
+use crate::domain::{AppError, CacheInvalidationResult};
+use crate::persistence::config_dir_from_app;
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Cache maintenance that depends on which symbols the app still tracks.
+pub struct CacheService {
+    stores: MarketDataStores,
+}
+
+impl CacheService {
+    pub fn new(stores: MarketDataStores) -> Self {
+        Self { stores }
+    }
+
+    pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
+        Ok(Self::from_config_dir(&config_dir_from_app(app)?))
+    }
+
+    pub fn from_config_dir(config_dir: &Path) -> Self {
+        Self::new(MarketDataStores::from_config_dir(config_dir))
+    }
+
+    /// Deletes every cached quote and bar. Unreadable cache files are deleted
+    /// too, so this also recovers from a corrupted cache.
+    pub fn clear(&self) -> Result<(), AppError> {
+        self.stores.quote_cache.clear()?;
+        self.stores.timeseries.clear()
+    }
+
+    /// Drops cached quotes and bars for symbols that are neither on the
+    /// watchlist nor in the ledger, the same set a quote refresh keeps.
+    pub fn prune_untracked(&self) -> Result<CacheInvalidationResult, AppError> {
+        let watchlist = self.stores.watchlist.load()?;
+        let ledger = self.stores.ledger.load()?;
+        let keep = tracked_symbols(&watchlist, &ledger);
+
+        Ok(CacheInvalidationResult {
+            removed_quotes: self
+                .stores
+                .quote_cache
+                .remove_where(&mut |symbol| !keep.contains(symbol))?,
+            removed_timeseries: self
+                .stores
+                .timeseries
+                .remove_where(&mut |file| !keep.contains(file.symbol.as_str()))?,
+        })
+    }
+}
@@ -0,0 +1,301 @@
+use super::{Clock, SystemClock, default_provider, require_api_key, tracked_symbols};
+use crate::connectivity::ConnectivityMonitor;
+use crate::domain::{
+    AppError, AppSettings, OFFLINE_REASON, QuoteStatus, QuoteSummary, SeriesSource,
+    SymbolPerformance, TimeRange, now_rfc3339, unix_secs_to_rfc3339,
+};
+use crate::observability::{CacheKind, metrics};
+use crate::persistence::{
+    CacheLimits, LedgerRepository, LedgerStore, QuoteCacheMap, QuoteCacheRepository,
+    QuoteCacheStore, TimeSeriesCacheStore, TimeSeriesRepository, WatchlistRepository,
+    WatchlistStore, config_dir_from_app, covers_range, is_cache_fresh, is_timeseries_cache_fresh,
+    merge_series, missing_bar_count, to_cached_entry, to_range_performance, to_stale_performance,
+    to_stale_quote,
+};
+use crate::provider::MarketDataProvider;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// Stores the market data service reads and writes.
+pub struct MarketDataStores {
+    pub watchlist: Box<dyn WatchlistRepository>,
+    pub ledger: Box<dyn LedgerRepository>,
+    pub quote_cache: Box<dyn QuoteCacheRepository>,
+    pub timeseries: Box<dyn TimeSeriesRepository>,
+}
+
+impl MarketDataStores {
+    /// The file stores under `config_dir`.
+    pub fn from_config_dir(config_dir: &Path) -> Self {
+        Self {
+            watchlist: Box::new(WatchlistStore::new(config_dir.to_path_buf())),
+            ledger: Box::new(LedgerStore::new(config_dir.to_path_buf())),
+            quote_cache: Box::new(QuoteCacheStore::new(config_dir.to_path_buf())),
+            timeseries: Box::new(TimeSeriesCacheStore::new(config_dir.to_path_buf())),
+        }
+    }
+}
+
+/// Quotes and price history, served from the caches where possible and
+/// fetched from the provider otherwise. Alerts and webhooks are left to the
+/// caller.
+pub struct MarketDataService<'a> {
+    stores: MarketDataStores,
+    provider: Arc<dyn MarketDataProvider>,
+    clock: Arc<dyn Clock>,
+    monitor: &'a ConnectivityMonitor,
+}
+
+impl<'a> MarketDataService<'a> {
+    pub fn new(
+        stores: MarketDataStores,
+        provider: Arc<dyn MarketDataProvider>,
+        clock: Arc<dyn Clock>,
+        monitor: &'a ConnectivityMonitor,
+    ) -> Self {
+        Self {
+            stores,
+            provider,
+            clock,
+            monitor,
+        }
+    }
+
+    pub fn from_app(app: &'a AppHandle) -> Result<Self, AppError> {
+        let config_dir = config_dir_from_app(app)?;
+        Self::from_config_dir(&config_dir, app.state::<ConnectivityMonitor>().inner())
+    }
+
+    pub fn from_config_dir(
+        config_dir: &Path,
+        monitor: &'a ConnectivityMonitor,
+    ) -> Result<Self, AppError> {
+        Ok(Self::new(
+            MarketDataStores::from_config_dir(config_dir),
+            default_provider()?,
+            Arc::new(SystemClock),
+            monitor,
+        ))
+    }
+
+    /// Resolves quotes for the watchlist and saves the quote cache, trimmed
+    /// to the watchlist and ledger symbols. Returns no quotes for an empty
+    /// watchlist.
+    pub async fn refresh_quotes(
+        &self,
+        settings: &AppSettings,
+    ) -> Result<Vec<QuoteSummary>, AppError> {
+        self.require_api_key_unless_offline(settings)?;
+        let watchlist = self.stores.watchlist.load()?;
+        if watchlist.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut cache = self.stores.quote_cache.load()?;
+        let symbols: Vec<String> = watchlist.iter().map(|item| item.symbol.clone()).collect();
+        let quotes = self.resolve_quotes(settings, &symbols, &mut cache).await;
+
+        let ledger = self.stores.ledger.load()?;
+        let keep = tracked_symbols(&watchlist, &ledger);
+        cache.retain(|symbol, _| keep.contains(symbol.as_str()));
+        self.stores.quote_cache.save(&cache)?;
+
+        Ok(quotes)
+    }
+
+    /// Resolves quotes for `symbols` through the quote cache without
+    /// trimming it.
+    pub async fn quotes_for_symbols(
+        &self,
+        settings: &AppSettings,
+        symbols: &[String],
+    ) -> Result<Vec<QuoteSummary>, AppError> {
+        self.require_api_key_unless_offline(settings)?;
+        let mut cache = self.stores.quote_cache.load()?;
+        let quotes = self.resolve_quotes(settings, symbols, &mut cache).await;
+        self.stores.quote_cache.save(&cache)?;
+        Ok(quotes)
+    }
+
+    /// Offline mode serves only cached data, so it works without an API key.
+    fn require_api_key_unless_offline(&self, settings: &AppSettings) -> Result<(), AppError> {
+        if self
+            .monitor
+            .is_offline(settings.offline_mode, self.clock.now())
+        {
+            return Ok(());
+        }
+        require_api_key(settings)
+    }
+
+    /// Resolves one quote per symbol: fresh cache entries are reused,
+    /// everything else is fetched, and failed fetches fall back to stale
+    /// cache entries.
+    async fn resolve_quotes(
+        &self,
+        settings: &AppSettings,
+        symbols: &[String],
+        cache: &mut QuoteCacheMap,
+    ) -> Vec<QuoteSummary> {
+        let offline_error = offline_error("cached_quotes");
+        let now = self.clock.now();
+        let mut quotes = Vec::with_capacity(symbols.len());
+
+        for symbol in symbols {
+            if self.monitor.is_offline(settings.offline_mode, now) {
+                quotes.push(match cache.get(symbol) {
+                    Some(entry) => to_stale_quote(entry, &offline_error),
+                    None => to_error_quote(symbol, offline_error.clone(), now),
+                });
+                continue;
+            }
+
+            if let Some(entry) = cache.get(symbol)
+                && is_cache_fresh(entry.cached_at, now)
+            {
+                metrics().record_cache(CacheKind::Quotes, true);
+                let mut fresh_quote = entry.quote.clone();
+                fresh_quote.status = QuoteStatus::Fresh;
+                fresh_quote.error_code = None;
+                fresh_quote.error_message = None;
+                quotes.push(fresh_quote);
+                continue;
+            }
+
+            metrics().record_cache(CacheKind::Quotes, false);
+
+            let fetched = self.provider.fetch_quote(symbol, &settings.api_key).await;
+            self.monitor.record_outcome(&fetched, self.clock.now());
+            match fetched {
+                Ok(mut quote) => {
+                    quote.status = QuoteStatus::Fresh;
+                    quote.error_code = None;
+                    quote.error_message = None;
+                    cache.insert(symbol.clone(), to_cached_entry(quote.clone(), now));
+                    quotes.push(quote);
+                }
+                Err(err) => {
+                    if let Some(entry) = cache.get(symbol) {
+                        quotes.push(to_stale_quote(entry, &err));
+                    } else {
+                        quotes.push(to_error_quote(symbol, err, now));
+                    }
+                }
+            }
+        }
+
+        quotes
+    }
+
+    /// Serves `range` for an already normalized symbol from the canonical
+    /// timeseries cache, fetching only the missing tail when the cache is
+    /// short or expired. Falls back to stale bars when the fetch fails or the
+    /// app is offline.
+    pub async fn load_symbol_performance(
+        &self,
+        settings: &AppSettings,
+        normalized_symbol: &str,
+        range: TimeRange,
+        force_refresh: bool,
+    ) -> Result<SymbolPerformance, AppError> {
+        self.load_symbol_performance_with_source(settings, normalized_symbol, range, force_refresh)
+            .await
+            .map(|(performance, _)| performance)
+    }
+
+    /// Same as `load_symbol_performance`, also reporting where the bars came
+    /// from.
+    pub async fn load_symbol_performance_with_source(
+        &self,
+        settings: &AppSettings,
+        normalized_symbol: &str,
+        range: TimeRange,
+        force_refresh: bool,
+    ) -> Result<(SymbolPerformance, SeriesSource), AppError> {
+        let cache_store = &self.stores.timeseries;
+        let now = self.clock.now();
+        let cached_entry = cache_store.load(normalized_symbol, range.interval())?;
+
+        // Offline mode never reaches the provider, so it needs no API key.
+        if self.monitor.is_offline(settings.offline_mode, now) {
+            let no_cache_error = || offline_error("no_cached_chart");
+            let entry = cached_entry.as_ref().ok_or_else(no_cache_error)?;
+            let offline_performance =
+                to_stale_performance(entry, range, &offline_error("cached_chart"))
+                    .ok_or_else(no_cache_error)?;
+            cache_store.mark_accessed(entry, now)?;
+            return Ok((offline_performance, SeriesSource::StaleCache));
+        }
+
+        require_api_key(settings)?;
+
+        if !force_refresh
+            && let Some(entry) = cached_entry.as_ref()
+            && covers_range(entry, range)
+            && is_timeseries_cache_fresh(entry.cached_at, now)
+            && let Some(fresh_performance) = to_range_performance(entry, range, QuoteStatus::Fresh)
+        {
+            metrics().record_cache(CacheKind::Timeseries, true);
+            cache_store.mark_accessed(entry, now)?;
+            return Ok((fresh_performance, SeriesSource::Cache));
+        }
+
+        metrics().record_cache(CacheKind::Timeseries, false);
+
+        let outputsize = missing_bar_count(cached_entry.as_ref(), range, now);
+        let fetched = self
+            .provider
+            .fetch_time_series(
+                normalized_symbol,
+                range.interval(),
+                outputsize,
+                &settings.api_key,
+            )
+            .await;
+        self.monitor.record_outcome(&fetched, self.clock.now());
+
+        match fetched {
+            Ok(series) => {
+                let entry = merge_series(cached_entry.as_ref(), series, now);
+                cache_store.save(&entry)?;
+                cache_store.evict(CacheLimits::from_settings(settings))?;
+                to_range_performance(&entry, range, QuoteStatus::Fresh)
+                    .map(|performance| (performance, SeriesSource::Fetched))
+                    .ok_or_else(|| {
+                        AppError::provider("provider_payload_invalid").with_reason("no_values")
+                    })
+            }
+            Err(err) => match cached_entry.as_ref() {
+                Some(entry) => {
+                    let stale = to_stale_performance(entry, range, &err).ok_or(err)?;
+                    cache_store.mark_accessed(entry, now)?;
+                    Ok((stale, SeriesSource::StaleCache))
+                }
+                None => Err(err),
+            },
+        }
+    }
+}
+
+fn to_error_quote(symbol: &str, err: AppError, now: u64) -> QuoteSummary {
+    QuoteSummary {
+        symbol: symbol.to_string(),
+        price: 0.0,
+        change_abs: None,
+        change_pct: None,
+        currency: None,
+        last_updated_at: i64::try_from(now)
+            .ok()
+            .and_then(unix_secs_to_rfc3339)
+            .unwrap_or_else(now_rfc3339),
+        exchange_timezone: None,
+        status: QuoteStatus::Error,
+        error_code: Some(err.code),
+        error_message: Some(err.message),
+    }
+}
+
+fn offline_error(reason: &str) -> AppError {
+    AppError::provider(OFFLINE_REASON).with_reason(reason)
+}
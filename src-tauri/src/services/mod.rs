@@ -0,0 +1,67 @@
+//! Command logic behind the Tauri commands and the CLI. Services are built
+//! from injected stores, a provider and a clock, so they run without an
+//! `AppHandle`; `from_app` and `from_config_dir` wire up the real ones.
+
+mod alerts;
+mod cache;
+mod market_data;
+mod settings;
+mod watchlist;
+mod webhooks;
+
+pub use alerts::AlertService;
+pub use cache::CacheService;
+pub use market_data::{MarketDataService, MarketDataStores};
+pub use settings::SettingsService;
+pub use watchlist::WatchlistService;
+pub use webhooks::WebhookService;
+pub(crate) use webhooks::webhook_not_found;
+
+use crate::domain::{AppError, AppSettings, Transaction, WatchlistItem, unix_timestamp_secs};
+use crate::http::build_http_client;
+use crate::provider::{MarketDataProvider, TwelveDataAdapter};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+const PROVIDER_REQUEST_TIMEOUT_SECONDS: u64 = 8;
+
+/// Source of the current time in unix seconds, used for cache freshness and
+/// the connectivity circuit.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> u64;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        unix_timestamp_secs()
+    }
+}
+
+/// The Twelve Data adapter every service uses outside of tests.
+pub fn default_provider() -> Result<Arc<dyn MarketDataProvider>, AppError> {
+    let client = build_http_client(PROVIDER_REQUEST_TIMEOUT_SECONDS)?;
+    Ok(Arc::new(TwelveDataAdapter::new(client)))
+}
+
+pub fn require_api_key(settings: &AppSettings) -> Result<(), AppError> {
+    if settings.api_key.trim().is_empty() {
+        return Err(AppError::validation("invalid_settings").with_reason("api_key_missing"));
+    }
+    Ok(())
+}
+
+/// Symbols whose cached quotes and bars are worth keeping: everything on the
+/// watchlist or in the ledger.
+fn tracked_symbols<'a>(
+    watchlist: &'a [WatchlistItem],
+    ledger: &'a [Transaction],
+) -> HashSet<&'a str> {
+    watchlist
+        .iter()
+        .map(|item| item.symbol.as_str())
+        .chain(ledger.iter().map(|transaction| transaction.symbol.as_str()))
+        .collect()
+}
@@ -0,0 +1,135 @@
+use super::{Clock, default_provider, require_api_key};
+use crate::connectivity::ConnectivityMonitor;
+use crate::domain::{
+    ApiCredits, AppError, AppSettings, AppSettingsInput, EndpointStatus, OFFLINE_REASON,
+    ProviderEndpoint, ProviderStatusReport, ProviderTestResult, now_rfc3339,
+};
+use crate::observability::metrics;
+use crate::persistence::{
+    CacheLimits, SettingsRepository, SettingsStore, TimeSeriesCacheStore, TimeSeriesRepository,
+    config_dir_from_app,
+};
+use crate::provider::MarketDataProvider;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::AppHandle;
+
+pub struct SettingsService {
+    settings: Box<dyn SettingsRepository>,
+    timeseries: Box<dyn TimeSeriesRepository>,
+    provider: Arc<dyn MarketDataProvider>,
+}
+
+impl SettingsService {
+    pub fn new(
+        settings: Box<dyn SettingsRepository>,
+        timeseries: Box<dyn TimeSeriesRepository>,
+        provider: Arc<dyn MarketDataProvider>,
+    ) -> Self {
+        Self {
+            settings,
+            timeseries,
+            provider,
+        }
+    }
+
+    pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
+        Self::from_config_dir(&config_dir_from_app(app)?)
+    }
+
+    pub fn from_config_dir(config_dir: &Path) -> Result<Self, AppError> {
+        Ok(Self::new(
+            Box::new(SettingsStore::new(config_dir.to_path_buf())),
+            Box::new(TimeSeriesCacheStore::new(config_dir.to_path_buf())),
+            default_provider()?,
+        ))
+    }
+
+    pub fn load(&self) -> Result<AppSettings, AppError> {
+        self.settings.load()
+    }
+
+    /// Validates and saves `input`, then evicts chart cache entries beyond
+    /// the new cache limits. Returns the saved settings and the number of
+    /// evicted entries.
+    pub fn save(&self, input: AppSettingsInput) -> Result<(AppSettings, usize), AppError> {
+        let saved = self.settings.save(input)?;
+        let evicted = self.timeseries.evict(CacheLimits::from_settings(&saved))?;
+        Ok((saved, evicted.len()))
+    }
+
+    /// Sends one request with the saved API key to check it works.
+    pub async fn test_connection(&self) -> Result<ProviderTestResult, AppError> {
+        let settings = self.settings.load()?;
+        require_api_key(&settings)?;
+        self.provider.test_connection(&settings.api_key).await
+    }
+
+    /// Probes the quote, time series and symbol search endpoints and reports
+    /// their latency, the credits left, the connectivity circuit and the last
+    /// error recorded per endpoint before this check. In manual offline mode
+    /// nothing is sent and no latency is reported.
+    pub async fn provider_status(
+        &self,
+        monitor: &ConnectivityMonitor,
+        clock: &dyn Clock,
+    ) -> Result<ProviderStatusReport, AppError> {
+        let settings = self.settings.load()?;
+        require_api_key(&settings)?;
+
+        // Taken before probing, so a failed probe is reported once, as the
+        // endpoint's current error, and not again as its last error.
+        let recent_errors = metrics().recent_provider_errors();
+        let mut credits = ApiCredits::default();
+        let mut endpoints = Vec::with_capacity(ProviderEndpoint::ALL.len());
+        for endpoint in ProviderEndpoint::ALL {
+            let (outcome, latency_ms) = if settings.offline_mode {
+                let err = AppError::provider(OFFLINE_REASON).with_reason("not_contacted");
+                (Err(err), None)
+            } else {
+                let started = Instant::now();
+                let outcome = self
+                    .provider
+                    .probe_endpoint(endpoint, &settings.api_key)
+                    .await;
+                monitor.record_outcome(&outcome, clock.now());
+                (outcome, Some(started.elapsed().as_millis() as u64))
+            };
+
+            if let Ok(reported) = &outcome {
+                credits.used = reported.used.or(credits.used);
+                credits.left = reported.left.or(credits.left);
+            }
+            let last_error = recent_errors
+                .iter()
+                .rev()
+                .find(|record| record.endpoint == endpoint.as_str())
+                .cloned();
+            let (error_code, error_message) = match outcome {
+                Ok(_) => (None, None),
+                Err(err) => (Some(err.code), Some(err.message)),
+            };
+            endpoints.push(EndpointStatus {
+                endpoint,
+                ok: error_code.is_none(),
+                latency_ms,
+                error_code,
+                error_message,
+                last_error,
+            });
+        }
+
+        let now = clock.now();
+        let status = monitor.status(settings.offline_mode, now);
+        Ok(ProviderStatusReport {
+            provider: settings.provider,
+            checked_at: now_rfc3339(),
+            circuit: monitor.circuit_state(now),
+            consecutive_failures: status.consecutive_failures,
+            retry_at: status.retry_at,
+            credits,
+            endpoints,
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use crate::domain::{AppError, WatchlistItem, normalize_symbol};
+use crate::persistence::{WatchlistRepository, WatchlistStore, config_dir_from_app};
+use std::path::Path;
+use tauri::AppHandle;
+
+pub struct WatchlistService {
+    store: Box<dyn WatchlistRepository>,
+}
+
+impl WatchlistService {
+    pub fn new(store: Box<dyn WatchlistRepository>) -> Self {
+        Self { store }
+    }
+
+    pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
+        Ok(Self::from_config_dir(&config_dir_from_app(app)?))
+    }
+
+    pub fn from_config_dir(config_dir: &Path) -> Self {
+        Self::new(Box::new(WatchlistStore::new(config_dir.to_path_buf())))
+    }
+
+    pub fn list(&self) -> Result<Vec<WatchlistItem>, AppError> {
+        self.store.load()
+    }
+
+    /// Adds `symbol` to the watchlist, rejecting duplicates.
+    pub fn add(&self, symbol: &str) -> Result<Vec<WatchlistItem>, AppError> {
+        let normalized_symbol = normalize_symbol(symbol)?;
+        let mut watchlist = self.store.load()?;
+
+        if watchlist
+            .iter()
+            .any(|item| item.symbol == normalized_symbol)
+        {
+            return Err(
+                AppError::validation("symbol_exists").with_arg("symbol", &normalized_symbol)
+            );
+        }
+
+        watchlist.push(WatchlistItem {
+            symbol: normalized_symbol,
+            display_name: None,
+            pinned: None,
+        });
+
+        self.store.save(&watchlist)
+    }
+
+    pub fn remove(&self, symbol: &str) -> Result<Vec<WatchlistItem>, AppError> {
+        let normalized_symbol = normalize_symbol(symbol)?;
+        let mut watchlist = self.store.load()?;
+        let initial_len = watchlist.len();
+        watchlist.retain(|item| item.symbol != normalized_symbol);
+
+        if watchlist.len() == initial_len {
+            return Err(
+                AppError::validation("symbol_not_found").with_arg("symbol", &normalized_symbol)
+            );
+        }
+
+        self.store.save(&watchlist)
+    }
+}
@@ -0,0 +1,121 @@
+use super::{Clock, SystemClock};
+use crate::connectivity::ConnectivityMonitor;
+use crate::domain::{
+    AlertCondition, AlertEvent, AppError, AppSettings, OFFLINE_REASON, WebhookDelivery,
+    WebhookTrigger, now_rfc3339,
+};
+use crate::http::build_http_client;
+use crate::persistence::{DeliveryLogLock, WebhookStore};
+use crate::webhooks::{RetryPolicy, WEBHOOK_REQUEST_TIMEOUT_SECONDS, deliver_webhook};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// Delivers webhooks and records each delivery in the log. Nothing is sent
+/// while the app is offline, by choice or because the connectivity circuit is
+/// open.
+pub struct WebhookService<'a> {
+    store: WebhookStore,
+    log_lock: &'a DeliveryLogLock,
+    monitor: &'a ConnectivityMonitor,
+    clock: Arc<dyn Clock>,
+}
+
+impl<'a> WebhookService<'a> {
+    pub fn new(
+        store: WebhookStore,
+        log_lock: &'a DeliveryLogLock,
+        monitor: &'a ConnectivityMonitor,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            store,
+            log_lock,
+            monitor,
+            clock,
+        }
+    }
+
+    pub fn from_app(app: &'a AppHandle) -> Result<Self, AppError> {
+        Ok(Self::new(
+            WebhookStore::from_app(app)?,
+            app.state::<DeliveryLogLock>().inner(),
+            app.state::<ConnectivityMonitor>().inner(),
+            Arc::new(SystemClock),
+        ))
+    }
+
+    /// Posts every trigger to the enabled webhooks that want it. Returns no
+    /// deliveries when offline or when no webhook wants the triggers.
+    pub async fn dispatch(
+        &self,
+        settings: &AppSettings,
+        triggers: &[WebhookTrigger],
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        if self.is_offline(settings) {
+            return Ok(Vec::new());
+        }
+
+        let webhooks = self.store.load_webhooks()?;
+        if !triggers
+            .iter()
+            .any(|trigger| webhooks.iter().any(|webhook| trigger.wanted_by(webhook)))
+        {
+            return Ok(Vec::new());
+        }
+
+        let client = build_http_client(WEBHOOK_REQUEST_TIMEOUT_SECONDS)?;
+        let mut deliveries = Vec::new();
+        for trigger in triggers {
+            for webhook in webhooks.iter().filter(|webhook| trigger.wanted_by(webhook)) {
+                deliveries
+                    .push(deliver_webhook(&client, webhook, trigger, RetryPolicy::default()).await);
+            }
+        }
+
+        self.store.append_deliveries(self.log_lock, &deliveries)?;
+        Ok(deliveries)
+    }
+
+    /// Sends a sample alert to one webhook right away, whether or not it is
+    /// enabled, and logs the delivery like any other. Fails while offline.
+    pub async fn send_test(
+        &self,
+        settings: &AppSettings,
+        id: &str,
+    ) -> Result<WebhookDelivery, AppError> {
+        let webhook = self
+            .store
+            .load_webhooks()?
+            .into_iter()
+            .find(|webhook| webhook.id == id)
+            .ok_or_else(|| webhook_not_found(id))?;
+        if self.is_offline(settings) {
+            return Err(AppError::provider(OFFLINE_REASON).with_reason("webhook_not_sent"));
+        }
+
+        let client = build_http_client(WEBHOOK_REQUEST_TIMEOUT_SECONDS)?;
+        let trigger = WebhookTrigger::Alert(AlertEvent {
+            id: "test".to_string(),
+            rule_id: "test".to_string(),
+            symbol: "TEST".to_string(),
+            condition: AlertCondition::PriceAbove { price: 100.0 },
+            price: 101.0,
+            reference_value: None,
+            message: "Test alert from Stocks.".to_string(),
+            triggered_at: now_rfc3339(),
+        });
+        let delivery = deliver_webhook(&client, &webhook, &trigger, RetryPolicy::default()).await;
+        self.store
+            .append_deliveries(self.log_lock, std::slice::from_ref(&delivery))?;
+        Ok(delivery)
+    }
+
+    fn is_offline(&self, settings: &AppSettings) -> bool {
+        self.monitor
+            .is_offline(settings.offline_mode, self.clock.now())
+    }
+}
+
+pub(crate) fn webhook_not_found(id: &str) -> AppError {
+    AppError::validation("webhook_not_found").with_arg("id", id)
+}
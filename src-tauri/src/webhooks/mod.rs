@@ -0,0 +1,257 @@
+use crate::domain::{Webhook, WebhookDelivery, WebhookTrigger, now_rfc3339, render_webhook_body};
+use reqwest::StatusCode;
+use reqwest::header::CONTENT_TYPE;
+use std::time::Duration;
+
+pub const WEBHOOK_REQUEST_TIMEOUT_SECONDS: u64 = 10;
+
+/// How often a failed delivery is retried. The wait doubles after every
+/// failed attempt, up to `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait after `failed_attempts` failures before trying again.
+    pub fn backoff(&self, failed_attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failed_attempts.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Posts `trigger` to `webhook`, retrying transport failures, rate limits and
+/// server errors. Other client errors are not retried. The outcome is
+/// always returned as a delivery log entry, never as an error.
+pub async fn deliver_webhook(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    trigger: &WebhookTrigger,
+    policy: RetryPolicy,
+) -> WebhookDelivery {
+    let mut delivery = WebhookDelivery {
+        id: uuid::Uuid::new_v4().to_string(),
+        webhook_id: webhook.id.clone(),
+        event: trigger.event_name().to_string(),
+        attempts: 0,
+        status_code: None,
+        success: false,
+        error: None,
+        started_at: now_rfc3339(),
+        finished_at: String::new(),
+    };
+
+    let body = match render_webhook_body(
+        webhook.body_template.as_deref(),
+        trigger,
+        &delivery.started_at,
+    ) {
+        Ok(body) => body,
+        Err(err) => {
+            delivery.error = Some(err.message);
+            delivery.finished_at = now_rfc3339();
+            return delivery;
+        }
+    };
+
+    while delivery.attempts < policy.max_attempts {
+        if delivery.attempts > 0 {
+            tokio::time::sleep(policy.backoff(delivery.attempts)).await;
+        }
+        delivery.attempts += 1;
+
+        let mut request = client
+            .post(&webhook.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.clone());
+        for (name, value) in &webhook.headers {
+            request = request.header(name, value);
+        }
+
+        match request.send().await {
+            Ok(response) => {
+                let status = response.status();
+                delivery.status_code = Some(status.as_u16());
+                if status.is_success() {
+                    delivery.success = true;
+                    delivery.error = None;
+                    break;
+                }
+
+                delivery.error = Some(format!("Webhook answered with HTTP {}.", status.as_u16()));
+                if !status.is_server_error() && status != StatusCode::TOO_MANY_REQUESTS {
+                    break;
+                }
+            }
+            Err(err) => {
+                delivery.status_code = None;
+                delivery.error = Some(format!("Webhook request failed: {}", err.without_url()));
+            }
+        }
+    }
+
+    delivery.finished_at = now_rfc3339();
+    delivery
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{AlertCondition, AlertEvent};
+    use std::collections::BTreeMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Answers one request per status in `statuses` and sends each raw request
+    /// back through the channel.
+    fn serve(statuses: Vec<u16>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+                sender.send(request).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                )
+                .unwrap();
+            }
+        });
+
+        (url, receiver)
+    }
+
+    fn webhook(url: &str) -> Webhook {
+        Webhook {
+            id: "hook-1".to_string(),
+            name: "Chat".to_string(),
+            url: url.to_string(),
+            headers: BTreeMap::from([("X-Token".to_string(), "abc".to_string())]),
+            body_template: Some(r#"{"text": {{message}}}"#.to_string()),
+            on_alert: true,
+            on_refresh: false,
+            enabled: true,
+            created_at: "2026-02-22T12:00:00Z".to_string(),
+        }
+    }
+
+    fn alert() -> WebhookTrigger {
+        WebhookTrigger::Alert(AlertEvent {
+            id: "event-1".to_string(),
+            rule_id: "rule-1".to_string(),
+            symbol: "AAPL".to_string(),
+            condition: AlertCondition::PriceAbove { price: 200.0 },
+            price: 201.0,
+            reference_value: None,
+            message: "AAPL rose above 200.00 (now 201.00).".to_string(),
+            triggered_at: "2026-02-22T15:00:00Z".to_string(),
+        })
+    }
+
+    fn local_client() -> reqwest::Client {
+        reqwest::Client::builder().no_proxy().build().unwrap()
+    }
+
+    fn quick_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+        }
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried_until_delivered() {
+        let (url, requests) = serve(vec![503, 200]);
+        let client = local_client();
+
+        let delivery = deliver_webhook(&client, &webhook(&url), &alert(), quick_policy()).await;
+
+        assert!(delivery.success);
+        assert_eq!(delivery.attempts, 2);
+        assert_eq!(delivery.status_code, Some(200));
+        assert_eq!(delivery.event, "alert");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /hook "));
+        assert!(request.to_ascii_lowercase().contains("x-token: abc"));
+        assert!(request.ends_with(r#"{"text": "AAPL rose above 200.00 (now 201.00)."}"#));
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (url, _requests) = serve(vec![404]);
+        let client = local_client();
+
+        let delivery = deliver_webhook(&client, &webhook(&url), &alert(), quick_policy()).await;
+
+        assert!(!delivery.success);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.status_code, Some(404));
+    }
+
+    #[tokio::test]
+    async fn unreachable_hosts_use_every_attempt() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let client = local_client();
+        let url = format!("http://127.0.0.1:{port}/hook");
+
+        let delivery = deliver_webhook(&client, &webhook(&url), &alert(), quick_policy()).await;
+
+        assert!(!delivery.success);
+        assert_eq!(delivery.attempts, 3);
+        assert_eq!(delivery.status_code, None);
+        assert!(delivery.error.is_some());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(10), Duration::from_secs(8));
+    }
+}
@@ -0,0 +1,65 @@
+//! Runs the `stocks` binary against a temp config dir set through
+//! `STOCKS_CONFIG_DIR`. Nothing here reaches the provider.
+
+use serde_json::Value;
+use std::path::Path;
+use std::process::{Command, Output};
+use tauri_appy_lib::persistence::CONFIG_DIR_ENV;
+
+fn stocks(config_dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_stocks"))
+        .env(CONFIG_DIR_ENV, config_dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn watchlist_edits_print_text_or_json() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let added = stocks(dir.path(), &["--json", "watchlist", "add", "aapl"]);
+    assert!(added.status.success());
+    let watchlist: Value = serde_json::from_str(&stdout(&added)).unwrap();
+    assert_eq!(watchlist[0]["symbol"], "AAPL");
+
+    let listed = stocks(dir.path(), &["watchlist", "list"]);
+    assert!(listed.status.success());
+    assert_eq!(stdout(&listed), "AAPL\n");
+    assert!(dir.path().join("watchlist.json").exists());
+}
+
+#[test]
+fn failures_exit_non_zero_with_the_error() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let json = stocks(dir.path(), &["--json", "quotes"]);
+    assert!(!json.status.success());
+    let err: Value = serde_json::from_str(&stdout(&json)).unwrap();
+    assert_eq!(err["code"], "invalid_settings");
+    assert_eq!(err["reason"], "api_key_missing");
+
+    let text = stocks(dir.path(), &["quotes"]);
+    assert!(!text.status.success());
+    assert!(stdout(&text).is_empty());
+    let stderr = String::from_utf8(text.stderr).unwrap();
+    assert!(stderr.starts_with("error: "));
+    assert!(stderr.contains("(invalid_settings)"));
+}
+
+#[test]
+fn cache_clear_reports_in_text_mode_only() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let text = stocks(dir.path(), &["cache", "clear"]);
+    assert!(text.status.success());
+    assert_eq!(stdout(&text), "Cache cleared.\n");
+
+    let json = stocks(dir.path(), &["--json", "cache", "clear"]);
+    assert!(json.status.success());
+    assert!(stdout(&json).is_empty());
+}
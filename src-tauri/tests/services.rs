@@ -0,0 +1,799 @@
+//! Runs the services end to end against the file stores in a temp dir, with a
+//! mock provider and a clock the tests move by hand.
+
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri_appy_lib::connectivity::ConnectivityMonitor;
+use tauri_appy_lib::domain::{
+    AlertCondition, AlertRuleInput, ApiCredits, AppError, AppProvider, AppSettings,
+    AppSettingsInput, OFFLINE_REASON, PricePoint, PriceSeries, ProviderEndpoint,
+    ProviderTestResult, QuoteStatus, QuoteSummary, SeriesInterval, SeriesSource, TimeRange,
+    Transaction, TransactionKind, Webhook, WebhookTrigger, unix_secs_to_rfc3339,
+    validate_alert_rule,
+};
+use tauri_appy_lib::observability::metrics;
+use tauri_appy_lib::persistence::{
+    AlertStore, DeliveryLogLock, LedgerStore, QuoteCacheStore, SettingsStore, TimeSeriesCacheStore,
+    WebhookStore,
+};
+use tauri_appy_lib::provider::MarketDataProvider;
+use tauri_appy_lib::services::{
+    AlertService, CacheService, Clock, MarketDataService, MarketDataStores, SettingsService,
+    WatchlistService, WebhookService,
+};
+use tempfile::TempDir;
+
+const START: u64 = 1_760_000_000;
+
+struct TestClock(AtomicU64);
+
+impl TestClock {
+    fn advance(&self, seconds: u64) {
+        self.0.fetch_add(seconds, Ordering::SeqCst);
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Serves made-up quotes and daily bars ending at the test clock, and records
+/// every request.
+struct MockProvider {
+    clock: Arc<TestClock>,
+    failing: AtomicBool,
+    calls: Mutex<Vec<String>>,
+}
+
+impl MockProvider {
+    fn new(clock: Arc<TestClock>) -> Self {
+        Self {
+            clock,
+            failing: AtomicBool::new(false),
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn fail(&self) {
+        self.failing.store(true, Ordering::SeqCst);
+    }
+
+    fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn record(&self, call: String) -> Result<(), AppError> {
+        self.calls.lock().unwrap().push(call);
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(AppError::provider("provider_unavailable"));
+        }
+        Ok(())
+    }
+
+    fn timestamp(&self, secs: u64) -> String {
+        unix_secs_to_rfc3339(secs as i64).unwrap()
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for MockProvider {
+    async fn test_connection(&self, _api_key: &str) -> Result<ProviderTestResult, AppError> {
+        self.record("test_connection".to_string())?;
+        Ok(ProviderTestResult {
+            ok: true,
+            provider: AppProvider::Twelvedata,
+            message: "Connected.".to_string(),
+        })
+    }
+
+    async fn fetch_quote(&self, symbol: &str, _api_key: &str) -> Result<QuoteSummary, AppError> {
+        self.record(format!("quote:{symbol}"))?;
+        Ok(QuoteSummary {
+            symbol: symbol.to_string(),
+            price: 100.0,
+            change_abs: Some(1.0),
+            change_pct: Some(1.0),
+            currency: Some("USD".to_string()),
+            last_updated_at: self.timestamp(self.clock.now()),
+            exchange_timezone: None,
+            status: QuoteStatus::Fresh,
+            error_code: None,
+            error_message: None,
+        })
+    }
+
+    async fn fetch_time_series(
+        &self,
+        symbol: &str,
+        interval: SeriesInterval,
+        outputsize: usize,
+        _api_key: &str,
+    ) -> Result<PriceSeries, AppError> {
+        self.record(format!("series:{symbol}:{outputsize}"))?;
+        let now = self.clock.now();
+        let points = (0..outputsize as u64)
+            .rev()
+            .map(|age| {
+                let ts = now - age * interval.seconds();
+                PricePoint::new(self.timestamp(ts), 100.0 + (ts % 7) as f64)
+            })
+            .collect();
+        Ok(PriceSeries {
+            symbol: symbol.to_string(),
+            interval,
+            points,
+            exchange_timezone: Some("America/New_York".to_string()),
+        })
+    }
+
+    async fn probe_endpoint(
+        &self,
+        endpoint: ProviderEndpoint,
+        _api_key: &str,
+    ) -> Result<ApiCredits, AppError> {
+        // Like the real adapter, every probe lands in the provider metrics.
+        let result = self
+            .record(format!("probe:{}", endpoint.as_str()))
+            .map(|()| ApiCredits {
+                used: Some(1),
+                left: Some(7),
+            });
+        metrics().record_provider_call(endpoint.as_str(), &result);
+        result
+    }
+}
+
+struct Harness {
+    dir: TempDir,
+    clock: Arc<TestClock>,
+    provider: Arc<MockProvider>,
+    monitor: ConnectivityMonitor,
+    delivery_log_lock: DeliveryLogLock,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let clock = Arc::new(TestClock(AtomicU64::new(START)));
+        Self {
+            dir: tempfile::tempdir().unwrap(),
+            provider: Arc::new(MockProvider::new(clock.clone())),
+            clock,
+            monitor: ConnectivityMonitor::new(),
+            delivery_log_lock: DeliveryLogLock::new(),
+        }
+    }
+
+    fn config_dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    fn market_data(&self) -> MarketDataService<'_> {
+        MarketDataService::new(
+            MarketDataStores::from_config_dir(self.config_dir()),
+            self.provider.clone(),
+            self.clock.clone(),
+            &self.monitor,
+        )
+    }
+
+    fn alerts(&self) -> AlertService<'_> {
+        AlertService::new(self.alert_store(), self.market_data(), self.clock.clone())
+    }
+
+    fn alert_store(&self) -> AlertStore {
+        AlertStore::new(self.config_dir().to_path_buf())
+    }
+
+    fn add_alert_rule(&self, symbol: &str, condition: AlertCondition) {
+        let store = self.alert_store();
+        let mut rules = store.load_rules().unwrap();
+        let input = AlertRuleInput {
+            symbol: symbol.to_string(),
+            condition,
+            enabled: None,
+            notify: None,
+            cooldown_seconds: Some(0),
+        };
+        let created_at = unix_secs_to_rfc3339(START as i64).unwrap();
+        rules
+            .push(validate_alert_rule(format!("rule-{}", rules.len()), input, created_at).unwrap());
+        store.save_rules(&rules).unwrap();
+    }
+
+    fn webhook_store(&self) -> WebhookStore {
+        WebhookStore::new(self.config_dir().to_path_buf())
+    }
+
+    fn webhooks(&self) -> WebhookService<'_> {
+        WebhookService::new(
+            self.webhook_store(),
+            &self.delivery_log_lock,
+            &self.monitor,
+            self.clock.clone(),
+        )
+    }
+
+    fn watchlist(&self) -> WatchlistService {
+        WatchlistService::from_config_dir(self.config_dir())
+    }
+
+    fn settings_service(&self) -> SettingsService {
+        SettingsService::new(
+            Box::new(SettingsStore::new(self.config_dir().to_path_buf())),
+            Box::new(TimeSeriesCacheStore::new(self.config_dir().to_path_buf())),
+            self.provider.clone(),
+        )
+    }
+}
+
+fn settings() -> AppSettings {
+    AppSettings {
+        api_key: "test-key".to_string(),
+        ..AppSettings::default()
+    }
+}
+
+#[tokio::test]
+async fn refresh_quotes_serves_fresh_cache_entries_until_they_expire() {
+    let harness = Harness::new();
+    harness.watchlist().add("aapl").unwrap();
+    harness.watchlist().add("MSFT").unwrap();
+
+    let quotes = harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+    assert_eq!(quotes.len(), 2);
+    assert!(
+        quotes
+            .iter()
+            .all(|quote| quote.status == QuoteStatus::Fresh)
+    );
+    assert_eq!(harness.provider.calls(), ["quote:AAPL", "quote:MSFT"]);
+
+    harness.clock.advance(QuoteCacheStore::ttl_seconds());
+    harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+    assert_eq!(harness.provider.calls().len(), 2);
+
+    harness.clock.advance(1);
+    harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+    assert_eq!(harness.provider.calls().len(), 4);
+}
+
+#[tokio::test]
+async fn refresh_quotes_falls_back_to_stale_quotes_when_the_provider_fails() {
+    let harness = Harness::new();
+    harness.watchlist().add("AAPL").unwrap();
+    harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+
+    harness.clock.advance(QuoteCacheStore::ttl_seconds() + 1);
+    harness.provider.fail();
+    let quotes = harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+
+    assert_eq!(quotes[0].status, QuoteStatus::Stale);
+    assert_eq!(quotes[0].price, 100.0);
+    assert_eq!(
+        quotes[0].error_code.as_deref(),
+        Some("provider_unavailable")
+    );
+}
+
+#[tokio::test]
+async fn refresh_quotes_trims_the_cache_to_the_watchlist() {
+    let harness = Harness::new();
+    harness.watchlist().add("AAPL").unwrap();
+    harness.watchlist().add("MSFT").unwrap();
+    harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+
+    harness.watchlist().remove("MSFT").unwrap();
+    harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+
+    let cache = QuoteCacheStore::new(harness.config_dir().to_path_buf())
+        .load()
+        .unwrap();
+    assert_eq!(cache.keys().collect::<Vec<_>>(), ["AAPL"]);
+}
+
+#[tokio::test]
+async fn clearing_the_cache_removes_even_unreadable_files() {
+    let harness = Harness::new();
+    harness
+        .market_data()
+        .load_symbol_performance(&settings(), "AAPL", TimeRange::OneMonth, false)
+        .await
+        .unwrap();
+    let quotes_file = harness.config_dir().join("cache").join("quotes.json");
+    std::fs::create_dir_all(quotes_file.parent().unwrap()).unwrap();
+    std::fs::write(&quotes_file, "{ not json").unwrap();
+
+    CacheService::from_config_dir(harness.config_dir())
+        .clear()
+        .unwrap();
+
+    assert!(!quotes_file.exists());
+    let quote_cache = QuoteCacheStore::new(harness.config_dir().to_path_buf());
+    assert!(quote_cache.load().unwrap().is_empty());
+    let timeseries = TimeSeriesCacheStore::new(harness.config_dir().to_path_buf());
+    assert!(timeseries.list_files().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn pruning_keeps_watchlist_and_ledger_symbols() {
+    let harness = Harness::new();
+    for symbol in ["AAPL", "MSFT", "TSLA"] {
+        harness.watchlist().add(symbol).unwrap();
+    }
+    harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+    for symbol in ["AAPL", "MSFT", "TSLA"] {
+        harness
+            .market_data()
+            .load_symbol_performance(&settings(), symbol, TimeRange::OneMonth, false)
+            .await
+            .unwrap();
+    }
+    LedgerStore::new(harness.config_dir().to_path_buf())
+        .append(Transaction {
+            id: "tx-1".to_string(),
+            symbol: "MSFT".to_string(),
+            kind: TransactionKind::Buy,
+            date: "2025-10-01".to_string(),
+            quantity: 1.0,
+            price: 100.0,
+            fees: 0.0,
+            recorded_at: "2025-10-01T00:00:00Z".to_string(),
+        })
+        .unwrap();
+    harness.watchlist().remove("MSFT").unwrap();
+    harness.watchlist().remove("TSLA").unwrap();
+
+    let removed = CacheService::from_config_dir(harness.config_dir())
+        .prune_untracked()
+        .unwrap();
+
+    assert_eq!(removed.removed_quotes, ["TSLA"]);
+    assert_eq!(removed.removed_timeseries.len(), 1);
+    let cache = QuoteCacheStore::new(harness.config_dir().to_path_buf())
+        .load()
+        .unwrap();
+    let mut kept: Vec<&String> = cache.keys().collect();
+    kept.sort();
+    assert_eq!(kept, ["AAPL", "MSFT"]);
+}
+
+#[tokio::test]
+async fn offline_mode_never_calls_the_provider() {
+    let harness = Harness::new();
+    harness.watchlist().add("AAPL").unwrap();
+    let offline = AppSettings {
+        offline_mode: true,
+        ..settings()
+    };
+
+    let quotes = harness
+        .market_data()
+        .refresh_quotes(&offline)
+        .await
+        .unwrap();
+    let chart = harness
+        .market_data()
+        .load_symbol_performance(&offline, "AAPL", TimeRange::OneMonth, false)
+        .await;
+
+    assert_eq!(quotes[0].status, QuoteStatus::Error);
+    assert_eq!(quotes[0].error_code.as_deref(), Some(OFFLINE_REASON));
+    assert_eq!(chart.unwrap_err().code, OFFLINE_REASON);
+    assert!(harness.provider.calls().is_empty());
+}
+
+#[tokio::test]
+async fn offline_mode_serves_cached_data_without_an_api_key() {
+    let harness = Harness::new();
+    harness.watchlist().add("AAPL").unwrap();
+    harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+    harness
+        .market_data()
+        .load_symbol_performance(&settings(), "AAPL", TimeRange::OneMonth, false)
+        .await
+        .unwrap();
+
+    let offline_without_key = AppSettings {
+        offline_mode: true,
+        ..AppSettings::default()
+    };
+    let quotes = harness
+        .market_data()
+        .refresh_quotes(&offline_without_key)
+        .await
+        .unwrap();
+    let chart = harness
+        .market_data()
+        .load_symbol_performance(&offline_without_key, "AAPL", TimeRange::OneMonth, false)
+        .await
+        .unwrap();
+
+    assert_eq!(quotes[0].status, QuoteStatus::Stale);
+    assert_eq!(chart.status, QuoteStatus::Stale);
+    assert_eq!(chart.error_code, quotes[0].error_code);
+    assert_eq!(chart.error_code.as_deref(), Some(OFFLINE_REASON));
+    assert_eq!(harness.provider.calls().len(), 2);
+}
+
+#[tokio::test]
+async fn refresh_quotes_requires_an_api_key() {
+    let harness = Harness::new();
+    harness.watchlist().add("AAPL").unwrap();
+
+    let err = harness
+        .market_data()
+        .refresh_quotes(&AppSettings::default())
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.code, "invalid_settings");
+    assert_eq!(err.reason.as_deref(), Some("api_key_missing"));
+    assert!(harness.provider.calls().is_empty());
+}
+
+#[tokio::test]
+async fn symbol_performance_is_cached_and_refetched_on_demand() {
+    let harness = Harness::new();
+    let market_data = harness.market_data();
+
+    let (performance, source) = market_data
+        .load_symbol_performance_with_source(&settings(), "AAPL", TimeRange::OneMonth, false)
+        .await
+        .unwrap();
+    assert_eq!(source, SeriesSource::Fetched);
+    assert_eq!(performance.points.len(), TimeRange::OneMonth.bar_count());
+    assert_eq!(performance.status, QuoteStatus::Fresh);
+
+    // The week is a slice of the same daily series.
+    let (_, source) = market_data
+        .load_symbol_performance_with_source(&settings(), "AAPL", TimeRange::OneWeek, false)
+        .await
+        .unwrap();
+    assert_eq!(source, SeriesSource::Cache);
+
+    let (_, source) = market_data
+        .load_symbol_performance_with_source(&settings(), "AAPL", TimeRange::OneWeek, true)
+        .await
+        .unwrap();
+    assert_eq!(source, SeriesSource::Fetched);
+    assert_eq!(
+        harness.provider.calls(),
+        ["series:AAPL:30", "series:AAPL:1"]
+    );
+}
+
+#[tokio::test]
+async fn symbol_performance_falls_back_to_stale_bars_when_the_provider_fails() {
+    let harness = Harness::new();
+    harness
+        .market_data()
+        .load_symbol_performance(&settings(), "AAPL", TimeRange::OneMonth, false)
+        .await
+        .unwrap();
+
+    harness
+        .clock
+        .advance(TimeSeriesCacheStore::ttl_seconds() + 1);
+    harness.provider.fail();
+    let performance = harness
+        .market_data()
+        .load_symbol_performance(&settings(), "AAPL", TimeRange::OneMonth, false)
+        .await
+        .unwrap();
+
+    assert_eq!(performance.status, QuoteStatus::Stale);
+    assert_eq!(
+        performance.error_code.as_deref(),
+        Some("provider_unavailable")
+    );
+
+    let missing = harness
+        .market_data()
+        .load_symbol_performance(&settings(), "MSFT", TimeRange::OneMonth, false)
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code, "provider_unavailable");
+}
+
+#[test]
+fn watchlist_rejects_duplicates_and_unknown_symbols() {
+    let harness = Harness::new();
+    let watchlist = harness.watchlist();
+
+    watchlist.add(" aapl ").unwrap();
+    assert_eq!(watchlist.add("AAPL").unwrap_err().code, "symbol_exists");
+    assert_eq!(
+        watchlist.remove("MSFT").unwrap_err().code,
+        "symbol_not_found"
+    );
+    assert!(watchlist.remove("AAPL").unwrap().is_empty());
+    assert!(watchlist.list().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn saving_settings_evicts_charts_beyond_the_new_limit() {
+    let harness = Harness::new();
+    for symbol in ["AAPL", "MSFT"] {
+        harness
+            .market_data()
+            .load_symbol_performance(&settings(), symbol, TimeRange::OneMonth, false)
+            .await
+            .unwrap();
+    }
+
+    let service = harness.settings_service();
+    let (saved, evicted_count) = service
+        .save(AppSettingsInput {
+            cache_max_entries: 1,
+            ..AppSettingsInput::from(settings())
+        })
+        .unwrap();
+
+    assert_eq!(saved.cache_max_entries, 1);
+    assert_eq!(evicted_count, 1);
+    assert_eq!(service.load().unwrap().api_key, "test-key");
+    let remaining = TimeSeriesCacheStore::new(harness.config_dir().to_path_buf())
+        .list_files()
+        .unwrap();
+    assert_eq!(remaining.len(), 1);
+}
+
+#[tokio::test]
+async fn test_connection_uses_the_saved_api_key() {
+    let harness = Harness::new();
+    let service = harness.settings_service();
+
+    let err = service.test_connection().await.unwrap_err();
+    assert_eq!(err.reason.as_deref(), Some("api_key_missing"));
+
+    service.save(AppSettingsInput::from(settings())).unwrap();
+    assert!(service.test_connection().await.unwrap().ok);
+    assert_eq!(harness.provider.calls(), ["test_connection"]);
+}
+
+fn fresh_quote(symbol: &str, price: f64) -> QuoteSummary {
+    QuoteSummary {
+        symbol: symbol.to_string(),
+        price,
+        change_abs: None,
+        change_pct: None,
+        currency: Some("USD".to_string()),
+        last_updated_at: unix_secs_to_rfc3339(START as i64).unwrap(),
+        exchange_timezone: None,
+        status: QuoteStatus::Fresh,
+        error_code: None,
+        error_message: None,
+    }
+}
+
+#[tokio::test]
+async fn alert_rules_off_the_watchlist_are_quoted_and_evaluated() {
+    let harness = Harness::new();
+    harness.watchlist().add("AAPL").unwrap();
+    harness.add_alert_rule("MSFT", AlertCondition::PriceAbove { price: 99.0 });
+
+    let quotes = harness
+        .market_data()
+        .refresh_quotes(&settings())
+        .await
+        .unwrap();
+    let (_, events) = harness
+        .alerts()
+        .evaluate(&settings(), &quotes)
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].symbol, "MSFT");
+    assert_eq!(harness.provider.calls(), ["quote:AAPL", "quote:MSFT"]);
+    assert_eq!(harness.alert_store().load_history().unwrap(), events);
+}
+
+#[tokio::test]
+async fn crossing_rules_average_the_loaded_daily_series() {
+    let harness = Harness::new();
+    harness.add_alert_rule(
+        "AAPL",
+        AlertCondition::CrossesBelowMovingAverage { period: 20 },
+    );
+
+    let above = [fresh_quote("AAPL", 200.0)];
+    let (_, events) = harness
+        .alerts()
+        .evaluate(&settings(), &above)
+        .await
+        .unwrap();
+    assert!(events.is_empty());
+
+    let below = [fresh_quote("AAPL", 50.0)];
+    let (rules, events) = harness
+        .alerts()
+        .evaluate(&settings(), &below)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    let average = events[0].reference_value.unwrap();
+    assert!((100.0..=106.0).contains(&average));
+    assert!(!rules[0].armed);
+
+    let bars = TimeRange::ThreeMonths.bar_count();
+    assert_eq!(harness.provider.calls(), [format!("series:AAPL:{bars}")]);
+}
+
+/// A webhook on a port nothing listens on, so any delivery would fail.
+fn unreachable_webhook() -> Webhook {
+    Webhook {
+        id: "hook-1".to_string(),
+        name: "Chat".to_string(),
+        url: "http://127.0.0.1:9/hook".to_string(),
+        headers: Default::default(),
+        body_template: None,
+        on_alert: true,
+        on_refresh: true,
+        enabled: true,
+        created_at: unix_secs_to_rfc3339(START as i64).unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn webhooks_are_not_sent_while_offline() {
+    let harness = Harness::new();
+    harness
+        .webhook_store()
+        .save_webhooks(&[unreachable_webhook()])
+        .unwrap();
+    let offline = AppSettings {
+        offline_mode: true,
+        ..settings()
+    };
+    let triggers = [WebhookTrigger::Refresh(vec![fresh_quote("AAPL", 100.0)])];
+
+    let deliveries = harness
+        .webhooks()
+        .dispatch(&offline, &triggers)
+        .await
+        .unwrap();
+    assert!(deliveries.is_empty());
+
+    let err = harness
+        .webhooks()
+        .send_test(&offline, "hook-1")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, OFFLINE_REASON);
+    assert_eq!(err.reason.as_deref(), Some("webhook_not_sent"));
+    assert!(
+        harness
+            .webhook_store()
+            .load_deliveries()
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn webhooks_are_not_sent_while_the_connectivity_circuit_is_open() {
+    let harness = Harness::new();
+    harness
+        .webhook_store()
+        .save_webhooks(&[unreachable_webhook()])
+        .unwrap();
+    let failure: Result<(), AppError> = Err(AppError::provider("network_connect_error"));
+    for _ in 0..3 {
+        harness
+            .monitor
+            .record_outcome(&failure, harness.clock.now());
+    }
+    let triggers = [WebhookTrigger::Refresh(vec![fresh_quote("AAPL", 100.0)])];
+
+    let deliveries = harness
+        .webhooks()
+        .dispatch(&settings(), &triggers)
+        .await
+        .unwrap();
+    assert!(deliveries.is_empty());
+    assert!(
+        harness
+            .webhook_store()
+            .load_deliveries()
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn provider_status_reports_errors_seen_before_the_probe() {
+    let harness = Harness::new();
+    let service = harness.settings_service();
+    service.save(AppSettingsInput::from(settings())).unwrap();
+    let earlier: Result<(), AppError> = Err(AppError::provider("rate_limited"));
+    metrics().record_provider_call("symbol_search", &earlier);
+    harness.provider.fail();
+
+    let report = service
+        .provider_status(&harness.monitor, harness.clock.as_ref())
+        .await
+        .unwrap();
+
+    assert_eq!(report.endpoints.len(), 3);
+    for endpoint in &report.endpoints {
+        assert!(!endpoint.ok);
+        assert!(endpoint.latency_ms.is_some());
+        assert_eq!(endpoint.error_code.as_deref(), Some("provider_unavailable"));
+    }
+    let last_errors: Vec<Option<&str>> = report
+        .endpoints
+        .iter()
+        .map(|endpoint| {
+            endpoint
+                .last_error
+                .as_ref()
+                .map(|record| record.code.as_str())
+        })
+        .collect();
+    assert_eq!(last_errors, [None, None, Some("rate_limited")]);
+}
+
+#[tokio::test]
+async fn provider_status_in_offline_mode_probes_nothing() {
+    let harness = Harness::new();
+    let service = harness.settings_service();
+    service
+        .save(AppSettingsInput {
+            offline_mode: true,
+            ..AppSettingsInput::from(settings())
+        })
+        .unwrap();
+
+    let report = service
+        .provider_status(&harness.monitor, harness.clock.as_ref())
+        .await
+        .unwrap();
+
+    for endpoint in &report.endpoints {
+        assert!(!endpoint.ok);
+        assert_eq!(endpoint.latency_ms, None);
+        assert_eq!(endpoint.error_code.as_deref(), Some(OFFLINE_REASON));
+    }
+    assert!(harness.provider.calls().is_empty());
+}
@@ -1,19 +1,45 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type {
+  AlertEvent,
+  AlertRule,
+  AlertRuleInput,
   AppError,
   AppSettings,
+  BenchmarkComparison,
+  CacheInvalidationResult,
+  CacheStats,
+  ComparisonSeries,
+  ConnectivityStatus,
+  CorrelationMatrix,
+  DiagnosticsExport,
+  GapFill,
+  IndicatorSet,
+  IndicatorSpec,
+  LedgerReport,
+  Lot,
+  MetricsSnapshot,
+  PortfolioPerformance,
+  PortfolioSummary,
+  ProviderStatusReport,
   ProviderTestResult,
   QuoteSummary,
+  SymbolAnalytics,
   SymbolPerformance,
   TimeRange,
+  Transaction,
+  TransactionInput,
   WatchlistItem,
+  Webhook,
+  WebhookDelivery,
+  WebhookInput,
 } from '../types';
 
 function normalizeAppError(rawError: unknown): AppError {
   if (typeof rawError === 'object' && rawError !== null) {
     const maybeError = rawError as Record<string, unknown>;
     if (typeof maybeError.code === 'string' && typeof maybeError.message === 'string') {
-      return { code: maybeError.code, message: maybeError.message };
+      return { ...maybeError, code: maybeError.code, message: maybeError.message } as AppError;
     }
 
     if (typeof maybeError.error === 'string') {
@@ -62,6 +88,10 @@ export const tauriClient = {
     return await invokeWithError<ProviderTestResult>('test_provider_connection');
   },
 
+  getProviderStatus: async (): Promise<ProviderStatusReport> => {
+    return await invokeWithError<ProviderStatusReport>('get_provider_status');
+  },
+
   getWatchlist: async (): Promise<WatchlistItem[]> => {
     return await invokeWithError<WatchlistItem[]>('get_watchlist');
   },
@@ -101,4 +131,179 @@ export const tauriClient = {
   clearCache: async (): Promise<void> => {
     return await invokeWithError<void>('clear_cache');
   },
+
+  getCacheStats: async (): Promise<CacheStats> => {
+    return await invokeWithError<CacheStats>('get_cache_stats');
+  },
+
+  getMetrics: async (writePrometheus?: boolean): Promise<MetricsSnapshot> => {
+    return await invokeWithError<MetricsSnapshot>('get_metrics', { writePrometheus });
+  },
+
+  exportDiagnostics: async (path: string): Promise<DiagnosticsExport> => {
+    return await invokeWithError<DiagnosticsExport>('export_diagnostics', { path });
+  },
+
+  invalidateSymbolCache: async (symbol: string): Promise<CacheInvalidationResult> => {
+    return await invokeWithError<CacheInvalidationResult>('invalidate_symbol_cache', { symbol });
+  },
+
+  invalidateRangeCache: async (
+    range: TimeRange,
+    symbol?: string,
+  ): Promise<CacheInvalidationResult> => {
+    return await invokeWithError<CacheInvalidationResult>('invalidate_range_cache', {
+      range,
+      symbol,
+    });
+  },
+
+  clearQuoteCache: async (): Promise<CacheInvalidationResult> => {
+    return await invokeWithError<CacheInvalidationResult>('clear_quote_cache');
+  },
+
+  clearTimeseriesCache: async (): Promise<CacheInvalidationResult> => {
+    return await invokeWithError<CacheInvalidationResult>('clear_timeseries_cache');
+  },
+
+  pruneCacheToWatchlist: async (): Promise<CacheInvalidationResult> => {
+    return await invokeWithError<CacheInvalidationResult>('prune_cache_to_watchlist');
+  },
+
+  getConnectivityStatus: async (): Promise<ConnectivityStatus> => {
+    return await invokeWithError<ConnectivityStatus>('get_connectivity_status');
+  },
+
+  setOfflineMode: async (enabled: boolean): Promise<ConnectivityStatus> => {
+    return await invokeWithError<ConnectivityStatus>('set_offline_mode', { enabled });
+  },
+
+  getPortfolioLots: async (): Promise<Lot[]> => {
+    return await invokeWithError<Lot[]>('get_portfolio_lots');
+  },
+
+  getTransactions: async (): Promise<Transaction[]> => {
+    return await invokeWithError<Transaction[]>('get_transactions');
+  },
+
+  addTransaction: async (transaction: TransactionInput): Promise<Transaction[]> => {
+    return await invokeWithError<Transaction[]>('add_transaction', { transaction });
+  },
+
+  updateTransaction: async (
+    id: string,
+    transaction: TransactionInput,
+  ): Promise<Transaction[]> => {
+    return await invokeWithError<Transaction[]>('update_transaction', { id, transaction });
+  },
+
+  deleteTransaction: async (id: string): Promise<Transaction[]> => {
+    return await invokeWithError<Transaction[]>('delete_transaction', { id });
+  },
+
+  getLedgerReport: async (): Promise<LedgerReport> => {
+    return await invokeWithError<LedgerReport>('get_ledger_report');
+  },
+
+  getPortfolioSummary: async (): Promise<PortfolioSummary> => {
+    return await invokeWithError<PortfolioSummary>('get_portfolio_summary');
+  },
+
+  getPortfolioPerformance: async (range: TimeRange): Promise<PortfolioPerformance> => {
+    return await invokeWithError<PortfolioPerformance>('get_portfolio_performance', { range });
+  },
+
+  getBenchmarkComparison: async (
+    benchmark: string,
+    range: TimeRange,
+    symbol?: string,
+  ): Promise<BenchmarkComparison> => {
+    return await invokeWithError<BenchmarkComparison>('get_benchmark_comparison', {
+      symbol: symbol ?? null,
+      benchmark,
+      range,
+    });
+  },
+
+  getComparison: async (
+    symbols: string[],
+    range: TimeRange,
+    gapFill?: GapFill,
+  ): Promise<ComparisonSeries> => {
+    return await invokeWithError<ComparisonSeries>('get_comparison', {
+      symbols,
+      range,
+      gapFill: gapFill ?? null,
+    });
+  },
+
+  getIndicators: async (
+    symbol: string,
+    range: TimeRange,
+    specs: IndicatorSpec[],
+  ): Promise<IndicatorSet> => {
+    return await invokeWithError<IndicatorSet>('get_indicators', { symbol, range, specs });
+  },
+
+  getSymbolAnalytics: async (symbol: string, range: TimeRange): Promise<SymbolAnalytics> => {
+    return await invokeWithError<SymbolAnalytics>('get_symbol_analytics', { symbol, range });
+  },
+
+  getCorrelationMatrix: async (
+    range: TimeRange,
+    symbols?: string[],
+  ): Promise<CorrelationMatrix> => {
+    return await invokeWithError<CorrelationMatrix>('get_correlation_matrix', {
+      range,
+      symbols: symbols ?? null,
+    });
+  },
+
+  getAlertRules: async (): Promise<AlertRule[]> => {
+    return await invokeWithError<AlertRule[]>('get_alert_rules');
+  },
+
+  addAlertRule: async (rule: AlertRuleInput): Promise<AlertRule[]> => {
+    return await invokeWithError<AlertRule[]>('add_alert_rule', { rule });
+  },
+
+  updateAlertRule: async (id: string, rule: AlertRuleInput): Promise<AlertRule[]> => {
+    return await invokeWithError<AlertRule[]>('update_alert_rule', { id, rule });
+  },
+
+  deleteAlertRule: async (id: string): Promise<AlertRule[]> => {
+    return await invokeWithError<AlertRule[]>('delete_alert_rule', { id });
+  },
+
+  getAlertHistory: async (): Promise<AlertEvent[]> => {
+    return await invokeWithError<AlertEvent[]>('get_alert_history');
+  },
+
+  onAlertTriggered: async (handler: (event: AlertEvent) => void): Promise<UnlistenFn> => {
+    return await listen<AlertEvent>('alert-triggered', (event) => handler(event.payload));
+  },
+
+  getWebhooks: async (): Promise<Webhook[]> => {
+    return await invokeWithError<Webhook[]>('get_webhooks');
+  },
+
+  addWebhook: async (webhook: WebhookInput): Promise<Webhook[]> => {
+    return await invokeWithError<Webhook[]>('add_webhook', { webhook });
+  },
+
+  updateWebhook: async (id: string, webhook: WebhookInput): Promise<Webhook[]> => {
+    return await invokeWithError<Webhook[]>('update_webhook', { id, webhook });
+  },
+
+  deleteWebhook: async (id: string): Promise<Webhook[]> => {
+    return await invokeWithError<Webhook[]>('delete_webhook', { id });
+  },
+
+  getWebhookDeliveries: async (): Promise<WebhookDelivery[]> => {
+    return await invokeWithError<WebhookDelivery[]>('get_webhook_deliveries');
+  },
+
+  testWebhook: async (id: string): Promise<WebhookDelivery> => {
+    return await invokeWithError<WebhookDelivery>('test_webhook', { id });
+  },
 };
@@ -17,6 +17,7 @@ export interface QuoteSummary {
   changePct?: number;
   currency?: string;
   lastUpdatedAt: string;
+  exchangeTimezone?: string;
   status: QuoteStatus;
   errorCode?: string;
   errorMessage?: string;
@@ -25,6 +26,10 @@ export interface QuoteSummary {
 export interface PricePoint {
   ts: string;
   close: number;
+  open?: number;
+  high?: number;
+  low?: number;
+  volume?: number;
 }
 
 export interface SymbolPerformance {
@@ -36,7 +41,10 @@ export interface SymbolPerformance {
   start: number;
   end: number;
   lastUpdatedAt: string;
+  exchangeTimezone?: string;
   status: QuoteStatus;
+  errorCode?: string;
+  errorMessage?: string;
 }
 
 export interface AppSettings {
@@ -45,6 +53,23 @@ export interface AppSettings {
   defaultRange: TimeRange;
   autoRefreshSeconds: number;
   notificationsEnabled: boolean;
+  cacheMaxEntries?: number;
+  cacheMaxMegabytes?: number;
+  offlineMode?: boolean;
+  lotMatching?: LotMatching;
+  riskFreeRatePct?: number;
+  quietHours?: QuietHours | null;
+  logLevel?: LogLevel;
+  locale?: Locale;
+}
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+export type Locale = 'en' | 'de';
+
+export interface QuietHours {
+  start: string;
+  end: string;
 }
 
 export interface ProviderTestResult {
@@ -53,7 +78,403 @@ export interface ProviderTestResult {
   message: string;
 }
 
+export interface CacheEntryInfo {
+  key: string;
+  cachedAt: string;
+}
+
+export interface CacheStoreStats {
+  entryCount: number;
+  bytesOnDisk: number;
+  oldestEntry?: CacheEntryInfo;
+  newestEntry?: CacheEntryInfo;
+}
+
+export interface CacheStats {
+  quotes: CacheStoreStats;
+  timeseries: CacheStoreStats;
+}
+
+export interface CacheInvalidationResult {
+  removedQuotes: string[];
+  removedTimeseries: string[];
+}
+
+export type ProviderEndpoint = 'quote' | 'time_series' | 'symbol_search';
+
+export type CircuitState = 'closed' | 'open' | 'halfOpen';
+
+export interface ApiCredits {
+  used?: number;
+  left?: number;
+}
+
+export interface EndpointStatus {
+  endpoint: ProviderEndpoint;
+  ok: boolean;
+  latencyMs?: number;
+  errorCode?: string;
+  errorMessage?: string;
+  lastError?: ProviderErrorRecord;
+}
+
+export interface ProviderStatusReport {
+  provider: AppProvider;
+  checkedAt: string;
+  circuit: CircuitState;
+  consecutiveFailures: number;
+  retryAt?: string;
+  credits: ApiCredits;
+  endpoints: EndpointStatus[];
+}
+
+export interface HistogramBucket {
+  leMs: number;
+  count: number;
+}
+
+export interface CommandMetrics {
+  command: string;
+  count: number;
+  errorCount: number;
+  totalMs: number;
+  buckets: HistogramBucket[];
+}
+
+export interface ProviderCallMetrics {
+  endpoint: string;
+  code: string;
+  count: number;
+}
+
+export interface CacheMetrics {
+  cache: string;
+  hits: number;
+  misses: number;
+  hitRatio?: number;
+}
+
+export interface ProviderErrorRecord {
+  endpoint: string;
+  code: string;
+  occurredAt: string;
+}
+
+export interface DiagnosticsExport {
+  path: string;
+  sizeBytes: number;
+  entries: string[];
+}
+
+export interface MetricsSnapshot {
+  collectedAt: string;
+  commands: CommandMetrics[];
+  providerCalls: ProviderCallMetrics[];
+  caches: CacheMetrics[];
+  bytesFetched: number;
+  prometheusFile?: string;
+}
+
+export interface ConnectivityStatus {
+  offline: boolean;
+  manualOffline: boolean;
+  autoOffline: boolean;
+  consecutiveFailures: number;
+  retryAt?: string;
+}
+
+export interface Lot {
+  id: string;
+  symbol: string;
+  quantity: number;
+  price: number;
+  purchasedOn: string;
+  fees: number;
+}
+
+export type LotMatching = 'fifo' | 'lifo' | 'average';
+
+export type TransactionKind = 'buy' | 'sell' | 'dividend' | 'split';
+
+export interface Transaction {
+  id: string;
+  symbol: string;
+  kind: TransactionKind;
+  date: string;
+  quantity: number;
+  price: number;
+  fees: number;
+  recordedAt: string;
+}
+
+export interface TransactionInput {
+  symbol: string;
+  kind: TransactionKind;
+  date: string;
+  quantity?: number;
+  price?: number;
+  fees?: number;
+}
+
+export interface RealizedGain {
+  transactionId: string;
+  symbol: string;
+  date: string;
+  quantity: number;
+  proceeds: number;
+  costBasis: number;
+  gain: number;
+}
+
+export interface DividendPayment {
+  transactionId: string;
+  symbol: string;
+  date: string;
+  sharesHeld: number;
+  amount: number;
+}
+
+export interface LedgerReport {
+  method: LotMatching;
+  openLots: Lot[];
+  realized: RealizedGain[];
+  dividends: DividendPayment[];
+  totalRealizedGain: number;
+  totalDividendIncome: number;
+}
+
+export interface PositionSummary {
+  symbol: string;
+  quantity: number;
+  costBasis: number;
+  averageCost: number;
+  lastPrice?: number;
+  marketValue?: number;
+  unrealizedPnl?: number;
+  unrealizedPnlPct?: number;
+  quoteStatus: QuoteStatus;
+  lastUpdatedAt?: string;
+}
+
+export interface PortfolioSummary {
+  positions: PositionSummary[];
+  totalMarketValue: number;
+  totalCostBasis: number;
+  totalUnrealizedPnl: number;
+  totalUnrealizedPnlPct?: number;
+  unpricedSymbols: string[];
+  totalRealizedGain: number;
+  totalDividendIncome: number;
+}
+
+export interface PortfolioValuePoint {
+  ts: string;
+  value: number;
+  netFlow: number;
+}
+
+export interface PortfolioPerformance {
+  range: TimeRange;
+  points: PortfolioValuePoint[];
+  startValue: number;
+  endValue: number;
+  netFlows: number;
+  timeWeightedReturnPct?: number;
+  moneyWeightedReturnPct?: number;
+  status: QuoteStatus;
+  missingSymbols: string[];
+}
+
+export interface BenchmarkPoint {
+  ts: string;
+  subject: number;
+  benchmark: number;
+}
+
+export interface BenchmarkComparison {
+  subject: string;
+  benchmark: string;
+  range: TimeRange;
+  points: BenchmarkPoint[];
+  subjectReturnPct: number;
+  benchmarkReturnPct: number;
+  excessReturnPct: number;
+  beta?: number;
+  correlation?: number;
+  status: QuoteStatus;
+}
+
+export type GapFill = 'forward' | 'none' | 'intersect';
+
+export interface ComparisonLine {
+  symbol: string;
+  values: (number | null)[];
+  status: QuoteStatus;
+  errorCode?: string;
+  errorMessage?: string;
+}
+
+export interface ComparisonSeries {
+  range: TimeRange;
+  gapFill: GapFill;
+  timestamps: string[];
+  lines: ComparisonLine[];
+  missingSymbols: string[];
+}
+
+export type IndicatorSpec =
+  | { kind: 'sma'; period: number }
+  | { kind: 'ema'; period: number }
+  | { kind: 'rsi'; period: number }
+  | { kind: 'macd'; fastPeriod: number; slowPeriod: number; signalPeriod: number }
+  | { kind: 'bollinger'; period: number; stdDevs: number }
+  | { kind: 'atr'; period: number }
+  | { kind: 'vwap' };
+
+export interface IndicatorLine {
+  name: string;
+  values: (number | null)[];
+}
+
+export interface IndicatorResult {
+  spec: IndicatorSpec;
+  lines: IndicatorLine[];
+}
+
+export interface IndicatorSet {
+  symbol: string;
+  range: TimeRange;
+  timestamps: string[];
+  indicators: IndicatorResult[];
+  status: QuoteStatus;
+  errorCode?: string;
+  errorMessage?: string;
+}
+
+export interface PeriodReturn {
+  ts: string;
+  returnPct: number;
+}
+
+export interface SymbolAnalytics {
+  symbol: string;
+  range: TimeRange;
+  riskFreeRatePct: number;
+  periodReturnPct: number;
+  annualizedVolatilityPct?: number;
+  maxDrawdownPct: number;
+  drawdownPeakAt?: string;
+  drawdownTroughAt?: string;
+  sharpeRatio?: number;
+  sortinoRatio?: number;
+  bestPeriod?: PeriodReturn;
+  worstPeriod?: PeriodReturn;
+  status: QuoteStatus;
+  errorCode?: string;
+  errorMessage?: string;
+}
+
+export interface CorrelationMatrix {
+  range: TimeRange;
+  symbols: string[];
+  matrix: (number | null)[][];
+  overlap: number[][];
+  minOverlap: number;
+  insufficientOverlap: string[];
+  fetchedSymbols: string[];
+  missingSymbols: string[];
+}
+
+export type ErrorKind = 'validation' | 'provider' | 'persistence' | 'internal';
+
+export type UserAction =
+  | 'fixInput'
+  | 'updateApiKey'
+  | 'waitAndRetry'
+  | 'checkConnection'
+  | 'retry'
+  | 'checkStorage'
+  | 'reportIssue';
+
 export interface AppError {
   code: string;
   message: string;
+  reason?: string | null;
+  args?: Record<string, string>;
+  kind?: ErrorKind;
+  retryable?: boolean;
+  retryAfterSeconds?: number | null;
+  action?: UserAction;
+}
+
+export type AlertCondition =
+  | { kind: 'priceAbove'; price: number }
+  | { kind: 'priceBelow'; price: number }
+  | { kind: 'percentChange'; thresholdPct: number }
+  | { kind: 'crossesAboveMovingAverage'; period: number }
+  | { kind: 'crossesBelowMovingAverage'; period: number };
+
+export interface AlertRule {
+  id: string;
+  symbol: string;
+  condition: AlertCondition;
+  enabled: boolean;
+  notify: boolean;
+  cooldownSeconds: number;
+  createdAt: string;
+  armed: boolean;
+  lastTriggeredAt?: string;
+}
+
+export interface AlertRuleInput {
+  symbol: string;
+  condition: AlertCondition;
+  enabled?: boolean;
+  notify?: boolean;
+  cooldownSeconds?: number;
+}
+
+export interface AlertEvent {
+  id: string;
+  ruleId: string;
+  symbol: string;
+  condition: AlertCondition;
+  price: number;
+  referenceValue?: number;
+  message: string;
+  triggeredAt: string;
+}
+
+export interface Webhook {
+  id: string;
+  name: string;
+  url: string;
+  headers: Record<string, string>;
+  bodyTemplate?: string;
+  onAlert: boolean;
+  onRefresh: boolean;
+  enabled: boolean;
+  createdAt: string;
+}
+
+export interface WebhookInput {
+  name: string;
+  url: string;
+  headers?: Record<string, string>;
+  bodyTemplate?: string;
+  onAlert?: boolean;
+  onRefresh?: boolean;
+  enabled?: boolean;
+}
+
+export interface WebhookDelivery {
+  id: string;
+  webhookId: string;
+  event: 'alert' | 'refresh';
+  attempts: number;
+  statusCode?: number;
+  success: boolean;
+  error?: string;
+  startedAt: string;
+  finishedAt: string;
 }